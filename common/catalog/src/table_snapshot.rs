@@ -70,7 +70,7 @@ pub struct SegmentInfo {
     pub summary: Stats,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct Stats {
     pub row_count: u64,
     pub block_count: u64,
@@ -96,7 +96,7 @@ pub struct BlockLocation {
     pub meta_size: u64,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 pub struct ColStats {
     pub min: DataValue,
     pub max: DataValue,
@@ -0,0 +1,70 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A token bucket limiting the bytes per second of the storage IO.
+///
+/// Instead of refilling tokens, the limiter keeps the time at which all the bytes reserved so
+/// far have been paid for. A reservation moves that time forward by `bytes / bytes_per_second`
+/// and returns how long the caller has to wait, which is zero while the bucket is not empty.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    bytes_per_second: u64,
+    base: Instant,
+    // Nanoseconds since `base` until which the bandwidth has been reserved.
+    reserved_until_ns: AtomicU64,
+}
+
+impl BandwidthLimiter {
+    pub fn create(bytes_per_second: u64) -> Self {
+        BandwidthLimiter {
+            bytes_per_second: std::cmp::max(bytes_per_second, 1),
+            base: Instant::now(),
+            reserved_until_ns: AtomicU64::new(0),
+        }
+    }
+
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// Reserve the bandwidth of `bytes`, returns how long to wait before the IO.
+    pub fn reserve(&self, bytes: usize) -> Duration {
+        self.reserve_at(bytes, Instant::now())
+    }
+
+    /// Same as `reserve`, with `now` given by the caller.
+    pub fn reserve_at(&self, bytes: usize, now: Instant) -> Duration {
+        let now_ns = now.saturating_duration_since(self.base).as_nanos() as u64;
+        let cost_ns = (bytes as u128 * 1_000_000_000 / self.bytes_per_second as u128) as u64;
+
+        let mut reserved_until = self.reserved_until_ns.load(Ordering::Relaxed);
+        loop {
+            let start = std::cmp::max(reserved_until, now_ns);
+            match self.reserved_until_ns.compare_exchange_weak(
+                reserved_until,
+                start.saturating_add(cost_ns),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Duration::from_nanos(start - now_ns),
+                Err(current) => reserved_until = current,
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use rand::Rng;
+
+use crate::BandwidthLimiter;
+use crate::DalContext;
+use crate::DataAccessorWithMetric;
+use crate::Local;
+
+const MB: usize = 1024 * 1024;
+
+#[test]
+fn test_bandwidth_limiter_virtual_clock() -> Result<()> {
+    let limiter = BandwidthLimiter::create(10 * MB as u64);
+
+    // Read 100MB in 1MB chunks, the virtual clock only advances by the waits.
+    let start = Instant::now();
+    let mut now = start;
+    for _ in 0..100 {
+        now += limiter.reserve_at(MB, now);
+    }
+    // The last chunk is paid for 100ms after its wait.
+    let elapsed = now - start + Duration::from_millis(100);
+    assert!(elapsed >= Duration::from_millis(9_900), "{:?}", elapsed);
+    assert!(elapsed <= Duration::from_millis(10_100), "{:?}", elapsed);
+    Ok(())
+}
+
+#[test]
+fn test_bandwidth_limiter_idle() -> Result<()> {
+    let limiter = BandwidthLimiter::create(10 * MB as u64);
+
+    let start = Instant::now();
+    assert_eq!(limiter.reserve_at(MB, start), Duration::ZERO);
+    assert_eq!(limiter.reserve_at(MB, start), Duration::from_millis(100));
+
+    // Nothing to wait once the reserved bandwidth has been used up.
+    let later = start + Duration::from_secs(1);
+    assert_eq!(limiter.reserve_at(MB, later), Duration::ZERO);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dal_context_throttle() -> Result<()> {
+    let unlimited = Arc::new(DalContext::create());
+    for _ in 0..100 {
+        unlimited.throttle_read(100 * MB).await;
+        unlimited.throttle_write(100 * MB).await;
+    }
+    assert_eq!(unlimited.get_throttle_wait(), Duration::ZERO);
+
+    // 1MB/s for reads, writes are not limited.
+    let ctx = Arc::new(DalContext::create().with_bandwidth_limit(MB as u64, 0));
+    ctx.throttle_write(100 * MB).await;
+    assert_eq!(ctx.get_throttle_wait(), Duration::ZERO);
+
+    let start = Instant::now();
+    ctx.throttle_read(MB / 20).await;
+    ctx.throttle_read(MB / 20).await;
+    assert!(start.elapsed() >= Duration::from_millis(40));
+    assert!(ctx.get_throttle_wait() >= Duration::from_millis(40));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_data_accessor_bandwidth_limit() -> Result<()> {
+    let root = std::env::temp_dir().join(format!(
+        "dal_bandwidth_test_{}",
+        rand::thread_rng().gen::<u64>()
+    ));
+    std::fs::create_dir_all(&root)?;
+    let root = root.canonicalize()?;
+
+    // 100KB/s for reads and writes, the wrapper throttles whatever the scheme is.
+    let ctx = Arc::new(DalContext::create().with_bandwidth_limit(100 * 1024, 100 * 1024));
+    let local = Arc::new(Local::new(root.to_str().unwrap()));
+    let da = DataAccessorWithMetric::wrap(local, "local", ctx.clone());
+
+    let content = vec![1u8; 5 * 1024];
+    da.put("file_0", content.clone()).await?;
+    let chunks = vec![Ok(content.clone()), Ok(content.clone())];
+    let stream = futures::stream::iter::<Vec<std::io::Result<Vec<u8>>>>(chunks);
+    da.put_stream("sub/file_1", Box::new(stream), content.len() * 2)
+        .await?;
+    assert!(ctx.get_throttle_wait() >= Duration::from_millis(80));
+
+    let wait = ctx.get_throttle_wait();
+    let mut stream = da.get_input_stream("file_0", None).await?;
+    let mut read = vec![];
+    stream.read_to_end(&mut read).await?;
+    assert_eq!(read, content);
+    assert_eq!(da.get("sub/file_1").await?.len(), content.len() * 2);
+    assert!(ctx.get_throttle_wait() >= wait + Duration::from_millis(80));
+
+    std::fs::remove_dir_all(&root)?;
+    Ok(())
+}
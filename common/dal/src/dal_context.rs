@@ -0,0 +1,256 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio::sync::OwnedSemaphorePermit;
+use common_base::tokio::sync::Semaphore;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::BandwidthLimiter;
+use crate::IoTrace;
+use crate::IoTraceEntry;
+
+/// Upper bounds(in milliseconds) of the read latency histogram buckets,
+/// the last bucket counts everything above the last bound.
+pub const READ_LATENCY_BUCKETS_MS: [u64; 8] = [1, 5, 10, 50, 100, 500, 1000, 5000];
+
+/// Per-query statistics of the data access layer.
+#[derive(Debug, Default)]
+pub struct DalContext {
+    read_bytes: AtomicUsize,
+    read_count: AtomicUsize,
+    max_read_latency_us: AtomicU64,
+    read_latency_buckets: [AtomicU64; READ_LATENCY_BUCKETS_MS.len() + 1],
+    open_files_limiter: Option<Arc<Semaphore>>,
+    open_files: AtomicUsize,
+    max_open_files: AtomicUsize,
+    open_files_wait_count: AtomicUsize,
+    read_limiter: Option<Arc<BandwidthLimiter>>,
+    write_limiter: Option<Arc<BandwidthLimiter>>,
+    throttle_wait_us: AtomicU64,
+    io_trace: Option<Arc<IoTrace>>,
+}
+
+impl DalContext {
+    pub fn create() -> Self {
+        Self::default()
+    }
+
+    /// Create a context which allows at most `limit` files to be opened at the same time,
+    /// 0 means no limit.
+    pub fn with_open_files_limit(limit: usize) -> Self {
+        DalContext {
+            open_files_limiter: match limit {
+                0 => None,
+                _ => Some(Arc::new(Semaphore::new(limit))),
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Limit the read and write bandwidth of the storage IO in bytes per second,
+    /// 0 means no limit.
+    pub fn with_bandwidth_limit(
+        self,
+        read_bytes_per_second: u64,
+        write_bytes_per_second: u64,
+    ) -> Self {
+        let limiter = |bytes_per_second| match bytes_per_second {
+            0 => None,
+            _ => Some(Arc::new(BandwidthLimiter::create(bytes_per_second))),
+        };
+
+        DalContext {
+            read_limiter: limiter(read_bytes_per_second),
+            write_limiter: limiter(write_bytes_per_second),
+            ..self
+        }
+    }
+
+    /// Record the storage requests of the query, keeps at most the last `capacity` of them.
+    pub fn with_io_trace(self, capacity: usize) -> Self {
+        DalContext {
+            io_trace: Some(Arc::new(IoTrace::create(capacity))),
+            ..self
+        }
+    }
+
+    /// The io trace of the query, None if it is off.
+    pub fn get_io_trace(&self) -> Option<Arc<IoTrace>> {
+        self.io_trace.clone()
+    }
+
+    #[inline]
+    pub fn trace_io(&self, entry: IoTraceEntry) {
+        if let Some(io_trace) = &self.io_trace {
+            io_trace.record(entry);
+        }
+    }
+
+    pub fn inc_read_bytes(&self, bytes: usize) {
+        self.read_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn get_read_bytes(&self) -> usize {
+        self.read_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn get_read_count(&self) -> usize {
+        self.read_count.load(Ordering::Relaxed)
+    }
+
+    /// Record the elapsed time of one underlying read call.
+    pub fn observe_read_latency(&self, elapsed: Duration) {
+        let elapsed_us = elapsed.as_micros() as u64;
+        let elapsed_ms = elapsed_us / 1000;
+        let bucket = READ_LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| elapsed_ms < *bound)
+            .unwrap_or(READ_LATENCY_BUCKETS_MS.len());
+
+        self.read_count.fetch_add(1, Ordering::Relaxed);
+        self.read_latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.max_read_latency_us
+            .fetch_max(elapsed_us, Ordering::Relaxed);
+    }
+
+    /// The max latency of single read call of the query.
+    pub fn get_max_read_latency(&self) -> Duration {
+        Duration::from_micros(self.max_read_latency_us.load(Ordering::Relaxed))
+    }
+
+    /// Count of read calls in each bucket, see [READ_LATENCY_BUCKETS_MS].
+    pub fn get_read_latency_histogram(&self) -> Vec<u64> {
+        self.read_latency_buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    /// Acquire a permit before opening a file, waits if the limit has been reached.
+    ///
+    /// The permit is released when the returned guard is dropped, callers must hold at most
+    /// one guard at a time, otherwise they may deadlock each other.
+    pub async fn acquire_open_file(self: &Arc<Self>) -> Result<OpenFileGuard> {
+        let permit = match &self.open_files_limiter {
+            None => None,
+            Some(limiter) => match limiter.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    self.open_files_wait_count.fetch_add(1, Ordering::Relaxed);
+                    let permit = limiter.clone().acquire_owned().await.map_err(|cause| {
+                        ErrorCode::UnknownException(format!(
+                            "Cannot acquire open file permit: {}",
+                            cause
+                        ))
+                    })?;
+                    Some(permit)
+                }
+            },
+        };
+
+        let open_files = self.open_files.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_open_files.fetch_max(open_files, Ordering::Relaxed);
+        Ok(OpenFileGuard {
+            _permit: permit,
+            ctx: self.clone(),
+        })
+    }
+
+    /// The max number of files opened at the same time by the query.
+    pub fn get_max_open_files(&self) -> usize {
+        self.max_open_files.load(Ordering::Relaxed)
+    }
+
+    /// How many times opening a file had to wait for the open files limit.
+    pub fn get_open_files_wait_count(&self) -> usize {
+        self.open_files_wait_count.load(Ordering::Relaxed)
+    }
+
+    /// Reserve the read bandwidth of `bytes`, returns how long to wait before the next read.
+    ///
+    /// The wait is accounted in the throttle wait of the query, the caller must wait it out.
+    pub fn reserve_read(&self, bytes: usize) -> Option<Duration> {
+        self.reserve(&self.read_limiter, bytes)
+    }
+
+    /// Wait until the read bandwidth of `bytes` is available.
+    pub async fn throttle_read(&self, bytes: usize) {
+        if let Some(wait) = self.reserve_read(bytes) {
+            common_base::tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Wait until the write bandwidth of `bytes` is available.
+    pub async fn throttle_write(&self, bytes: usize) {
+        if let Some(wait) = self.reserve(&self.write_limiter, bytes) {
+            common_base::tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// The total time the IO of the query has been delayed by the bandwidth limits.
+    pub fn get_throttle_wait(&self) -> Duration {
+        Duration::from_micros(self.throttle_wait_us.load(Ordering::Relaxed))
+    }
+
+    /// One line of the storage stats of the query for the log, None if it has done no IO.
+    pub fn get_stats_summary(&self) -> Option<String> {
+        let read_count = self.get_read_count();
+        let throttle_wait = self.get_throttle_wait();
+        let max_open_files = self.get_max_open_files();
+        if read_count == 0 && throttle_wait.is_zero() && max_open_files == 0 {
+            return None;
+        }
+
+        Some(format!(
+            "reads: {}, bytes: {}, max_latency: {:?}, latency_histogram_ms: {:?}, throttle_wait: {:?}, max_open_files: {}, open_files_wait_count: {}",
+            read_count,
+            self.get_read_bytes(),
+            self.get_max_read_latency(),
+            self.get_read_latency_histogram(),
+            throttle_wait,
+            max_open_files,
+            self.get_open_files_wait_count()
+        ))
+    }
+
+    fn reserve(&self, limiter: &Option<Arc<BandwidthLimiter>>, bytes: usize) -> Option<Duration> {
+        let wait = limiter.as_ref()?.reserve(bytes);
+        if wait.is_zero() {
+            return None;
+        }
+
+        self.throttle_wait_us
+            .fetch_add(wait.as_micros() as u64, Ordering::Relaxed);
+        Some(wait)
+    }
+}
+
+/// Keeps a file counted as opened in the [DalContext] until dropped.
+pub struct OpenFileGuard {
+    _permit: Option<OwnedSemaphorePermit>,
+    ctx: Arc<DalContext>,
+}
+
+impl Drop for OpenFileGuard {
+    fn drop(&mut self) {
+        self.ctx.open_files.fetch_sub(1, Ordering::Relaxed);
+    }
+}
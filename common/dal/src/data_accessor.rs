@@ -27,6 +27,7 @@ use futures::AsyncReadExt;
 use futures::AsyncSeek;
 use serde::de::DeserializeOwned;
 
+use crate::DalContext;
 use crate::Local;
 use crate::StorageScheme;
 use crate::S3;
@@ -123,9 +124,24 @@ pub trait DataAccessorBuilder {
         match scheme {
             StorageScheme::S3 => Ok(Arc::new(S3::fake_new())),
             StorageScheme::LocalFs => Ok(Arc::new(Local::new("/tmp"))),
+            // There is no sensible default name node, see WebHdfs::try_create.
+            StorageScheme::Hdfs => Err(ErrorCode::BadArguments(
+                "The HDFS data accessor needs the name node of the storage config",
+            )),
             _ => todo!(),
         }
     }
+
+    /// Build a DataAccessor for a query, local files are opened within the limit of the `ctx`.
+    fn build_with_context(
+        scheme: &StorageScheme,
+        ctx: Arc<DalContext>,
+    ) -> Result<Arc<dyn DataAccessor>> {
+        match scheme {
+            StorageScheme::LocalFs => Ok(Arc::new(Local::with_context("/tmp", ctx))),
+            _ => Self::build(scheme),
+        }
+    }
 }
 
 /// A default DataAccessorBuilder impl.
@@ -0,0 +1,102 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::Bytes;
+use crate::DalContext;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::InputStreamWithMetric;
+use crate::SeekableReader;
+
+/// A DataAccessor wrapper, which records the reads of the input streams into the `ctx`
+/// and the read latency metric of the scheme.
+///
+/// The async reads and writes of every scheme are throttled here by the bandwidth limits
+/// of the `ctx`, the blocking readers and writers are not.
+pub struct DataAccessorWithMetric {
+    inner: Arc<dyn DataAccessor>,
+    scheme: &'static str,
+    ctx: Arc<DalContext>,
+}
+
+impl DataAccessorWithMetric {
+    pub fn wrap(
+        inner: Arc<dyn DataAccessor>,
+        scheme: &'static str,
+        ctx: Arc<DalContext>,
+    ) -> Arc<dyn DataAccessor> {
+        Arc::new(DataAccessorWithMetric { inner, scheme, ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for DataAccessorWithMetric {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_writer(&self, path: &str) -> Result<Box<dyn Write>> {
+        self.inner.get_writer(path)
+    }
+
+    async fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        let input_stream = self.inner.get_input_stream(path, stream_len).await?;
+        Ok(Box::new(InputStreamWithMetric::new(
+            input_stream,
+            self.scheme,
+            self.ctx.clone(),
+        )))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let bytes = self.inner.get(path).await?;
+        self.ctx.throttle_read(bytes.len()).await;
+        Ok(bytes)
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.ctx.throttle_write(content.len()).await;
+        self.inner.put(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + Unpin + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let ctx = self.ctx.clone();
+        let input_stream = input_stream.then(move |bytes| {
+            let ctx = ctx.clone();
+            async move {
+                if let Ok(bytes) = &bytes {
+                    ctx.throttle_write(bytes.len()).await;
+                }
+                bytes
+            }
+        });
+        self.inner
+            .put_stream(path, Box::new(Box::pin(input_stream)), stream_len)
+            .await
+    }
+}
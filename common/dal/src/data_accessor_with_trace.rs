@@ -0,0 +1,233 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::io::Write;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use common_exception::Result;
+use futures::AsyncRead;
+use futures::AsyncSeek;
+use futures::Stream;
+
+use crate::Bytes;
+use crate::DalContext;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::IoTraceEntry;
+use crate::SeekableReader;
+
+/// A DataAccessor wrapper, which records every request into the io trace of the query
+/// with the tag of the caller.
+pub struct DataAccessorWithTrace {
+    inner: Arc<dyn DataAccessor>,
+    ctx: Arc<DalContext>,
+    tag: &'static str,
+}
+
+impl DataAccessorWithTrace {
+    /// Wrap the accessor if the io trace of the query is on, otherwise `inner` is returned as it is.
+    pub fn wrap(
+        inner: Arc<dyn DataAccessor>,
+        ctx: Arc<DalContext>,
+        tag: &'static str,
+    ) -> Arc<dyn DataAccessor> {
+        match ctx.get_io_trace() {
+            None => inner,
+            Some(_) => Arc::new(DataAccessorWithTrace { inner, ctx, tag }),
+        }
+    }
+
+    fn trace(&self, path: &str, operation: &'static str, bytes: usize, start: TraceStart) {
+        trace(&self.ctx, path, operation, None, bytes, self.tag, start);
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for DataAccessorWithTrace {
+    // The blocking readers are not traced.
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_writer(&self, path: &str) -> Result<Box<dyn Write>> {
+        Ok(Box::new(WriterWithTrace {
+            inner: self.inner.get_writer(path)?,
+            path: path.to_string(),
+            tag: self.tag,
+            ctx: self.ctx.clone(),
+            position: 0,
+        }))
+    }
+
+    async fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        let start = TraceStart::now();
+        let input_stream = self.inner.get_input_stream(path, stream_len).await?;
+        self.trace(path, "open", 0, start);
+
+        Ok(Box::new(InputStreamWithTrace {
+            inner: input_stream,
+            path: path.to_string(),
+            tag: self.tag,
+            ctx: self.ctx.clone(),
+            position: 0,
+            read_start: None,
+        }))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let start = TraceStart::now();
+        let bytes = self.inner.get(path).await?;
+        self.trace(path, "get", bytes.len(), start);
+        Ok(bytes)
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let start = TraceStart::now();
+        let bytes = content.len();
+        self.inner.put(path, content).await?;
+        self.trace(path, "put", bytes, start);
+        Ok(())
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + Unpin + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let start = TraceStart::now();
+        self.inner
+            .put_stream(path, input_stream, stream_len)
+            .await?;
+        self.trace(path, "put_stream", stream_len, start);
+        Ok(())
+    }
+}
+
+/// An InputStream wrapper, which records each read with its offset in the object.
+struct InputStreamWithTrace {
+    inner: InputStream,
+    path: String,
+    tag: &'static str,
+    ctx: Arc<DalContext>,
+    position: u64,
+    read_start: Option<TraceStart>,
+}
+
+impl AsyncRead for InputStreamWithTrace {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let start = *self.read_start.get_or_insert_with(TraceStart::now);
+        match Pin::new(&mut self.inner).poll_read(cx, buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(res) => {
+                self.read_start = None;
+                if let Ok(bytes) = &res {
+                    let offset = Some(self.position);
+                    trace(
+                        &self.ctx, &self.path, "read", offset, *bytes, self.tag, start,
+                    );
+                    self.position += *bytes as u64;
+                }
+                Poll::Ready(res)
+            }
+        }
+    }
+}
+
+impl AsyncSeek for InputStreamWithTrace {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let res = Pin::new(&mut self.inner).poll_seek(cx, pos);
+        if let Poll::Ready(Ok(position)) = &res {
+            self.position = *position;
+        }
+        res
+    }
+}
+
+/// A blocking writer wrapper, which records each write with its offset in the object.
+struct WriterWithTrace {
+    inner: Box<dyn Write>,
+    path: String,
+    tag: &'static str,
+    ctx: Arc<DalContext>,
+    position: u64,
+}
+
+impl Write for WriterWithTrace {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let start = TraceStart::now();
+        let bytes = self.inner.write(buf)?;
+        let offset = Some(self.position);
+        trace(
+            &self.ctx, &self.path, "write", offset, bytes, self.tag, start,
+        );
+        self.position += bytes as u64;
+        Ok(bytes)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TraceStart {
+    time: SystemTime,
+    instant: Instant,
+}
+
+impl TraceStart {
+    fn now() -> Self {
+        TraceStart {
+            time: SystemTime::now(),
+            instant: Instant::now(),
+        }
+    }
+}
+
+fn trace(
+    ctx: &DalContext,
+    path: &str,
+    operation: &'static str,
+    offset: Option<u64>,
+    bytes: usize,
+    tag: &'static str,
+    start: TraceStart,
+) {
+    ctx.trace_io(IoTraceEntry {
+        start: start.time,
+        path: path.to_string(),
+        operation,
+        offset,
+        bytes,
+        latency: start.instant.elapsed(),
+        tag,
+    });
+}
@@ -15,40 +15,75 @@
 
 use std::io::Error;
 use std::io::ErrorKind;
+use std::io::SeekFrom;
 use std::io::Write;
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
 
+use async_compat::Compat;
 use async_compat::CompatExt;
 use common_base::tokio;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use futures::AsyncRead;
+use futures::AsyncSeek;
 use futures::Stream;
 use futures::StreamExt;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 
 use crate::Bytes;
+use crate::DalContext;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::OpenFileGuard;
 use crate::SeekableReader;
 
 pub struct Local {
     root: PathBuf,
+    ctx: Option<Arc<DalContext>>,
 }
 
 impl Local {
     pub fn new(root: &str) -> Local {
         Local {
             root: PathBuf::from(root),
+            ctx: None,
+        }
+    }
+
+    /// Files opened by the async reads are limited by the open files limit of the `ctx`.
+    pub fn with_context(root: &str, ctx: Arc<DalContext>) -> Local {
+        Local {
+            root: PathBuf::from(root),
+            ctx: Some(ctx),
+        }
+    }
+
+    async fn acquire_open_file(&self) -> Result<Option<OpenFileGuard>> {
+        match &self.ctx {
+            None => Ok(None),
+            Some(ctx) => Ok(Some(ctx.acquire_open_file().await?)),
         }
     }
 }
 
 impl Local {
     fn prefix_with_root(&self, path: &str) -> Result<PathBuf> {
-        let path = self.root.join(path).canonicalize()?;
-        if path.starts_with(&self.root) {
-            Ok(path)
+        // Checked by the components rather than `canonicalize`, which fails on the
+        // paths that do not exist yet, i.e. every path that is `put`.
+        let path = Path::new(path);
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        let inside_root = relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+        if inside_root {
+            Ok(self.root.join(relative))
         } else {
             // TODO customize error code
             Err(ErrorCode::from(Error::new(
@@ -71,11 +106,16 @@ impl DataAccessor for Local {
 
     async fn get_input_stream(&self, path: &str, _stream_len: Option<u64>) -> Result<InputStream> {
         let path = self.prefix_with_root(path)?;
-        Ok(Box::new(tokio::fs::File::open(path).await?.compat()))
+        let guard = self.acquire_open_file().await?;
+        Ok(Box::new(LocalInputStream {
+            inner: tokio::fs::File::open(path).await?.compat(),
+            _guard: guard,
+        }))
     }
 
     async fn get(&self, path: &str) -> Result<Bytes> {
         let path = self.prefix_with_root(path)?;
+        let _guard = self.acquire_open_file().await?;
         let mut file = tokio::fs::File::open(path).await?;
         let mut contents = vec![];
         let _ = file.read_to_end(&mut contents).await?;
@@ -116,3 +156,29 @@ impl DataAccessor for Local {
         Ok(())
     }
 }
+
+/// A local file input stream, which holds the open file permit until it is dropped.
+struct LocalInputStream {
+    inner: Compat<tokio::fs::File>,
+    _guard: Option<OpenFileGuard>,
+}
+
+impl AsyncRead for LocalInputStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncSeek for LocalInputStream {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        Pin::new(&mut self.inner).poll_seek(cx, pos)
+    }
+}
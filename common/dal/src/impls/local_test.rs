@@ -0,0 +1,104 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use rand::Rng;
+
+use crate::DalContext;
+use crate::DataAccessor;
+use crate::Local;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_local_open_files_limit() -> Result<()> {
+    let root = std::env::temp_dir().join(format!(
+        "dal_local_test_{}",
+        rand::thread_rng().gen::<u64>()
+    ));
+    std::fs::create_dir_all(&root)?;
+    let root = root.canonicalize()?;
+    let root_str = root.to_str().unwrap().to_string();
+
+    let files = 64;
+    for i in 0..files {
+        std::fs::write(root.join(format!("file_{}", i)), format!("content_{}", i))?;
+    }
+
+    let ctx = Arc::new(DalContext::with_open_files_limit(4));
+    let local = Arc::new(Local::with_context(&root_str, ctx.clone()));
+
+    // Hold all the permits, the next open has to wait.
+    let mut opened = vec![];
+    for i in 0..4 {
+        opened.push(local.get_input_stream(&format!("file_{}", i), None).await?);
+    }
+    let waiting = {
+        let local = local.clone();
+        tokio::spawn(async move { local.get_input_stream("file_4", None).await })
+    };
+    while ctx.get_open_files_wait_count() == 0 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(ctx.get_max_open_files(), 4);
+    opened.pop();
+    let mut content = String::new();
+    waiting.await.unwrap()?.read_to_string(&mut content).await?;
+    assert_eq!(content, "content_4");
+    drop(opened);
+
+    let handles = (0..files)
+        .map(|i| {
+            let local = local.clone();
+            tokio::spawn(async move {
+                let mut stream = local.get_input_stream(&format!("file_{}", i), None).await?;
+                tokio::task::yield_now().await;
+                let mut content = String::new();
+                stream.read_to_string(&mut content).await?;
+                Ok::<_, common_exception::ErrorCode>((i, content))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let (i, content) = handle.await.unwrap()?;
+        assert_eq!(content, format!("content_{}", i));
+    }
+    assert!(ctx.get_max_open_files() <= 4);
+
+    // Permits are released, get works after all the streams are dropped.
+    assert_eq!(local.get("file_0").await?, b"content_0".to_vec());
+
+    std::fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_without_open_files_limit() -> Result<()> {
+    let ctx = Arc::new(DalContext::create());
+    let guards = vec![
+        ctx.acquire_open_file().await?,
+        ctx.acquire_open_file().await?,
+        ctx.acquire_open_file().await?,
+    ];
+    assert_eq!(ctx.get_max_open_files(), 3);
+    assert_eq!(ctx.get_open_files_wait_count(), 0);
+    drop(guards);
+
+    let _guard = ctx.acquire_open_file().await?;
+    assert_eq!(ctx.get_max_open_files(), 3);
+    Ok(())
+}
@@ -12,5 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod local_test;
+
 pub mod aws_s3;
 pub mod local;
+pub mod webhdfs;
@@ -0,0 +1,25 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod webhdfs_test;
+
+mod webhdfs;
+mod webhdfs_input_stream;
+mod webhdfs_reader;
+
+pub use webhdfs::HdfsFileStatus;
+pub use webhdfs::WebHdfs;
+pub use webhdfs_input_stream::WebHdfsInputStream;
+pub use webhdfs_reader::WebHdfsReader;
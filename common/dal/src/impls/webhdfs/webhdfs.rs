@@ -0,0 +1,321 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::Client;
+use reqwest::Method;
+use reqwest::Response;
+use reqwest::StatusCode;
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::SeekableReader;
+use crate::WebHdfsInputStream;
+use crate::WebHdfsReader;
+
+/// The size of the chunks put_stream sends, each by a CREATE or an APPEND.
+pub(crate) const PUT_STREAM_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Status of a file or a directory, as returned by GETFILESTATUS.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HdfsFileStatus {
+    /// Always empty for GETFILESTATUS.
+    pub path_suffix: String,
+    /// FILE, DIRECTORY or SYMLINK.
+    #[serde(rename = "type")]
+    pub file_type: String,
+    pub length: u64,
+    /// Milliseconds since the epoch.
+    pub modification_time: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct FileStatusResponse {
+    file_status: HdfsFileStatus,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RemoteExceptionResponse {
+    remote_exception: RemoteException,
+}
+
+#[derive(Deserialize)]
+struct RemoteException {
+    exception: String,
+    message: String,
+}
+
+/// DataAccessor over the WebHDFS REST API.
+///
+/// Only the simple authentication (user.name) is supported, Kerberos(SPNEGO) is not.
+#[derive(Clone)]
+pub struct WebHdfs {
+    client: Client,
+    name_node: Url,
+    root: String,
+    user: Option<String>,
+}
+
+impl WebHdfs {
+    /// name_node is the http address of the name node, e.g. http://127.0.0.1:9870
+    pub fn try_create(name_node: &str, root: &str, user: Option<String>) -> Result<Self> {
+        let name_node = Url::parse(name_node).map_err(|e| {
+            ErrorCode::BadArguments(format!("Invalid HDFS name node {}: {}", name_node, e))
+        })?;
+
+        Ok(WebHdfs {
+            client: Self::build_client()?,
+            name_node,
+            root: root.trim_matches('/').to_string(),
+            user,
+        })
+    }
+
+    fn build_client() -> Result<Client> {
+        // Redirects to the data nodes are followed by hand, CREATE and APPEND have to send
+        // the content to the redirected location only.
+        Client::builder()
+            .redirect(Policy::none())
+            .build()
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))
+    }
+
+    fn hdfs_path(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        match self.root.is_empty() {
+            true => format!("/{}", path),
+            false => format!("/{}/{}", self.root, path),
+        }
+    }
+
+    fn op_url(&self, path: &str, op: &str, params: &[(&str, String)]) -> Url {
+        let mut url = self.name_node.clone();
+        url.set_path(&format!("/webhdfs/v1{}", self.hdfs_path(path)));
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("op", op);
+            if let Some(user) = &self.user {
+                query.append_pair("user.name", user);
+            }
+            for (key, value) in params {
+                query.append_pair(key, value);
+            }
+        }
+        url
+    }
+
+    async fn send(&self, method: Method, url: Url, content: Option<Vec<u8>>) -> Result<Response> {
+        let resp = self
+            .client
+            .request(method.clone(), url)
+            .send()
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+
+        let resp = match resp.status() {
+            StatusCode::TEMPORARY_REDIRECT | StatusCode::FOUND | StatusCode::SEE_OTHER => {
+                let location = resp
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .ok_or_else(|| {
+                        ErrorCode::DALTransportError("WebHDFS redirect without location")
+                    })?;
+
+                let mut request = self.client.request(method, location);
+                if let Some(content) = content {
+                    request = request.body(content);
+                }
+                request
+                    .send()
+                    .await
+                    .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?
+            }
+            _ if content.is_some() && resp.status().is_success() => {
+                return Err(ErrorCode::DALTransportError(
+                    "WebHDFS name node did not redirect the write to a data node",
+                ));
+            }
+            _ => resp,
+        };
+
+        Self::check_response(resp).await
+    }
+
+    async fn check_response(resp: Response) -> Result<Response> {
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(ErrorCode::DALTransportError(
+                "WebHDFS requires an authentication which is not supported, only the simple authentication (user.name) is supported",
+            ));
+        }
+
+        let body = resp
+            .bytes()
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        let message = match serde_json::from_slice::<RemoteExceptionResponse>(&body) {
+            Ok(resp) => format!(
+                "{}: {}",
+                resp.remote_exception.exception, resp.remote_exception.message
+            ),
+            Err(_) => String::from_utf8_lossy(&body).to_string(),
+        };
+
+        Err(ErrorCode::DALTransportError(format!(
+            "WebHDFS request failed, status: {}, {}",
+            status, message
+        )))
+    }
+
+    /// Open the file from the offset, to the end if the length is not given.
+    pub(crate) async fn open(
+        &self,
+        path: &str,
+        offset: u64,
+        length: Option<u64>,
+    ) -> Result<Response> {
+        let mut params = vec![("offset", offset.to_string())];
+        if let Some(length) = length {
+            params.push(("length", length.to_string()));
+        }
+        let url = self.op_url(path, "OPEN", &params);
+        self.send(Method::GET, url, None).await
+    }
+
+    /// Read at most length bytes from the offset, fewer if the file ends before.
+    pub(crate) async fn read_range(&self, path: &str, offset: u64, length: u64) -> Result<Bytes> {
+        let resp = self.open(path, offset, Some(length)).await?;
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// A copy with its own connection pool, the connections of a client can not be shared
+    /// across runtimes.
+    pub(crate) fn with_new_client(&self) -> Result<Self> {
+        Ok(WebHdfs {
+            client: Self::build_client()?,
+            ..self.clone()
+        })
+    }
+
+    pub async fn get_file_status(&self, path: &str) -> Result<HdfsFileStatus> {
+        let url = self.op_url(path, "GETFILESTATUS", &[]);
+        let resp = self.send(Method::GET, url, None).await?;
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(serde_json::from_slice::<FileStatusResponse>(&bytes)?.file_status)
+    }
+
+    async fn create(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let url = self.op_url(path, "CREATE", &[("overwrite", "true".to_string())]);
+        self.send(Method::PUT, url, Some(content)).await?;
+        Ok(())
+    }
+
+    async fn append(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        let url = self.op_url(path, "APPEND", &[]);
+        self.send(Method::POST, url, Some(content)).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for WebHdfs {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        Ok(Box::new(WebHdfsReader::try_create(self, path, len)?))
+    }
+
+    fn get_writer(&self, _path: &str) -> Result<Box<dyn Write>> {
+        Err(ErrorCode::UnImplement(
+            "Blocking writer of WebHDFS is not supported, please use put or put_stream",
+        ))
+    }
+
+    async fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        Ok(Box::new(WebHdfsInputStream::new(
+            self.clone(),
+            path,
+            stream_len,
+        )))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let resp = self.open(path, 0, None).await?;
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.create(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + Unpin + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        // The file is created with the first chunk and the rest is appended chunk by chunk,
+        // so at most one chunk of the stream is buffered.
+        let chunk_size = std::cmp::min(stream_len, PUT_STREAM_CHUNK_SIZE);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut created = false;
+        let mut input_stream = input_stream;
+        while let Some(bytes) = input_stream.next().await {
+            chunk.extend_from_slice(&bytes?);
+            if chunk.len() >= PUT_STREAM_CHUNK_SIZE {
+                let content = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+                match created {
+                    true => self.append(path, content).await?,
+                    false => self.create(path, content).await?,
+                }
+                created = true;
+            }
+        }
+
+        match created {
+            true if chunk.is_empty() => Ok(()),
+            true => self.append(path, chunk).await,
+            false => self.create(path, chunk).await,
+        }
+    }
+}
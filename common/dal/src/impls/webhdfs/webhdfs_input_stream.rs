@@ -0,0 +1,231 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::BufMut;
+use futures::ready;
+use futures::stream::unfold;
+use futures::Future;
+use futures::FutureExt;
+use futures::Stream;
+
+use crate::WebHdfs;
+
+type BodyStream = Pin<Box<dyn Stream<Item = Result<bytes::Bytes, Error>> + Send>>;
+type BodyFuture = Pin<Box<dyn Future<Output = Result<BodyStream, Error>> + Send>>;
+type StreamLenFuture = Pin<Box<dyn Future<Output = Result<u64, Error>> + Send>>;
+
+enum State {
+    Bare,
+    GettingBody(BodyFuture),
+    GotBody(BodyStream),
+    Seeking(StreamLenFuture),
+}
+
+/// Seekable input stream of a WebHDFS file.
+///
+/// Each (re)positioning opens the file at the cursor, the name node redirects the
+/// read to the data nodes, which stream the rest of the file across block boundaries.
+pub struct WebHdfsInputStream {
+    hdfs: WebHdfs,
+    path: String,
+
+    state: State,
+
+    buffer: bytes::BytesMut,
+    /// where reading begins
+    cursor_pos: u64,
+    /// total length of target file
+    stream_len: Option<u64>,
+}
+
+impl WebHdfsInputStream {
+    pub fn new(hdfs: WebHdfs, path: &str, len_hint: Option<u64>) -> Self {
+        Self {
+            hdfs,
+            path: path.to_owned(),
+            state: State::Bare,
+            buffer: bytes::BytesMut::new(),
+            cursor_pos: 0,
+            stream_len: len_hint,
+        }
+    }
+}
+
+impl futures::AsyncRead for WebHdfsInputStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        loop {
+            let empty = { self.buffer.is_empty() };
+            match &mut self.state {
+                State::Bare => {
+                    if matches!(self.stream_len, Some(len) if self.cursor_pos >= len) {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    let hdfs = self.hdfs.clone();
+                    let path = self.path.clone();
+                    let offset = self.cursor_pos;
+                    let body = async move {
+                        let resp = hdfs
+                            .open(&path, offset, None)
+                            .await
+                            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+                        let stream = unfold(resp, |mut resp| async move {
+                            match resp.chunk().await {
+                                Ok(Some(chunk)) => Some((Ok(chunk), resp)),
+                                Ok(None) => None,
+                                Err(e) => Some((Err(Error::new(ErrorKind::Other, e)), resp)),
+                            }
+                        });
+                        Ok(Box::pin(stream) as BodyStream)
+                    };
+                    self.state = State::GettingBody(body.boxed());
+                }
+                State::GettingBody(body) => match ready!(Pin::new(body).poll(cx)) {
+                    Ok(stream) => self.state = State::GotBody(stream),
+                    Err(e) => {
+                        self.reset();
+                        return Poll::Ready(Err(e));
+                    }
+                },
+                State::GotBody(stream) => {
+                    if !empty {
+                        return self.do_read(buf);
+                    }
+
+                    match ready!(stream.as_mut().poll_next(cx)) {
+                        Some(Ok(chunk)) => {
+                            self.buffer.put(chunk);
+                            return self.do_read(buf);
+                        }
+                        Some(Err(e)) => {
+                            self.reset();
+                            return Poll::Ready(Err(e));
+                        }
+                        None => return Poll::Ready(Ok(0)),
+                    }
+                }
+                State::Seeking(_) => {
+                    // read while seeking is NOT allowed
+                    return Poll::Ready(Err(Error::new(
+                        ErrorKind::Other,
+                        "read while seeking NOT allowed",
+                    )));
+                }
+            }
+        }
+    }
+}
+
+impl futures::AsyncSeek for WebHdfsInputStream {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        match self.stream_len {
+            Some(len) => Poll::Ready(self.seek_with_stream_len(pos, len)),
+            None => loop {
+                match &mut self.state {
+                    State::Seeking(f) => {
+                        let res = ready!(Pin::new(f).poll(cx));
+                        self.state = State::Bare;
+                        let len = res?;
+                        self.stream_len = Some(len);
+                        return Poll::Ready(self.seek_with_stream_len(pos, len));
+                    }
+                    State::Bare => {
+                        let hdfs = self.hdfs.clone();
+                        let path = self.path.clone();
+                        let res = async move {
+                            hdfs.get_file_status(&path)
+                                .await
+                                .map(|status| status.length)
+                                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+                        };
+                        self.state = State::Seeking(res.boxed());
+                    }
+                    State::GettingBody(_) | State::GotBody(_) => self.reset(),
+                }
+            },
+        }
+    }
+}
+
+impl WebHdfsInputStream {
+    /// Stop the pending read, the next read opens the file at the cursor again, so the
+    /// buffered bytes must be dropped too.
+    fn reset(&mut self) {
+        self.state = State::Bare;
+        self.buffer.clear();
+    }
+
+    fn seek_with_stream_len(
+        mut self: Pin<&mut Self>,
+        pos: SeekFrom,
+        stream_len: u64,
+    ) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(start) => (start, 0),
+            SeekFrom::End(end) => (stream_len, end),
+            SeekFrom::Current(current) => (self.cursor_pos, current),
+        };
+
+        let new_pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.wrapping_neg() as u64)
+        };
+
+        // invalid position, seeking beyond the end of the file is treated as an error
+        let new_pos = match new_pos {
+            Some(new_pos) if new_pos <= stream_len => new_pos,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "invalid seeking operation, current offset {}, SeekFrom {:?}",
+                        self.cursor_pos, pos
+                    ),
+                ))
+            }
+        };
+
+        if self.cursor_pos != new_pos {
+            self.reset();
+        }
+        self.cursor_pos = new_pos;
+        Ok(self.cursor_pos)
+    }
+
+    fn do_read(mut self: Pin<&mut Self>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let available = std::cmp::min(buf.len(), self.buffer.len());
+        let bytes = self.buffer.split_to(available);
+        let mut new_buf = buf;
+        new_buf.write_all(bytes.as_ref())?;
+        self.cursor_pos += available as u64;
+        Poll::Ready(Ok(available))
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use common_base::Runtime;
+use common_base::TrySpawn;
+use common_exception::Result;
+
+use crate::WebHdfs;
+
+/// The least bytes a ranged OPEN reads, the reads of the callers are usually much smaller.
+const READ_AHEAD_SIZE: u64 = 64 * 1024;
+
+/// Blocking seekable reader of a WebHDFS file.
+///
+/// Each read that misses the buffer opens a range(offset and length) of the file, the
+/// requests run on a runtime of the reader, so it can be used in and out of async contexts.
+pub struct WebHdfsReader {
+    hdfs: WebHdfs,
+    path: String,
+    runtime: Runtime,
+
+    buffer: bytes::Bytes,
+    /// where reading begins
+    cursor_pos: u64,
+    /// total length of target file
+    stream_len: Option<u64>,
+}
+
+impl WebHdfsReader {
+    pub fn try_create(hdfs: &WebHdfs, path: &str, len_hint: Option<u64>) -> Result<Self> {
+        Ok(Self {
+            hdfs: hdfs.with_new_client()?,
+            path: path.to_owned(),
+            runtime: Runtime::with_worker_threads(1)?,
+            buffer: bytes::Bytes::new(),
+            cursor_pos: 0,
+            stream_len: len_hint,
+        })
+    }
+
+    fn stream_len(&mut self) -> std::io::Result<u64> {
+        if let Some(len) = self.stream_len {
+            return Ok(len);
+        }
+
+        let hdfs = self.hdfs.clone();
+        let path = self.path.clone();
+        let status = self
+            .runtime
+            .block_on(async move { hdfs.get_file_status(&path).await }, None)
+            .and_then(|res| res)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        self.stream_len = Some(status.length);
+        Ok(status.length)
+    }
+}
+
+impl Read for WebHdfsReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buffer.is_empty() {
+            if matches!(self.stream_len, Some(len) if self.cursor_pos >= len) || buf.is_empty() {
+                return Ok(0);
+            }
+
+            let hdfs = self.hdfs.clone();
+            let path = self.path.clone();
+            let offset = self.cursor_pos;
+            let length = std::cmp::max(buf.len() as u64, READ_AHEAD_SIZE);
+            let bytes = self
+                .runtime
+                .block_on(
+                    async move { hdfs.read_range(&path, offset, length).await },
+                    None,
+                )
+                .and_then(|res| res)
+                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+            self.buffer = bytes::Bytes::from(bytes);
+        }
+
+        let available = std::cmp::min(buf.len(), self.buffer.len());
+        let bytes = self.buffer.split_to(available);
+        buf[..available].copy_from_slice(bytes.as_ref());
+        self.cursor_pos += available as u64;
+        Ok(available)
+    }
+}
+
+impl Seek for WebHdfsReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(start) => (start, 0),
+            SeekFrom::End(end) => (self.stream_len()?, end),
+            SeekFrom::Current(current) => (self.cursor_pos, current),
+        };
+
+        let new_pos = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.wrapping_neg() as u64)
+        };
+
+        // invalid position, seeking beyond the end of the file is treated as an error
+        let stream_len = self.stream_len()?;
+        let new_pos = match new_pos {
+            Some(new_pos) if new_pos <= stream_len => new_pos,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "invalid seeking operation, current offset {}, SeekFrom {:?}",
+                        self.cursor_pos, pos
+                    ),
+                ))
+            }
+        };
+
+        // keep the buffered bytes if the new position is still in the buffer
+        match new_pos.checked_sub(self.cursor_pos) {
+            Some(skip) if skip <= self.buffer.len() as u64 => {
+                let _ = self.buffer.split_to(skip as usize);
+            }
+            _ => self.buffer.clear(),
+        }
+        self.cursor_pos = new_pos;
+        Ok(self.cursor_pos)
+    }
+}
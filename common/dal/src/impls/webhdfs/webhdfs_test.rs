@@ -0,0 +1,383 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+use hyper::service::make_service_fn;
+use hyper::service::service_fn;
+use hyper::Body;
+use hyper::Request;
+use hyper::Response;
+use hyper::Server;
+use hyper::StatusCode;
+
+use crate::impls::webhdfs::webhdfs::PUT_STREAM_CHUNK_SIZE;
+use crate::DataAccessor;
+use crate::WebHdfs;
+
+/// Files of the mock WebHDFS, keyed by the HDFS path.
+type Files = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+const MOCK_BLOCK_SIZE: usize = 1024;
+
+fn response(status: StatusCode, body: String) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn file_not_found(path: &str) -> Response<Body> {
+    let body = serde_json::json!({
+        "RemoteException": {
+            "exception": "FileNotFoundException",
+            "javaClassName": "java.io.FileNotFoundException",
+            "message": format!("File does not exist: {}", path),
+        }
+    });
+    response(StatusCode::NOT_FOUND, body.to_string())
+}
+
+/// The name node redirects OPEN, CREATE and APPEND to the data node(the same server under /datanode),
+/// which streams the file in blocks.
+async fn handle(
+    files: Files,
+    req: Request<Body>,
+) -> std::result::Result<Response<Body>, Infallible> {
+    let path = req.uri().path().to_string();
+    let query = req.uri().query().unwrap_or("").to_string();
+    let params = query
+        .split('&')
+        .filter_map(|pair| {
+            let mut kv = pair.splitn(2, '=');
+            Some((kv.next()?.to_string(), kv.next().unwrap_or("").to_string()))
+        })
+        .collect::<HashMap<_, _>>();
+    let op = params.get("op").cloned().unwrap_or_default();
+    let host = req
+        .headers()
+        .get("Host")
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+
+    if let Some(hdfs_path) = path.strip_prefix("/webhdfs/v1") {
+        if hdfs_path.starts_with("/secure/") {
+            return Ok(response(StatusCode::UNAUTHORIZED, String::new()));
+        }
+
+        return Ok(match op.as_str() {
+            "OPEN" | "CREATE" | "APPEND" => Response::builder()
+                .status(StatusCode::TEMPORARY_REDIRECT)
+                .header(
+                    "Location",
+                    format!("http://{}/datanode{}?{}", host, hdfs_path, query),
+                )
+                .body(Body::empty())
+                .unwrap(),
+            "GETFILESTATUS" => match files.lock().unwrap().get(hdfs_path) {
+                None => file_not_found(hdfs_path),
+                Some(content) => {
+                    let body = serde_json::json!({
+                        "FileStatus": {
+                            "pathSuffix": "",
+                            "type": "FILE",
+                            "length": content.len(),
+                            "modificationTime": 1634220000000u64,
+                        }
+                    });
+                    response(StatusCode::OK, body.to_string())
+                }
+            },
+            _ => response(StatusCode::BAD_REQUEST, String::new()),
+        });
+    }
+
+    let hdfs_path = path.strip_prefix("/datanode").unwrap_or("").to_string();
+    match op.as_str() {
+        "CREATE" => {
+            let content = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            files.lock().unwrap().insert(hdfs_path, content.to_vec());
+            Ok(response(StatusCode::CREATED, String::new()))
+        }
+        "APPEND" => {
+            let content = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            match files.lock().unwrap().get_mut(&hdfs_path) {
+                None => Ok(file_not_found(&hdfs_path)),
+                Some(file) => {
+                    file.extend_from_slice(&content);
+                    Ok(response(StatusCode::OK, String::new()))
+                }
+            }
+        }
+        "OPEN" => {
+            let content = match files.lock().unwrap().get(&hdfs_path) {
+                None => return Ok(file_not_found(&hdfs_path)),
+                Some(content) => content.clone(),
+            };
+            let offset = params
+                .get("offset")
+                .and_then(|offset| offset.parse::<usize>().ok())
+                .unwrap_or(0)
+                .min(content.len());
+            let end = params
+                .get("length")
+                .and_then(|length| length.parse::<usize>().ok())
+                .map(|length| (offset + length).min(content.len()))
+                .unwrap_or_else(|| content.len());
+
+            let (mut sender, body) = Body::channel();
+            tokio::spawn(async move {
+                for block in content[offset..end].chunks(MOCK_BLOCK_SIZE) {
+                    let block = bytes::Bytes::copy_from_slice(block);
+                    if sender.send_data(block).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            Ok(Response::new(body))
+        }
+        _ => Ok(response(StatusCode::BAD_REQUEST, String::new())),
+    }
+}
+
+async fn start_mock_webhdfs(files: Files) -> SocketAddr {
+    let make_service = make_service_fn(move |_| {
+        let files = files.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |req| handle(files.clone(), req))) }
+    });
+
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+    tokio::spawn(server);
+    address
+}
+
+async fn create_webhdfs(files: Files) -> Result<WebHdfs> {
+    let address = start_mock_webhdfs(files).await;
+    WebHdfs::try_create(
+        &format!("http://{}", address),
+        "/databend",
+        Some("databend".to_string()),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_put_get() -> Result<()> {
+    let files = Files::default();
+    let hdfs = create_webhdfs(files.clone()).await?;
+
+    let content: Vec<u8> = (0..MOCK_BLOCK_SIZE * 10 + 7)
+        .map(|_| rand::random::<u8>())
+        .collect();
+    hdfs.put("data/test_put_get", content.clone()).await?;
+    assert!(files
+        .lock()
+        .unwrap()
+        .contains_key("/databend/data/test_put_get"));
+
+    assert_eq!(hdfs.get("data/test_put_get").await?, content);
+    assert_eq!(
+        hdfs.get_file_status("data/test_put_get").await?.length,
+        content.len() as u64
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_input_stream_range_read() -> Result<()> {
+    let files = Files::default();
+    let hdfs = create_webhdfs(files.clone()).await?;
+
+    let content: Vec<u8> = (0..MOCK_BLOCK_SIZE * 10)
+        .map(|_| rand::random::<u8>())
+        .collect();
+    files
+        .lock()
+        .unwrap()
+        .insert("/databend/data/test_range".to_string(), content.clone());
+
+    // Without length hint, the seeking gets the length from the name node.
+    let mut input = hdfs.get_input_stream("data/test_range", None).await?;
+
+    // A range spans three blocks.
+    let pos = input.seek(SeekFrom::Start(1500)).await?;
+    assert_eq!(pos, 1500);
+    let mut buffer = vec![0; 3000];
+    input.read_exact(&mut buffer).await?;
+    assert_eq!(buffer, &content[1500..4500]);
+
+    // Seek backwards, into the middle of the first block.
+    input.seek(SeekFrom::Current(-4000)).await?;
+    let mut buffer = vec![0; 100];
+    input.read_exact(&mut buffer).await?;
+    assert_eq!(buffer, &content[500..600]);
+
+    let mut buffer = vec![];
+    input.seek(SeekFrom::End(-100)).await?;
+    input.read_to_end(&mut buffer).await?;
+    assert_eq!(buffer, &content[content.len() - 100..]);
+
+    let r = input.seek(SeekFrom::End(1)).await;
+    assert!(r.is_err());
+
+    // With length hint.
+    let mut input = hdfs
+        .get_input_stream("data/test_range", Some(content.len() as u64))
+        .await?;
+    let mut buffer = vec![];
+    input
+        .seek(SeekFrom::Start(MOCK_BLOCK_SIZE as u64 - 1))
+        .await?;
+    input.read_to_end(&mut buffer).await?;
+    assert_eq!(buffer, &content[MOCK_BLOCK_SIZE - 1..]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_input_stream_seek_current() -> Result<()> {
+    let files = Files::default();
+    let hdfs = create_webhdfs(files.clone()).await?;
+
+    let content: Vec<u8> = (0..MOCK_BLOCK_SIZE * 4)
+        .map(|_| rand::random::<u8>())
+        .collect();
+    files
+        .lock()
+        .unwrap()
+        .insert("/databend/data/test_seek".to_string(), content.clone());
+
+    // Without length hint, the first seek drops the opened body and its buffered bytes.
+    let mut input = hdfs.get_input_stream("data/test_seek", None).await?;
+    let mut buffer = vec![0; 100];
+    input.read_exact(&mut buffer).await?;
+    assert_eq!(buffer, &content[..100]);
+
+    assert_eq!(input.seek(SeekFrom::Current(0)).await?, 100);
+    let mut buffer = vec![];
+    input.read_to_end(&mut buffer).await?;
+    assert_eq!(buffer, &content[100..]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_reader_range_read() -> Result<()> {
+    let files = Files::default();
+    let hdfs = create_webhdfs(files.clone()).await?;
+
+    let content: Vec<u8> = (0..MOCK_BLOCK_SIZE * 200)
+        .map(|_| rand::random::<u8>())
+        .collect();
+    files
+        .lock()
+        .unwrap()
+        .insert("/databend/data/test_reader".to_string(), content.clone());
+
+    // The reader is blocking.
+    let expected = content.clone();
+    tokio::task::spawn_blocking(move || -> Result<()> {
+        let mut reader = hdfs.get_reader("data/test_reader", None)?;
+
+        let mut buffer = vec![0; 100];
+        reader.seek(SeekFrom::Start(1500))?;
+        reader.read_exact(&mut buffer)?;
+        assert_eq!(buffer, &expected[1500..1600]);
+
+        // Seek within the read ahead bytes.
+        reader.seek(SeekFrom::Current(1000))?;
+        reader.read_exact(&mut buffer)?;
+        assert_eq!(buffer, &expected[2600..2700]);
+
+        // A read larger than the read ahead size.
+        let mut buffer = vec![0; MOCK_BLOCK_SIZE * 100];
+        reader.seek(SeekFrom::End(-(buffer.len() as i64) - 1))?;
+        reader.read_exact(&mut buffer)?;
+        assert_eq!(
+            buffer,
+            &expected[expected.len() - buffer.len() - 1..expected.len() - 1]
+        );
+
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer)?;
+        assert_eq!(buffer, &expected[expected.len() - 1..]);
+
+        assert!(reader.seek(SeekFrom::End(1)).is_err());
+        Ok(())
+    })
+    .await
+    .unwrap()?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_put_stream() -> Result<()> {
+    let files = Files::default();
+    let hdfs = create_webhdfs(files.clone()).await?;
+
+    // Created with the first chunk, then appended chunk by chunk.
+    let content: Vec<u8> = (0..PUT_STREAM_CHUNK_SIZE * 2 + PUT_STREAM_CHUNK_SIZE / 2)
+        .map(|_| rand::random::<u8>())
+        .collect();
+    let stream = futures::stream::iter(
+        content
+            .chunks(MOCK_BLOCK_SIZE * 100)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect::<Vec<std::io::Result<Vec<u8>>>>(),
+    );
+    hdfs.put_stream("data/test_put_stream", Box::new(stream), content.len())
+        .await?;
+    assert_eq!(hdfs.get("data/test_put_stream").await?, content);
+
+    // Overwrite with an empty stream.
+    let stream = futures::stream::iter(Vec::<std::io::Result<Vec<u8>>>::new());
+    hdfs.put_stream("data/test_put_stream", Box::new(stream), 0)
+        .await?;
+    assert!(hdfs.get("data/test_put_stream").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_webhdfs_errors() -> Result<()> {
+    let hdfs = create_webhdfs(Files::default()).await?;
+
+    let r = hdfs.get("data/not_exists").await;
+    let err = r.unwrap_err();
+    assert!(err.message().contains("FileNotFoundException"));
+
+    let r = hdfs.get("secure/file").await;
+    let err = r.unwrap_err();
+    assert!(err.message().contains("only the simple authentication"));
+
+    assert!(WebHdfs::try_create("not a url", "/", None).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+
+use common_base::tokio;
+use futures::AsyncRead;
+use futures::AsyncSeek;
+use futures::Future;
+use metrics::histogram;
+
+use crate::DalContext;
+use crate::InputStream;
+
+pub static METRIC_DAL_READ_LATENCY: &str = "dal.read_latency";
+
+/// An InputStream wrapper, which records the read bytes and the latency of each
+/// underlying read/seek call.
+///
+/// The latency of a call is measured from the first poll till it is ready,
+/// so pending polls of async backends are taken into account. The read latencies always
+/// go to the `ctx`, the global histogram only when there is a metrics recorder installed.
+///
+/// The reads are throttled by the read bandwidth limit of the `ctx`, the bytes of a read
+/// are paid after the read, so it is the next read that waits for them. The wait is not
+/// counted in the latency.
+pub struct InputStreamWithMetric {
+    inner: InputStream,
+    scheme: &'static str,
+    ctx: Arc<DalContext>,
+    recording: bool,
+    read_start: Option<Instant>,
+    seek_start: Option<Instant>,
+    throttle: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl InputStreamWithMetric {
+    pub fn new(inner: InputStream, scheme: &'static str, ctx: Arc<DalContext>) -> Self {
+        InputStreamWithMetric {
+            inner,
+            scheme,
+            ctx,
+            recording: metrics::try_recorder().is_some(),
+            read_start: None,
+            seek_start: None,
+            throttle: None,
+        }
+    }
+
+    #[inline]
+    fn report(&self, operation: &'static str, start: Instant) {
+        let elapsed = start.elapsed();
+        if operation == "read" {
+            self.ctx.observe_read_latency(elapsed);
+        }
+        if self.recording {
+            histogram!(METRIC_DAL_READ_LATENCY, elapsed, "scheme" => self.scheme, "operation" => operation);
+        }
+    }
+}
+
+impl AsyncRead for InputStreamWithMetric {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if let Some(throttle) = self.throttle.as_mut() {
+            match throttle.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(_) => self.throttle = None,
+            }
+        }
+
+        let start = *self.read_start.get_or_insert_with(Instant::now);
+        match Pin::new(&mut self.inner).poll_read(cx, buf) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(res) => {
+                self.read_start = None;
+                self.report("read", start);
+                if let Ok(bytes) = &res {
+                    self.ctx.inc_read_bytes(*bytes);
+                    if *bytes > 0 {
+                        let wait = self.ctx.reserve_read(*bytes);
+                        self.throttle = wait.map(|wait| Box::pin(tokio::time::sleep(wait)));
+                    }
+                }
+                Poll::Ready(res)
+            }
+        }
+    }
+}
+
+impl AsyncSeek for InputStreamWithMetric {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let start = *self.seek_start.get_or_insert_with(Instant::now);
+        match Pin::new(&mut self.inner).poll_seek(cx, pos) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(res) => {
+                self.seek_start = None;
+                self.report("seek", start);
+                Poll::Ready(res)
+            }
+        }
+    }
+}
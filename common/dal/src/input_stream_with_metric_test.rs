@@ -0,0 +1,137 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use common_base::tokio;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+use futures::AsyncSeek;
+use futures::AsyncSeekExt;
+use rand::Rng;
+
+use crate::DalContext;
+use crate::DataAccessor;
+use crate::DataAccessorWithMetric;
+use crate::InputStreamWithMetric;
+use crate::Local;
+
+/// Returns one byte per read, each read sleeps for the next duration of `latencies`.
+struct SleepyStream {
+    latencies: Vec<Duration>,
+    pos: usize,
+}
+
+impl AsyncRead for SleepyStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.latencies.get(self.pos).cloned() {
+            None => Poll::Ready(Ok(0)),
+            Some(latency) => {
+                std::thread::sleep(latency);
+                buf[0] = self.pos as u8;
+                self.pos += 1;
+                Poll::Ready(Ok(1))
+            }
+        }
+    }
+}
+
+impl AsyncSeek for SleepyStream {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        if let SeekFrom::Start(pos) = pos {
+            self.pos = pos as usize;
+        }
+        Poll::Ready(Ok(self.pos as u64))
+    }
+}
+
+#[tokio::test]
+async fn test_input_stream_with_metric() -> common_exception::Result<()> {
+    // No metrics recorder is installed, the latencies still go to the DalContext.
+    let latencies = vec![
+        Duration::from_millis(0),
+        Duration::from_millis(2),
+        Duration::from_millis(20),
+        Duration::from_millis(120),
+    ];
+    let ctx = Arc::new(DalContext::create());
+    let inner = Box::new(SleepyStream { latencies, pos: 0 });
+    let mut stream = InputStreamWithMetric::new(inner, "local", ctx.clone());
+
+    let mut buffer = vec![];
+    stream.read_to_end(&mut buffer).await?;
+    assert_eq!(buffer, vec![0, 1, 2, 3]);
+    assert_eq!(ctx.get_read_bytes(), 4);
+
+    // 4 reads returning data plus the final read returning 0 bytes.
+    assert_eq!(ctx.get_read_count(), 5);
+    let max = ctx.get_max_read_latency();
+    assert!(max >= Duration::from_millis(120), "max latency: {:?}", max);
+
+    // Buckets: [<1ms, <5ms, <10ms, <50ms, <100ms, <500ms, ...]
+    let histogram = ctx.get_read_latency_histogram();
+    assert_eq!(histogram.iter().sum::<u64>(), 5);
+    assert!(histogram[0] >= 1);
+    assert_eq!(histogram[3] + histogram[4], 1);
+    assert_eq!(histogram[5] + histogram[6], 1);
+
+    // Seeks are not counted as reads.
+    stream.seek(SeekFrom::Start(3)).await?;
+    assert_eq!(ctx.get_read_count(), 5);
+
+    let mut buffer = vec![];
+    stream.read_to_end(&mut buffer).await?;
+    assert_eq!(buffer, vec![3]);
+    assert_eq!(ctx.get_read_bytes(), 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_accessor_with_metric() -> common_exception::Result<()> {
+    let root = std::env::temp_dir().join(format!(
+        "dal_metric_test_{}",
+        rand::thread_rng().gen::<u64>()
+    ));
+    std::fs::create_dir_all(&root)?;
+    let root_str = root.to_str().unwrap().to_string();
+
+    let ctx = Arc::new(DalContext::create());
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::with_context(&root_str, ctx.clone()));
+    let da = DataAccessorWithMetric::wrap(local, "local", ctx.clone());
+
+    da.put("file", vec![1u8; 1024]).await?;
+    assert_eq!(da.read("file").await?, vec![1u8; 1024]);
+    assert_eq!(ctx.get_read_bytes(), 1024);
+    assert!(ctx.get_read_count() >= 2);
+    assert_eq!(
+        ctx.get_read_latency_histogram().iter().sum::<u64>(),
+        ctx.get_read_count() as u64
+    );
+
+    std::fs::remove_dir_all(&root)?;
+    Ok(())
+}
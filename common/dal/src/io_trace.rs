@@ -0,0 +1,71 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// One storage request of a query.
+#[derive(Clone, Debug)]
+pub struct IoTraceEntry {
+    pub start: SystemTime,
+    pub path: String,
+    pub operation: &'static str,
+    /// The offset in the object of a ranged read, None for the whole object.
+    pub offset: Option<u64>,
+    pub bytes: usize,
+    pub latency: Duration,
+    /// Who issued the request, e.g. "block_read" or "snapshot".
+    pub tag: &'static str,
+}
+
+/// A bounded log of the storage requests of a query, the oldest entries are dropped first.
+#[derive(Debug)]
+pub struct IoTrace {
+    capacity: usize,
+    entries: Mutex<VecDeque<IoTraceEntry>>,
+    dropped: AtomicUsize,
+}
+
+impl IoTrace {
+    pub fn create(capacity: usize) -> Self {
+        IoTrace {
+            capacity: std::cmp::max(capacity, 1),
+            entries: Mutex::new(VecDeque::new()),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn record(&self, entry: IoTraceEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == self.capacity {
+            entries.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> Vec<IoTraceEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+
+    /// How many entries have been dropped because the trace is full.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+use rand::Rng;
+
+use crate::DalContext;
+use crate::DataAccessor;
+use crate::DataAccessorWithTrace;
+use crate::IoTrace;
+use crate::IoTraceEntry;
+use crate::Local;
+
+#[test]
+fn test_io_trace_bounded() -> Result<()> {
+    let trace = IoTrace::create(2);
+    for i in 0..5 {
+        trace.record(IoTraceEntry {
+            start: std::time::SystemTime::now(),
+            path: format!("file_{}", i),
+            operation: "get",
+            offset: None,
+            bytes: i,
+            latency: std::time::Duration::ZERO,
+            tag: "test",
+        });
+    }
+
+    let paths = trace
+        .entries()
+        .iter()
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(paths, vec!["file_3", "file_4"]);
+    assert_eq!(trace.dropped(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_accessor_with_trace() -> Result<()> {
+    let root = std::env::temp_dir().join(format!(
+        "dal_io_trace_test_{}",
+        rand::thread_rng().gen::<u64>()
+    ));
+    std::fs::create_dir_all(&root)?;
+    let root = root.canonicalize()?;
+    let root_str = root.to_str().unwrap().to_string();
+    let local: Arc<dyn DataAccessor> = Arc::new(Local::new(&root_str));
+
+    // Off by default, the accessor is not wrapped.
+    let ctx = Arc::new(DalContext::create());
+    let da = DataAccessorWithTrace::wrap(local.clone(), ctx.clone(), "block_write");
+    assert_eq!(Arc::strong_count(&local), 2);
+    drop(da);
+    assert!(ctx.get_io_trace().is_none());
+
+    let ctx = Arc::new(DalContext::create().with_io_trace(1024));
+    let writer = DataAccessorWithTrace::wrap(local.clone(), ctx.clone(), "block_write");
+    writer.put("file", vec![7u8; 100]).await?;
+
+    let reader = DataAccessorWithTrace::wrap(local, ctx.clone(), "block_read");
+    let mut stream = reader.get_input_stream("file", None).await?;
+    stream.seek(SeekFrom::Start(90)).await?;
+    let mut buf = vec![];
+    stream.read_to_end(&mut buf).await?;
+    assert_eq!(buf.len(), 10);
+
+    let entries = ctx.get_io_trace().unwrap().entries();
+    let summary = entries
+        .iter()
+        .filter(|entry| entry.bytes > 0 || entry.operation != "read")
+        .map(|entry| (entry.tag, entry.operation, entry.offset, entry.bytes))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![
+        ("block_write", "put", None, 100),
+        ("block_read", "open", None, 0),
+        ("block_read", "read", Some(90), 10),
+    ]);
+    assert!(entries.iter().all(|entry| entry.path == "file"));
+
+    std::fs::remove_dir_all(&root)?;
+    Ok(())
+}
@@ -12,10 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod bandwidth_limiter_test;
+#[cfg(test)]
+mod input_stream_with_metric_test;
+#[cfg(test)]
+mod io_trace_test;
+
+mod bandwidth_limiter;
+mod dal_context;
 mod data_accessor;
+mod data_accessor_with_metric;
+mod data_accessor_with_trace;
 mod impls;
+mod input_stream_with_metric;
+mod io_trace;
 mod schemes;
 
+pub use bandwidth_limiter::BandwidthLimiter;
+pub use dal_context::DalContext;
+pub use dal_context::OpenFileGuard;
+pub use dal_context::READ_LATENCY_BUCKETS_MS;
 pub use data_accessor::AsyncSeekableReader;
 pub use data_accessor::Bytes;
 pub use data_accessor::DataAccessor;
@@ -24,7 +41,17 @@ pub use data_accessor::DefaultDataAccessorBuilder;
 pub use data_accessor::InputStream;
 pub use data_accessor::ObjectAccessor;
 pub use data_accessor::SeekableReader;
+pub use data_accessor_with_metric::DataAccessorWithMetric;
+pub use data_accessor_with_trace::DataAccessorWithTrace;
 pub use impls::aws_s3::S3InputStream;
 pub use impls::aws_s3::S3;
 pub use impls::local::Local;
+pub use impls::webhdfs::HdfsFileStatus;
+pub use impls::webhdfs::WebHdfs;
+pub use impls::webhdfs::WebHdfsInputStream;
+pub use impls::webhdfs::WebHdfsReader;
+pub use input_stream_with_metric::InputStreamWithMetric;
+pub use input_stream_with_metric::METRIC_DAL_READ_LATENCY;
+pub use io_trace::IoTrace;
+pub use io_trace::IoTraceEntry;
 pub use schemes::StorageScheme;
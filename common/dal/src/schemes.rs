@@ -18,4 +18,5 @@ pub enum StorageScheme {
     LocalFs,
     FuseDfs,
     S3,
+    Hdfs,
 }
@@ -0,0 +1,55 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use criterion::criterion_group;
+use criterion::Criterion;
+
+fn build_block(schema: &DataSchemaRef, start: usize, rows: usize) -> DataBlock {
+    DataBlock::create(schema.clone(), vec![
+        DataColumn::Array(Series::new(
+            (start as i64..(start + rows) as i64).collect::<Vec<_>>(),
+        )),
+        DataColumn::Constant(DataValue::String(Some(b"constant".to_vec())), rows),
+    ])
+}
+
+fn criterion_benchmark_concat(c: &mut Criterion) {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    // 100 blocks of 10000 rows.
+    let blocks = (0..100)
+        .map(|i| build_block(&schema, i * 10_000, 10_000))
+        .collect::<Vec<_>>();
+
+    // One non-empty block among 99 empty ones, as left behind by a selective filter.
+    let sparse_blocks = (0..100)
+        .map(|i| build_block(&schema, 0, if i == 50 { 10_000 } else { 0 }))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("concat_blocks");
+    group.bench_function("concat", |b| {
+        b.iter(|| DataBlock::concat_blocks(&blocks).unwrap())
+    });
+    group.bench_function("concat_skip_empty", |b| {
+        b.iter(|| DataBlock::concat_blocks(&sparse_blocks).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_concat);
@@ -0,0 +1,63 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::GroupHashTable;
+use criterion::criterion_group;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use rand::Rng;
+
+fn build_partials(partials: usize, rows: usize, cardinality: u64) -> Vec<GroupHashTable<u64, u64>> {
+    let mut rng = rand::thread_rng();
+    (0..partials)
+        .map(|_| {
+            let mut table = GroupHashTable::create();
+            for _ in 0..rows {
+                let key = rng.gen_range(0..cardinality);
+                match table.get_mut(&key) {
+                    Some(count) => *count += 1,
+                    None => table.insert(key, 1),
+                }
+            }
+            table
+        })
+        .collect()
+}
+
+fn criterion_benchmark_two_level_merge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("two_level_merge");
+    group.sample_size(10);
+
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, threads| {
+                b.iter_batched(
+                    || build_partials(8, 1_000_000, 2_000_000),
+                    |partials| {
+                        GroupHashTable::merge_parallel(partials, *threads, |a, b| {
+                            *a += b;
+                            Ok(())
+                        })
+                    },
+                    criterion::BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_two_level_merge);
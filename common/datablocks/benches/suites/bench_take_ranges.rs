@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use criterion::criterion_group;
+use criterion::Criterion;
+
+fn build_block(rows: usize) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    DataBlock::create_by_array(schema, vec![
+        Series::new(
+            (0..rows as i64)
+                .map(|v| if v % 7 == 0 { None } else { Some(v) })
+                .collect::<Vec<_>>(),
+        ),
+        Series::new(
+            (0..rows)
+                .map(|v| format!("value_{}", v))
+                .collect::<Vec<_>>()
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<_>>(),
+        ),
+    ])
+}
+
+fn criterion_benchmark_take_ranges(c: &mut Criterion) {
+    let block = build_block(1_000_000);
+
+    // 100 ranges of 5000 rows, every other chunk.
+    let ranges = (0..100)
+        .map(|chunk| chunk * 10_000..chunk * 10_000 + 5_000)
+        .collect::<Vec<Range<usize>>>();
+    let indices = ranges
+        .iter()
+        .flat_map(|range| range.start as u32..range.end as u32)
+        .collect::<Vec<u32>>();
+
+    let mut group = c.benchmark_group("take_ranges");
+    group.bench_function("take_ranges", |b| {
+        b.iter(|| block.take_ranges(&ranges).unwrap())
+    });
+    group.bench_function("take_by_indices", |b| {
+        b.iter(|| {
+            let indices = criterion::black_box(indices.clone());
+            DataBlock::block_take_by_indices(&block, &[], &indices).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_take_ranges);
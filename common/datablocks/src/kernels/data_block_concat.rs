@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_arrow::arrow::array::growable::make_growable;
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -26,19 +31,35 @@ impl DataBlock {
 
         let first_block = &blocks[0];
         for block in blocks.iter() {
-            if block.schema().ne(first_block.schema()) {
-                return Result::Err(ErrorCode::DataStructMissMatch("Schema not matched"));
-            }
+            Self::check_concat_schema(first_block.schema(), block.schema())?;
+        }
+
+        // Empty blocks contribute nothing, skip them.
+        let non_empty = blocks
+            .iter()
+            .filter(|block| block.num_rows() > 0)
+            .collect::<Vec<_>>();
+
+        match non_empty.len() {
+            0 => return Ok(first_block.clone()),
+            // Reuse the columns of the only non-empty block as they are.
+            1 => return Ok(non_empty[0].clone()),
+            _ => {}
         }
 
+        let num_rows = non_empty.iter().map(|block| block.num_rows()).sum();
         let mut concat_columns = Vec::with_capacity(first_block.num_columns());
-        for (i, _f) in blocks[0].schema().fields().iter().enumerate() {
-            let mut columns = Vec::with_capacity(blocks.len());
-            for block in blocks.iter() {
-                columns.push(block.column(i).clone());
-            }
+        for i in 0..first_block.num_columns() {
+            let columns = non_empty
+                .iter()
+                .map(|block| block.column(i))
+                .collect::<Vec<_>>();
 
-            concat_columns.push(DataColumnCommon::concat(&columns)?);
+            let column = match Self::same_constant(&columns) {
+                Some(value) => DataColumn::Constant(value, num_rows),
+                None => Self::concat_columns(&columns, num_rows)?,
+            };
+            concat_columns.push(column);
         }
 
         Ok(DataBlock::create(
@@ -46,4 +67,64 @@ impl DataBlock {
             concat_columns,
         ))
     }
+
+    fn check_concat_schema(expect: &DataSchemaRef, actual: &DataSchemaRef) -> Result<()> {
+        if expect.eq(actual) {
+            return Ok(());
+        }
+
+        if expect.fields().len() != actual.fields().len() {
+            return Err(ErrorCode::DataStructMissMatch(format!(
+                "Schema not matched, expect {} columns, but got {}",
+                expect.fields().len(),
+                actual.fields().len()
+            )));
+        }
+
+        for (expect_field, actual_field) in expect.fields().iter().zip(actual.fields().iter()) {
+            if expect_field != actual_field {
+                return Err(ErrorCode::DataStructMissMatch(format!(
+                    "Schema not matched, expect field {:?}, but got {:?}",
+                    expect_field, actual_field
+                )));
+            }
+        }
+
+        Err(ErrorCode::DataStructMissMatch("Schema not matched"))
+    }
+
+    /// Concatenates the columns of `num_rows` rows in total, the array is allocated once
+    /// for all the rows instead of growing block by block.
+    fn concat_columns(columns: &[&DataColumn], num_rows: usize) -> Result<DataColumn> {
+        let arrays = columns
+            .iter()
+            .map(|column| column.get_array_ref())
+            .collect::<Result<Vec<_>>>()?;
+        let arrays = arrays
+            .iter()
+            .map(|array| array.as_ref())
+            .collect::<Vec<_>>();
+
+        let use_validity = arrays.iter().any(|array| array.null_count() > 0);
+        let mut growable = make_growable(&arrays, use_validity, num_rows);
+        for (index, array) in arrays.iter().enumerate() {
+            growable.extend(index, 0, array.len());
+        }
+
+        let array: ArrayRef = Arc::from(growable.as_box());
+        Ok(DataColumn::Array(array.into_series()))
+    }
+
+    /// Returns the value if all the columns are constants of the same value.
+    fn same_constant(columns: &[&DataColumn]) -> Option<DataValue> {
+        let mut res: Option<&DataValue> = None;
+        for column in columns {
+            match (*column, res) {
+                (DataColumn::Constant(value, _), None) => res = Some(value),
+                (DataColumn::Constant(value, _), Some(prev)) if value == prev => {}
+                _ => return None,
+            }
+        }
+        res.cloned()
+    }
 }
@@ -62,3 +62,159 @@ fn test_data_block_concat() -> Result<()> {
     crate::assert_blocks_eq(expected, &[results]);
     Ok(())
 }
+
+#[test]
+fn test_data_block_concat_skip_empty() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    let empty = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(Vec::<i64>::new()),
+        Series::new(Vec::<&str>::new()),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1i64, 2, 3]),
+        Series::new(vec!["b1", "b2", "b3"]),
+    ]);
+
+    let results = DataBlock::concat_blocks(&[empty.clone(), block.clone(), empty.clone()])?;
+    assert_eq!(results.num_rows(), 3);
+    for i in 0..block.num_columns() {
+        match (block.column(i), results.column(i)) {
+            (DataColumn::Array(expect), DataColumn::Array(actual)) => {
+                assert!(std::sync::Arc::ptr_eq(&expect.0, &actual.0));
+            }
+            _ => panic!("Expect array columns"),
+        }
+    }
+
+    // All empty.
+    let results = DataBlock::concat_blocks(&[empty.clone(), empty])?;
+    assert_eq!(results.num_rows(), 0);
+    assert_eq!(results.num_columns(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_data_block_concat_constant() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    let create_block = |a: Vec<i64>, b: DataColumn| {
+        DataBlock::create(schema.clone(), vec![DataColumn::Array(Series::new(a)), b])
+    };
+
+    // Constant columns of the same value are kept constant.
+    let blocks = vec![
+        create_block(
+            vec![1, 2],
+            DataColumn::Constant(DataValue::String(Some(b"x".to_vec())), 2),
+        ),
+        create_block(
+            vec![3],
+            DataColumn::Constant(DataValue::String(Some(b"x".to_vec())), 1),
+        ),
+    ];
+    let results = DataBlock::concat_blocks(&blocks)?;
+    match results.column(1) {
+        DataColumn::Constant(value, size) => {
+            assert_eq!(value, &DataValue::String(Some(b"x".to_vec())));
+            assert_eq!(*size, 3);
+        }
+        _ => panic!("Expect a constant column"),
+    }
+
+    // Different constants or mixed constant/array inputs are materialized.
+    let blocks = vec![
+        create_block(
+            vec![1, 2],
+            DataColumn::Constant(DataValue::String(Some(b"x".to_vec())), 2),
+        ),
+        create_block(
+            vec![3],
+            DataColumn::Constant(DataValue::String(Some(b"y".to_vec())), 1),
+        ),
+        create_block(vec![4], DataColumn::Array(Series::new(vec!["z"]))),
+    ];
+    let results = DataBlock::concat_blocks(&blocks)?;
+    assert!(matches!(results.column(1), DataColumn::Array(_)));
+
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | x |",
+        "| 3 | y |",
+        "| 4 | z |",
+        "+---+---+",
+    ];
+    crate::assert_blocks_eq(expected, &[results]);
+    Ok(())
+}
+
+#[test]
+fn test_data_block_concat_nullable() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, true)]);
+
+    // Only some of the blocks have nulls.
+    let blocks = vec![
+        DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i64, 2])]),
+        DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![Some(3i64), None])]),
+        DataBlock::create(schema, vec![DataColumn::Constant(
+            DataValue::Int64(None),
+            2,
+        )]),
+    ];
+
+    let results = DataBlock::concat_blocks(&blocks)?;
+    let values = (0..results.num_rows())
+        .map(|row| results.column(0).try_get(row))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(values, vec![
+        DataValue::Int64(Some(1)),
+        DataValue::Int64(Some(2)),
+        DataValue::Int64(Some(3)),
+        DataValue::Int64(None),
+        DataValue::Int64(None),
+        DataValue::Int64(None),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_data_block_concat_schema_mismatch() -> Result<()> {
+    let schema_a = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let schema_b = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("c", DataType::String, false),
+    ]);
+
+    let blocks = vec![
+        DataBlock::create_by_array(schema_a, vec![
+            Series::new(vec![1i64]),
+            Series::new(vec!["b1"]),
+        ]),
+        DataBlock::create_by_array(schema_b, vec![
+            Series::new(vec![2i64]),
+            Series::new(vec!["c1"]),
+        ]),
+    ];
+
+    let result = DataBlock::concat_blocks(&blocks);
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.code(),
+        common_exception::ErrorCode::DataStructMissMatch("").code()
+    );
+    assert!(err.message().contains("name: \"c\""));
+    Ok(())
+}
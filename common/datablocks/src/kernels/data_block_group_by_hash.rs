@@ -12,18 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::marker::PhantomData;
 
 use common_datavalues::prelude::*;
 use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::DataBlock;
+use crate::GroupHashTable;
 
-type GroupIndices<T> = HashMap<T, (Vec<u32>, Vec<DataValue>), ahash::RandomState>;
+pub type GroupIndices<T> = GroupHashTable<T, (Vec<u32>, Vec<DataValue>)>;
 type GroupBlock<T> = Vec<(T, Vec<DataValue>, DataBlock)>;
 
 pub trait HashMethod {
@@ -233,9 +234,15 @@ where T: DFPrimitiveType
         keys: Vec<T>,
         group_fields: &[DataField],
     ) -> Result<Vec<Series>> {
+        let step = std::mem::size_of::<T>();
+        let sizes = group_fields
+            .iter()
+            .map(|f| common_datavalues::numeric_byte_size(f.data_type()))
+            .collect::<Result<Vec<_>>>()?;
+        let offsets = fixed_keys_offsets(&sizes, step)?;
+
         let mut keys = keys;
         let rows = keys.len();
-        let step = std::mem::size_of::<T>();
         let length = rows * step;
         let capacity = keys.capacity() * step;
         let mutptr = keys.as_mut_ptr() as *mut u8;
@@ -246,15 +253,12 @@ where T: DFPrimitiveType
         };
 
         let mut res = Vec::with_capacity(group_fields.len());
-        let mut offsize = 0;
-        for f in group_fields.iter() {
+        for (f, offset) in group_fields.iter().zip(offsets.into_iter()) {
             let data_type = f.data_type();
             let mut deserializer = data_type.create_serializer(rows)?;
             let reader = vec8.as_slice();
-            deserializer.de_batch(&reader[offsize..], step, rows)?;
+            deserializer.de_batch(&reader[offset..], step, rows)?;
             res.push(deserializer.finish_to_series());
-
-            offsize += common_datavalues::numeric_byte_size(data_type)?;
         }
         Ok(res)
     }
@@ -275,34 +279,58 @@ where
         let step = std::mem::size_of::<T>();
         let mut group_keys: Vec<T> = vec![T::default(); rows];
         let ptr = group_keys.as_mut_ptr() as *mut u8;
-        let mut offsize = 0;
-        let mut size = step;
-        while size > 0 {
-            build(size, &mut offsize, group_columns, ptr, step)?;
-            size /= 2;
+
+        let sizes = group_columns
+            .iter()
+            .map(|col| common_datavalues::numeric_byte_size(&col.data_type()))
+            .collect::<Result<Vec<_>>>()?;
+        let offsets = fixed_keys_offsets(&sizes, step)?;
+
+        for (col, offset) in group_columns.iter().zip(offsets.into_iter()) {
+            let series = col.to_array()?;
+            let writer = unsafe { ptr.add(offset) };
+            series.fixed_hash(writer, step)?;
         }
         Ok(group_keys)
     }
 }
 
-#[inline]
-fn build(
-    mem_size: usize,
-    offsize: &mut usize,
-    group_columns: &[&DataColumn],
-    writer: *mut u8,
-    step: usize,
-) -> Result<()> {
-    for col in group_columns.iter() {
-        let data_type = col.data_type();
-        let size = common_datavalues::numeric_byte_size(&data_type)?;
-        if size == mem_size {
-            let series = col.to_array()?;
-
-            let writer = unsafe { writer.add(*offsize) };
-            series.fixed_hash(writer, step)?;
-            *offsize += size;
+/// The byte offsets of the group columns inside a fixed key.
+///
+/// Columns are packed by descending byte size (and by declaration order for the same size),
+/// so that every column is aligned to its size. The offsets are returned in declaration order,
+/// build_keys and de_group_columns must both use them to agree on the layout.
+fn fixed_keys_offsets(sizes: &[usize], step: usize) -> Result<Vec<usize>> {
+    let mut offsets = vec![None; sizes.len()];
+    let mut offset = 0;
+    let mut size = step;
+    while size > 0 {
+        for (index, column_size) in sizes.iter().enumerate() {
+            if *column_size == size {
+                offsets[index] = Some(offset);
+                offset += size;
+            }
         }
+        size /= 2;
+    }
+
+    if offset > step {
+        return Err(ErrorCode::BadArguments(format!(
+            "Group columns of {} bytes do not fit in a fixed key of {} bytes",
+            offset, step
+        )));
     }
-    Ok(())
+
+    offsets
+        .into_iter()
+        .zip(sizes.iter())
+        .map(|(offset, size)| {
+            offset.ok_or_else(|| {
+                ErrorCode::BadArguments(format!(
+                    "Group column of {} bytes cannot be packed in a fixed key of {} bytes",
+                    size, step
+                ))
+            })
+        })
+        .collect()
 }
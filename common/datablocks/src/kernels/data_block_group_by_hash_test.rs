@@ -14,6 +14,8 @@
 
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use rand::rngs::ThreadRng;
+use rand::Rng;
 
 use crate::*;
 
@@ -60,3 +62,102 @@ fn test_data_block_group_by_hash() -> Result<()> {
     ]);
     Ok(())
 }
+
+fn de_group_columns(block: &DataBlock, names: &[String]) -> Result<Vec<Series>> {
+    let rows = block.num_rows();
+    let mut columns = Vec::with_capacity(names.len());
+    let mut fields = Vec::with_capacity(names.len());
+    for name in names {
+        columns.push(block.try_column_by_name(name)?);
+        fields.push(block.schema().field_with_name(name)?.clone());
+    }
+
+    match DataBlock::choose_hash_method(block, names)? {
+        HashMethodKind::Serializer(m) => m.de_group_columns(m.build_keys(&columns, rows)?, &fields),
+        HashMethodKind::KeysU8(m) => m.de_group_columns(m.build_keys(&columns, rows)?, &fields),
+        HashMethodKind::KeysU16(m) => m.de_group_columns(m.build_keys(&columns, rows)?, &fields),
+        HashMethodKind::KeysU32(m) => m.de_group_columns(m.build_keys(&columns, rows)?, &fields),
+        HashMethodKind::KeysU64(m) => m.de_group_columns(m.build_keys(&columns, rows)?, &fields),
+    }
+}
+
+fn random_series(rng: &mut ThreadRng, data_type: &DataType, rows: usize) -> Series {
+    match data_type {
+        DataType::UInt8 => Series::new((0..rows).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()),
+        DataType::UInt16 => Series::new((0..rows).map(|_| rng.gen::<u16>()).collect::<Vec<_>>()),
+        DataType::UInt32 => Series::new((0..rows).map(|_| rng.gen::<u32>()).collect::<Vec<_>>()),
+        DataType::UInt64 => Series::new((0..rows).map(|_| rng.gen::<u64>()).collect::<Vec<_>>()),
+        DataType::Int8 => Series::new((0..rows).map(|_| rng.gen::<i8>()).collect::<Vec<_>>()),
+        DataType::Int16 => Series::new((0..rows).map(|_| rng.gen::<i16>()).collect::<Vec<_>>()),
+        DataType::Int32 => Series::new((0..rows).map(|_| rng.gen::<i32>()).collect::<Vec<_>>()),
+        _ => Series::new((0..rows).map(|_| rng.gen::<i64>()).collect::<Vec<_>>()),
+    }
+}
+
+#[test]
+fn test_de_group_columns_mixed_width() -> Result<()> {
+    // Declared as u8, u32, u16 but packed as u32, u16, u8.
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt8, false),
+        DataField::new("b", DataType::UInt32, false),
+        DataField::new("c", DataType::UInt16, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1u8, 2, 3]),
+        Series::new(vec![100000u32, 200000, 300000]),
+        Series::new(vec![1000u16, 2000, 3000]),
+    ]);
+
+    let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    let method = DataBlock::choose_hash_method(&block, &names)?;
+    assert_eq!(method.name(), HashMethodKeysU64::default().name());
+
+    let columns = de_group_columns(&block, &names)?;
+    assert_eq!(columns[0].to_values()?, block.column(0).to_values()?);
+    assert_eq!(columns[1].to_values()?, block.column(1).to_values()?);
+    assert_eq!(columns[2].to_values()?, block.column(2).to_values()?);
+    Ok(())
+}
+
+#[test]
+fn test_de_group_columns_round_trip() -> Result<()> {
+    let types = vec![
+        DataType::UInt8,
+        DataType::UInt16,
+        DataType::UInt32,
+        DataType::UInt64,
+        DataType::Int8,
+        DataType::Int16,
+        DataType::Int32,
+        DataType::Int64,
+    ];
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..2000 {
+        let num_columns = rng.gen_range(1..=4);
+        let rows = rng.gen_range(1..=32);
+
+        let mut fields = Vec::with_capacity(num_columns);
+        let mut series = Vec::with_capacity(num_columns);
+        for i in 0..num_columns {
+            let data_type = types[rng.gen_range(0..types.len())].clone();
+            series.push(random_series(&mut rng, &data_type, rows));
+            fields.push(DataField::new(&format!("c{}", i), data_type, false));
+        }
+
+        let names = fields.iter().map(|f| f.name().clone()).collect::<Vec<_>>();
+        let block = DataBlock::create_by_array(DataSchemaRefExt::create(fields), series);
+
+        let columns = de_group_columns(&block, &names)?;
+        assert_eq!(columns.len(), num_columns);
+        for (i, column) in columns.iter().enumerate() {
+            assert_eq!(
+                column.to_values()?,
+                block.column(i).to_values()?,
+                "schema: {:?}",
+                block.schema()
+            );
+        }
+    }
+    Ok(())
+}
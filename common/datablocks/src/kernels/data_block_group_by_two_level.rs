@@ -0,0 +1,270 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::RawEntryMut;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Number of sub-tables of a two-level table, bucketed by the high byte of the key hash.
+pub const TWO_LEVEL_BUCKETS_NUM: usize = 256;
+
+/// Number of distinct keys above which a one-level table is converted to two-level.
+pub const TWO_LEVEL_THRESHOLD: usize = 100_000;
+
+type SubTable<K, V> = HashMap<K, V, ahash::RandomState>;
+
+/// A hash table used by the group-by kernels.
+///
+/// It starts as a single flat table (one-level). Once the number of distinct keys exceeds
+/// the threshold, it is split into 256 sub-tables (two-level), keyed by the high byte of the
+/// key hash. All the tables are built with the same fixed seeds, so the same key always lands
+/// in the same bucket, which makes it possible to merge the partial tables bucket by bucket
+/// on different threads.
+pub struct GroupHashTable<K, V> {
+    threshold: usize,
+    random_state: ahash::RandomState,
+    tables: Vec<SubTable<K, V>>,
+}
+
+impl<K: Hash + Eq, V> GroupHashTable<K, V> {
+    pub fn create() -> Self {
+        Self::with_threshold(TWO_LEVEL_THRESHOLD)
+    }
+
+    pub fn with_threshold(threshold: usize) -> Self {
+        let random_state = Self::fixed_random_state();
+        GroupHashTable {
+            threshold,
+            tables: vec![SubTable::with_hasher(random_state.clone())],
+            random_state,
+        }
+    }
+
+    #[inline]
+    fn fixed_random_state() -> ahash::RandomState {
+        ahash::RandomState::with_seeds(
+            0x243f_6a88_85a3_08d3,
+            0x1319_8a2e_0370_7344,
+            0xa409_3822_299f_31d0,
+            0x082e_fa98_ec4e_6c89,
+        )
+    }
+
+    #[inline]
+    fn hash_key(&self, key: &K) -> u64 {
+        let mut hasher = self.random_state.build_hasher();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[inline]
+    fn bucket(&self, hash: u64) -> usize {
+        match self.tables.len() {
+            1 => 0,
+            _ => (hash >> 56) as usize,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.iter().map(|table| table.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.iter().all(|table| table.is_empty())
+    }
+
+    pub fn is_two_level(&self) -> bool {
+        self.tables.len() == TWO_LEVEL_BUCKETS_NUM
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let hash = self.hash_key(key);
+        let bucket = self.bucket(hash);
+        match self.tables[bucket]
+            .raw_entry_mut()
+            .from_key_hashed_nocheck(hash, key)
+        {
+            RawEntryMut::Occupied(entry) => Some(entry.into_mut()),
+            RawEntryMut::Vacant(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) {
+        let hash = self.hash_key(&key);
+        let bucket = self.bucket(hash);
+        Self::insert_hashed(&mut self.tables[bucket], hash, key, value);
+
+        if !self.is_two_level() && self.tables[0].len() > self.threshold {
+            self.convert_to_two_level();
+        }
+    }
+
+    /// Split the flat table into 256 sub-tables, it is a no-op for a two-level table.
+    pub fn convert_to_two_level(&mut self) {
+        if self.is_two_level() {
+            return;
+        }
+
+        let one_level = match self.tables.pop() {
+            Some(table) => table,
+            None => SubTable::with_hasher(self.random_state.clone()),
+        };
+        let capacity = one_level.len() / TWO_LEVEL_BUCKETS_NUM;
+        self.tables = (0..TWO_LEVEL_BUCKETS_NUM)
+            .map(|_| SubTable::with_capacity_and_hasher(capacity, self.random_state.clone()))
+            .collect();
+
+        for (key, value) in one_level {
+            let hash = self.hash_key(&key);
+            Self::insert_hashed(&mut self.tables[(hash >> 56) as usize], hash, key, value);
+        }
+    }
+
+    #[inline]
+    fn insert_hashed(table: &mut SubTable<K, V>, hash: u64, key: K, value: V) {
+        match table.raw_entry_mut().from_key_hashed_nocheck(hash, &key) {
+            RawEntryMut::Occupied(mut entry) => {
+                entry.insert(value);
+            }
+            RawEntryMut::Vacant(entry) => {
+                entry.insert_hashed_nocheck(hash, key, value);
+            }
+        }
+    }
+
+    /// Returns the 256 sub-tables, converting to two-level first if necessary.
+    pub fn into_buckets(mut self) -> Vec<HashMap<K, V, ahash::RandomState>> {
+        self.convert_to_two_level();
+        self.tables
+    }
+
+    /// Merge other into self, values of the same key are combined by `merge`.
+    pub fn merge<F: Fn(&mut V, V)>(&mut self, other: Self, merge: F) {
+        for (key, value) in other {
+            match self.get_mut(&key) {
+                Some(exists) => merge(exists, value),
+                None => self.insert(key, value),
+            }
+        }
+    }
+}
+
+impl<K, V> GroupHashTable<K, V>
+where
+    K: Hash + Eq + Send + 'static,
+    V: Send + 'static,
+{
+    /// Merge the partial tables into one two-level table.
+    ///
+    /// Bucket `i` of the result only depends on bucket `i` of each partial, so the buckets
+    /// are distributed over `threads` threads and merged independently. The first error
+    /// returned by `merge` fails the whole merge.
+    pub fn merge_parallel<F>(partials: Vec<Self>, threads: usize, merge: F) -> Result<Self>
+    where F: Fn(&mut V, V) -> Result<()> + Send + Sync + 'static {
+        let threads = std::cmp::max(1, std::cmp::min(threads, TWO_LEVEL_BUCKETS_NUM));
+
+        // tasks[thread] = [(bucket_index, [bucket of each partial])]
+        let mut tasks: Vec<Vec<(usize, Vec<SubTable<K, V>>)>> = (0..threads)
+            .map(|_| Vec::with_capacity(TWO_LEVEL_BUCKETS_NUM / threads + 1))
+            .collect();
+
+        let mut buckets_of_partials = partials
+            .into_iter()
+            .map(|partial| partial.into_buckets().into_iter())
+            .collect::<Vec<_>>();
+
+        for bucket in 0..TWO_LEVEL_BUCKETS_NUM {
+            let mut same_buckets = Vec::with_capacity(buckets_of_partials.len());
+            for buckets in buckets_of_partials.iter_mut() {
+                if let Some(table) = buckets.next() {
+                    same_buckets.push(table);
+                }
+            }
+            tasks[bucket % threads].push((bucket, same_buckets));
+        }
+
+        let merge = Arc::new(merge);
+        let handles = tasks
+            .into_iter()
+            .map(|task| {
+                let merge = merge.clone();
+                std::thread::spawn(move || {
+                    task.into_iter()
+                        .map(|(bucket, tables)| Ok((bucket, Self::merge_bucket(tables, &*merge)?)))
+                        .collect::<Result<Vec<_>>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut res = Self::create();
+        res.convert_to_two_level();
+        for handle in handles {
+            let merged = handle.join().map_err(|cause| {
+                ErrorCode::LogicalError(format!(
+                    "Two-level group by merge thread panicked: {:?}",
+                    cause
+                ))
+            })??;
+
+            for (bucket, table) in merged {
+                res.tables[bucket] = table;
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn merge_bucket<F>(tables: Vec<SubTable<K, V>>, merge: &F) -> Result<SubTable<K, V>>
+    where F: Fn(&mut V, V) -> Result<()> {
+        let mut tables = tables.into_iter();
+        let mut res = tables
+            .next()
+            .unwrap_or_else(|| SubTable::with_hasher(Self::fixed_random_state()));
+
+        for table in tables {
+            for (key, value) in table {
+                match res.get_mut(&key) {
+                    Some(exists) => merge(exists, value)?,
+                    None => {
+                        res.insert(key, value);
+                    }
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<K: Hash + Eq, V> Default for GroupHashTable<K, V> {
+    fn default() -> Self {
+        Self::create()
+    }
+}
+
+impl<K, V> IntoIterator for GroupHashTable<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::iter::Flatten<std::vec::IntoIter<SubTable<K, V>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tables.into_iter().flatten()
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use rand::Rng;
+
+use crate::*;
+
+fn random_keys(rows: usize, cardinality: u64) -> Vec<u64> {
+    let mut rng = rand::thread_rng();
+    (0..rows).map(|_| rng.gen_range(0..cardinality)).collect()
+}
+
+fn count_by(table: &mut GroupHashTable<u64, u64>, keys: &[u64]) {
+    for key in keys {
+        match table.get_mut(key) {
+            Some(count) => *count += 1,
+            None => table.insert(*key, 1),
+        }
+    }
+}
+
+fn sorted(table: GroupHashTable<u64, u64>) -> Vec<(u64, u64)> {
+    let mut res = table.into_iter().collect::<Vec<_>>();
+    res.sort_unstable();
+    res
+}
+
+#[test]
+fn test_group_hash_table_two_level() -> Result<()> {
+    let keys = random_keys(100_000, 20_000);
+
+    let mut one_level = GroupHashTable::with_threshold(usize::MAX);
+    count_by(&mut one_level, &keys);
+    assert!(!one_level.is_two_level());
+
+    let mut two_level = GroupHashTable::with_threshold(1000);
+    count_by(&mut two_level, &keys);
+    assert!(two_level.is_two_level());
+
+    assert_eq!(one_level.len(), two_level.len());
+    assert_eq!(sorted(one_level), sorted(two_level));
+    Ok(())
+}
+
+#[test]
+fn test_group_hash_table_merge_parallel() -> Result<()> {
+    let partial_keys = (0..4)
+        .map(|_| random_keys(50_000, 30_000))
+        .collect::<Vec<_>>();
+
+    // Reference: sequential merge of one-level tables.
+    let mut expect = GroupHashTable::with_threshold(usize::MAX);
+    for keys in &partial_keys {
+        let mut partial = GroupHashTable::with_threshold(usize::MAX);
+        count_by(&mut partial, keys);
+        expect.merge(partial, |a, b| *a += b);
+    }
+    let expect = sorted(expect);
+
+    for threads in [1, 3, 8] {
+        // Mix one-level and two-level partials.
+        let partials = partial_keys
+            .iter()
+            .enumerate()
+            .map(|(index, keys)| {
+                let threshold = if index % 2 == 0 { 100 } else { usize::MAX };
+                let mut partial = GroupHashTable::with_threshold(threshold);
+                count_by(&mut partial, keys);
+                partial
+            })
+            .collect::<Vec<_>>();
+
+        let merged = GroupHashTable::merge_parallel(partials, threads, |a, b| {
+            *a += b;
+            Ok(())
+        })?;
+        assert!(merged.is_two_level());
+        assert_eq!(sorted(merged), expect);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_group_by_get_indices_two_level() -> Result<()> {
+    let rows = TWO_LEVEL_THRESHOLD * 2;
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt32, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(
+        (0..rows as u32).map(|v| v / 2).collect::<Vec<u32>>(),
+    )]);
+
+    let method = HashMethodKeysU32::default();
+    let group_indices = method.group_by_get_indices(&block, &["a".to_string()])?;
+    assert!(group_indices.is_two_level());
+    assert_eq!(group_indices.len(), rows / 2);
+
+    for (key, (indices, values)) in group_indices {
+        assert_eq!(indices, vec![key * 2, key * 2 + 1]);
+        assert_eq!(values, vec![DataValue::UInt32(Some(key))]);
+    }
+    Ok(())
+}
@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Range;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::growable::make_growable;
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::DataBlock;
@@ -51,4 +58,54 @@ impl DataBlock {
 
         Ok(DataBlock::create(raw.schema().clone(), columns))
     }
+
+    /// Take the rows of the ranges in order, without building the row indices.
+    ///
+    /// A single range is a zero-copy slice, otherwise the ranges are copied range by range,
+    /// the validity bitmaps are copied with them for nullable columns.
+    pub fn take_ranges(&self, ranges: &[Range<usize>]) -> Result<DataBlock> {
+        let num_rows = self.num_rows();
+        if let Some(range) = ranges
+            .iter()
+            .find(|range| range.start > range.end || range.end > num_rows)
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "Range {:?} is out of the block bounds, rows: {}",
+                range, num_rows
+            )));
+        }
+
+        let ranges = ranges
+            .iter()
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<_>>();
+
+        match ranges.len() {
+            0 => Ok(DataBlock::empty_with_schema(self.schema().clone())),
+            1 => Ok(self.slice(ranges[0].start, ranges[0].len())),
+            _ => {
+                let rows = ranges.iter().map(|range| range.len()).sum();
+                let columns = self
+                    .columns()
+                    .iter()
+                    .map(|column| match column {
+                        DataColumn::Constant(v, _) => DataColumn::Constant(v.clone(), rows),
+                        DataColumn::Array(series) => {
+                            let array = series.get_array_ref();
+                            let use_validity = array.null_count() > 0;
+                            let mut growable = make_growable(&[array.as_ref()], use_validity, rows);
+                            for range in &ranges {
+                                growable.extend(0, range.start, range.len());
+                            }
+
+                            let array: ArrayRef = Arc::from(growable.as_box());
+                            DataColumn::Array(array.into_series())
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(DataBlock::create(self.schema().clone(), columns))
+            }
+        }
+    }
 }
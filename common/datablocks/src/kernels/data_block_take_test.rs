@@ -44,3 +44,87 @@ fn test_data_block_take() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_data_block_take_ranges() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    let raw = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1i64, 2, 3, 4, 5, 6]),
+        Series::new(vec!["b1", "b2", "b3", "b4", "b5", "b6"]),
+    ]);
+
+    // Single range.
+    let take = raw.take_ranges(&[1..3])?;
+    assert_eq!(raw.schema(), take.schema());
+    let expected = vec![
+        "+---+----+",
+        "| a | b  |",
+        "+---+----+",
+        "| 2 | b2 |",
+        "| 3 | b3 |",
+        "+---+----+",
+    ];
+    crate::assert_blocks_eq(expected, &[take]);
+
+    // Multiple disjoint ranges, empty ranges are skipped.
+    let take = raw.take_ranges(&[0..1, 2..2, 3..6, 1..2])?;
+    let expected = vec![
+        "+---+----+",
+        "| a | b  |",
+        "+---+----+",
+        "| 1 | b1 |",
+        "| 4 | b4 |",
+        "| 5 | b5 |",
+        "| 6 | b6 |",
+        "| 2 | b2 |",
+        "+---+----+",
+    ];
+    crate::assert_blocks_eq(expected, &[take]);
+
+    // Empty ranges.
+    assert_eq!(raw.take_ranges(&[])?.num_rows(), 0);
+    assert_eq!(raw.take_ranges(&[4..4, 0..0])?.num_rows(), 0);
+
+    // Out of bounds.
+    assert!(raw.take_ranges(&[5..7]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_data_block_take_ranges_nullable() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::UInt8, false),
+    ]);
+
+    // Null runs cross byte boundaries of the validity bitmap.
+    let values = (0..20)
+        .map(|v| if (5..12).contains(&v) { None } else { Some(v) })
+        .collect::<Vec<Option<i64>>>();
+    let raw = DataBlock::create(schema, vec![
+        Series::new(values.clone()).into(),
+        DataColumn::Constant(DataValue::UInt8(Some(7)), 20),
+    ]);
+
+    let ranges = [3..7, 10..17, 19..20];
+    let take = raw.take_ranges(&ranges)?;
+    assert_eq!(take.num_rows(), 12);
+
+    let expected = ranges
+        .iter()
+        .flat_map(|range| values[range.clone()].iter().cloned())
+        .map(DataValue::Int64)
+        .collect::<Vec<_>>();
+    assert_eq!(take.try_column_by_name("a")?.to_values()?, expected);
+
+    let constant = take.try_column_by_name("b")?;
+    assert!(matches!(constant, DataColumn::Constant(_, 12)));
+    assert_eq!(constant.try_get(11)?, DataValue::UInt8(Some(7)));
+
+    Ok(())
+}
@@ -19,6 +19,8 @@ mod data_block_group_by_hash_test;
 #[cfg(test)]
 mod data_block_group_by_test;
 #[cfg(test)]
+mod data_block_group_by_two_level_test;
+#[cfg(test)]
 mod data_block_scatter_test;
 #[cfg(test)]
 mod data_block_slice_test;
@@ -30,10 +32,12 @@ mod data_block_take_test;
 mod data_block_concat;
 mod data_block_group_by;
 mod data_block_group_by_hash;
+mod data_block_group_by_two_level;
 mod data_block_scatter;
 mod data_block_slice;
 mod data_block_sort;
 mod data_block_take;
 
 pub use data_block_group_by_hash::*;
+pub use data_block_group_by_two_level::*;
 pub use data_block_sort::SortColumnDescription;
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use common_arrow::arrow::compute::comparison::binary_compare_scalar;
@@ -20,11 +22,11 @@ use common_arrow::arrow::compute::comparison::compare;
 use common_arrow::arrow::compute::comparison::primitive_compare_scalar;
 use common_arrow::arrow::compute::comparison::Operator;
 use common_arrow::arrow::compute::comparison::Simd8;
-use common_arrow::arrow::compute::like;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use num::Num;
 use num::NumCast;
+use regex::bytes::Regex;
 
 use crate::prelude::*;
 
@@ -220,25 +222,152 @@ impl DFStringArray {
         Ok(array.into())
     }
 
-    // pub fn like_binary<O: Offset>(lhs: &BinaryArray<O>, rhs: &BinaryArray<O>)
     fn like(&self, rhs: &DFStringArray) -> Result<DFBooleanArray> {
-        let array = like::like_binary(&self.array, &rhs.array)?;
-        Ok(array.into())
+        self.like_array(rhs, false)
     }
 
     fn like_scalar(&self, rhs: &[u8]) -> Result<DFBooleanArray> {
-        let array = like::like_binary_scalar(&self.array, rhs)?;
-        Ok(array.into())
+        if let Some(pattern) = SimpleLikePattern::try_create(rhs) {
+            return Ok(self.like_matches(|value| pattern.matches(value), false));
+        }
+
+        let regex = like_pattern_to_regex(rhs)?;
+        Ok(self.like_matches(|value| regex.is_match(value), false))
     }
 
     fn nlike(&self, rhs: &DFStringArray) -> Result<DFBooleanArray> {
-        let array = like::nlike_binary(&self.array, &rhs.array)?;
-        Ok(array.into())
+        self.like_array(rhs, true)
     }
 
     fn nlike_scalar(&self, rhs: &[u8]) -> Result<DFBooleanArray> {
-        let array = like::nlike_binary_scalar(&self.array, rhs)?;
-        Ok(array.into())
+        if let Some(pattern) = SimpleLikePattern::try_create(rhs) {
+            return Ok(self.like_matches(|value| pattern.matches(value), true));
+        }
+
+        let regex = like_pattern_to_regex(rhs)?;
+        Ok(self.like_matches(|value| regex.is_match(value), true))
+    }
+
+    /// LIKE with a pattern per row, the regex of each distinct pattern is built once.
+    fn like_array(&self, rhs: &DFStringArray, negated: bool) -> Result<DFBooleanArray> {
+        if self.len() != rhs.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "LIKE arrays must have the same length, but got {} and {}",
+                self.len(),
+                rhs.len()
+            )));
+        }
+
+        let mut regexes: HashMap<&[u8], Regex> = HashMap::new();
+        self.into_iter()
+            .zip(rhs.into_iter())
+            .map(|(value, pattern)| match (value, pattern) {
+                (Some(value), Some(pattern)) => {
+                    let regex = match regexes.entry(pattern) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => entry.insert(like_pattern_to_regex(pattern)?),
+                    };
+                    Ok(Some(regex.is_match(value) != negated))
+                }
+                _ => Ok(None),
+            })
+            .collect()
+    }
+
+    fn like_matches<F>(&self, matches: F, negated: bool) -> DFBooleanArray
+    where F: Fn(&[u8]) -> bool {
+        if self.null_count() == 0 {
+            self.into_no_null_iter()
+                .map(|value| matches(value) != negated)
+                .collect()
+        } else {
+            self.into_iter()
+                .map(|value| value.map(|value| matches(value) != negated))
+                .collect()
+        }
+    }
+}
+
+/// Builds the regex of a LIKE pattern with a constant right side.
+///
+/// '%' matches any characters and '_' any single character, both including '\n'. A backslash
+/// escapes the next character, the other characters are matched literally even if they are
+/// regex metacharacters, so the regex agrees with `SimpleLikePattern`.
+pub(crate) fn like_pattern_to_regex(pattern: &[u8]) -> Result<Regex> {
+    let pattern = std::str::from_utf8(pattern)
+        .map_err(|e| ErrorCode::BadArguments(format!("LIKE pattern is not valid UTF-8: {}", e)))?;
+
+    let mut re = String::with_capacity(pattern.len() * 2 + 8);
+    re.push_str("^(?s:");
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => re.push_str(".*"),
+            '_' => re.push('.'),
+            '\\' => {
+                let escaped = chars.next().unwrap_or('\\');
+                re.push_str(&regex::escape(escaped.encode_utf8(&mut [0; 4])));
+            }
+            c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    re.push_str(")$");
+
+    Regex::new(&re).map_err(|e| {
+        ErrorCode::BadArguments(format!("Unable to build regex from LIKE pattern: {}", e))
+    })
+}
+
+/// A LIKE pattern which can be matched by comparing bytes, without the regex engine.
+///
+/// Only '%' at the start and/or the end is supported, patterns with '_', a backslash or
+/// '%' in the middle go to the regex engine.
+#[derive(Debug, PartialEq)]
+pub(crate) enum SimpleLikePattern<'a> {
+    Equals(&'a [u8]),
+    StartsWith(&'a [u8]),
+    EndsWith(&'a [u8]),
+    Contains(&'a [u8]),
+}
+
+impl<'a> SimpleLikePattern<'a> {
+    pub(crate) fn try_create(pattern: &'a [u8]) -> Option<SimpleLikePattern<'a>> {
+        let is_literal = |bytes: &[u8]| !bytes.iter().any(|b| matches!(b, b'%' | b'_' | b'\\'));
+
+        let len = pattern.len();
+        let starts_with_any = pattern.first() == Some(&b'%');
+        let ends_with_any = len > 1 && pattern.last() == Some(&b'%');
+
+        let simple = match (starts_with_any, ends_with_any) {
+            (true, true) => SimpleLikePattern::Contains(&pattern[1..len - 1]),
+            (true, false) => SimpleLikePattern::EndsWith(&pattern[1..]),
+            (false, true) => SimpleLikePattern::StartsWith(&pattern[..len - 1]),
+            (false, false) => SimpleLikePattern::Equals(pattern),
+        };
+
+        let literal = match &simple {
+            SimpleLikePattern::Equals(literal)
+            | SimpleLikePattern::StartsWith(literal)
+            | SimpleLikePattern::EndsWith(literal)
+            | SimpleLikePattern::Contains(literal) => *literal,
+        };
+
+        match is_literal(literal) {
+            true => Some(simple),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn matches(&self, value: &[u8]) -> bool {
+        match self {
+            SimpleLikePattern::Equals(literal) => value == *literal,
+            SimpleLikePattern::StartsWith(literal) => value.starts_with(literal),
+            SimpleLikePattern::EndsWith(literal) => value.ends_with(literal),
+            SimpleLikePattern::Contains(literal) => {
+                literal.is_empty() || value.windows(literal.len()).any(|w| w == *literal)
+            }
+        }
     }
 }
 
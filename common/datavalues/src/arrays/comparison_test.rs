@@ -0,0 +1,180 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::arrays::comparison::like_pattern_to_regex;
+use crate::arrays::comparison::SimpleLikePattern;
+use crate::prelude::*;
+
+#[test]
+fn test_simple_like_pattern() -> Result<()> {
+    let tests: Vec<(&str, Option<SimpleLikePattern>)> = vec![
+        ("abc", Some(SimpleLikePattern::Equals(b"abc"))),
+        ("", Some(SimpleLikePattern::Equals(b""))),
+        ("abc%", Some(SimpleLikePattern::StartsWith(b"abc"))),
+        ("%abc", Some(SimpleLikePattern::EndsWith(b"abc"))),
+        ("%abc%", Some(SimpleLikePattern::Contains(b"abc"))),
+        ("%", Some(SimpleLikePattern::EndsWith(b""))),
+        ("%%", Some(SimpleLikePattern::Contains(b""))),
+        ("a%c", None),
+        ("a_c%", None),
+        ("%a%%", None),
+        ("a\\%%", None),
+    ];
+
+    for (pattern, expect) in tests {
+        let actual = SimpleLikePattern::try_create(pattern.as_bytes());
+        assert_eq!(actual, expect, "{}", pattern);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_like_scalar_same_as_regex() -> Result<()> {
+    // A small alphabet with multi-byte characters, regex metacharacters and '\n',
+    // so that the matches are frequent.
+    let alphabet = ["a", "b", "é", "中", "/", ".", "*", "(", "\n"];
+    let mut seed = 42u64;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as usize
+    };
+
+    let mut random_string = |len: usize| {
+        (0..len)
+            .map(|_| alphabet[next() % alphabet.len()])
+            .collect::<String>()
+    };
+
+    let values = (0..1000)
+        .map(|i| match i % 17 {
+            0 => None,
+            _ => Some(random_string(i % 7)),
+        })
+        .collect::<Vec<_>>();
+    let array = DFStringArray::new_from_opt_slice(&values);
+
+    let mut patterns = vec!["%".to_string(), "%%".to_string(), "".to_string()];
+    for len in 1..4 {
+        for _ in 0..10 {
+            let literal = random_string(len);
+            patterns.push(literal.clone());
+            patterns.push(format!("{}%", literal));
+            patterns.push(format!("%{}", literal));
+            patterns.push(format!("%{}%", literal));
+        }
+    }
+
+    for pattern in patterns {
+        let rhs = DFStringArray::new_from_slice(&[pattern.clone()]);
+        let regex = like_pattern_to_regex(pattern.as_bytes())?;
+        let expect = values
+            .iter()
+            .map(|v| v.as_ref().map(|v| regex.is_match(v.as_bytes())))
+            .collect::<Vec<_>>();
+        let actual = array.like(&rhs)?;
+        assert_eq!(actual.collect_values(), expect, "{}", pattern);
+
+        let expect = expect.iter().map(|v| v.map(|v| !v)).collect::<Vec<_>>();
+        let actual = array.nlike(&rhs)?;
+        assert_eq!(actual.collect_values(), expect, "{}", pattern);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_like_scalar_semantics() -> Result<()> {
+    let values = [
+        "abc", "a.c", "a.cd", "a\nb", "a\nc", "a%", "ab", "a.xc", "abxc",
+    ];
+    let array = DFStringArray::new_from_slice(&values);
+
+    let tests = vec![
+        // Regex metacharacters are matched literally.
+        ("a.c%", vec!["a.c", "a.cd"]),
+        ("a.%c", vec!["a.c", "a.xc"]),
+        // '%' and '_' match '\n'.
+        ("a%", values.to_vec()),
+        ("a_c", vec!["abc", "a.c", "a\nc"]),
+        ("a_b", vec!["a\nb"]),
+        // A backslash escapes the next character.
+        ("a\\%", vec!["a%"]),
+        ("a\\.c", vec!["a.c"]),
+    ];
+
+    for (pattern, expect) in tests {
+        let rhs = DFStringArray::new_from_slice(&[pattern]);
+        let expect = values
+            .iter()
+            .map(|v| Some(expect.contains(v)))
+            .collect::<Vec<_>>();
+        assert_eq!(array.like(&rhs)?.collect_values(), expect, "{}", pattern);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_like_array_same_as_scalar() -> Result<()> {
+    let values = [
+        "abc", "a.c", "a.cd", "a\nb", "a\nc", "a%", "ab", "a.xc", "abxc",
+    ];
+    let patterns = [
+        "a.c%", "a.%c", "a%", "a_c", "a_b", "a\\%", "a\\.c", "%x%", "a(c",
+    ];
+
+    // Every value against every pattern, the pattern of a row comes from the right array.
+    let mut lhs = vec![];
+    let mut rhs = vec![];
+    for value in values.iter() {
+        for pattern in patterns.iter() {
+            lhs.push(Some(*value));
+            rhs.push(Some(*pattern));
+        }
+    }
+    lhs.push(None);
+    rhs.push(Some("%"));
+    lhs.push(Some("abc"));
+    rhs.push(None);
+    let lhs_array = DFStringArray::new_from_opt_slice(&lhs);
+    let rhs_array = DFStringArray::new_from_opt_slice(&rhs);
+
+    let like = lhs_array.like(&rhs_array)?.collect_values();
+    let nlike = lhs_array.nlike(&rhs_array)?.collect_values();
+    for (row, (value, pattern)) in lhs.iter().zip(rhs.iter()).enumerate() {
+        let (value, pattern) = match (value, pattern) {
+            (Some(value), Some(pattern)) => (value, pattern),
+            _ => {
+                assert_eq!(like[row], None);
+                assert_eq!(nlike[row], None);
+                continue;
+            }
+        };
+
+        let scalar = DFStringArray::new_from_slice(&[*value]);
+        let pattern_array = DFStringArray::new_from_slice(&[*pattern]);
+        let expect = scalar.like(&pattern_array)?.collect_values()[0];
+        assert_eq!(like[row], expect, "{:?} LIKE {:?}", value, pattern);
+        assert_eq!(
+            nlike[row],
+            expect.map(|v| !v),
+            "{:?} NOT LIKE {:?}",
+            value,
+            pattern
+        );
+    }
+    Ok(())
+}
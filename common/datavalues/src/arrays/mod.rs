@@ -14,6 +14,8 @@
 
 #[cfg(test)]
 mod arithmetic_test;
+#[cfg(test)]
+mod comparison_test;
 
 #[macro_use]
 mod arithmetic;
@@ -247,6 +247,20 @@ impl DataValue {
             ))),
         }
     }
+
+    pub fn as_f64(&self) -> Result<f64> {
+        match self {
+            DataValue::Float32(Some(v)) => Ok(*v as f64),
+            DataValue::Float64(Some(v)) => Ok(*v),
+            DataValue::UInt64(Some(v)) => Ok(*v as f64),
+            other => other.as_i64().map(|v| v as f64).map_err(|_| {
+                ErrorCode::BadDataValueType(format!(
+                    "Unexpected type:{:?} to get f64 number",
+                    other.data_type()
+                ))
+            }),
+        }
+    }
 }
 
 // Did not use std::convert:TryFrom
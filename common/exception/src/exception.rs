@@ -54,6 +54,8 @@ pub struct ErrorCode {
     // TODO: remove `cause` when we completely get rid of `anyhow::Error`.
     cause: Option<Box<dyn std::error::Error + Sync + Send>>,
     backtrace: Option<ErrorCodeBacktrace>,
+    // Where the error was raised, e.g. the processor of a pipeline.
+    context: Option<String>,
 }
 
 impl ErrorCode {
@@ -74,6 +76,7 @@ impl ErrorCode {
             display_text: format!("{}\n{}", msg.as_ref(), self.display_text),
             cause: self.cause,
             backtrace: self.backtrace,
+            context: self.context,
         }
     }
 
@@ -83,6 +86,19 @@ impl ErrorCode {
             display_text: format!("{}{}", self.display_text, msg.as_ref()),
             cause: self.cause,
             backtrace: self.backtrace,
+            context: self.context,
+        }
+    }
+
+    pub fn context(&self) -> Option<String> {
+        self.context.clone()
+    }
+
+    /// Records where the error was raised, the message is kept unchanged.
+    pub fn set_context(self, context: impl Into<String>) -> Self {
+        Self {
+            context: Some(context.into()),
+            ..self
         }
     }
 
@@ -115,6 +131,7 @@ macro_rules! build_exceptions {
                         display_text: display_text.into(),
                         cause: None,
                         backtrace: Some(ErrorCodeBacktrace::Origin(Arc::new(Backtrace::new()))),
+                        context: None,
                     }
                 })*
             }
@@ -178,6 +195,7 @@ build_exceptions! {
     TLSConfigurationFailure(52),
     UnknownSession(53),
     UnexpectedError(54),
+    ResultSizeExceeded(55),
 
     // uncategorized
     UnexpectedResponseType(600),
@@ -348,6 +366,7 @@ impl From<anyhow::Error> for ErrorCode {
             display_text: format!("{}, source: {:?}", error, error.source()),
             cause: Some(Box::new(OtherErrors::AnyHow { error })),
             backtrace: Some(ErrorCodeBacktrace::Origin(Arc::new(Backtrace::new()))),
+            context: None,
         }
     }
 }
@@ -419,6 +438,7 @@ impl ErrorCode {
             display_text: format!("{}", error),
             cause: None,
             backtrace: Some(ErrorCodeBacktrace::Origin(Arc::new(Backtrace::new()))),
+            context: None,
         }
     }
 
@@ -432,6 +452,7 @@ impl ErrorCode {
             display_text,
             cause: None,
             backtrace,
+            context: None,
         }
     }
 }
@@ -489,6 +510,8 @@ struct SerializedError {
     code: u16,
     message: String,
     backtrace: String,
+    #[serde(default)]
+    context: Option<String>,
 }
 
 impl From<&Status> for ErrorCode {
@@ -497,18 +520,26 @@ impl From<&Status> for ErrorCode {
             tonic::Code::Unknown => {
                 match serde_json::from_slice::<SerializedError>(status.details()) {
                     Err(error) => ErrorCode::from(error),
-                    Ok(serialized_error) => match serialized_error.backtrace.len() {
-                        0 => {
-                            ErrorCode::create(serialized_error.code, serialized_error.message, None)
+                    Ok(serialized_error) => {
+                        let error = match serialized_error.backtrace.len() {
+                            0 => ErrorCode::create(
+                                serialized_error.code,
+                                serialized_error.message,
+                                None,
+                            ),
+                            _ => ErrorCode::create(
+                                serialized_error.code,
+                                serialized_error.message,
+                                Some(ErrorCodeBacktrace::Serialized(Arc::new(
+                                    serialized_error.backtrace,
+                                ))),
+                            ),
+                        };
+                        ErrorCode {
+                            context: serialized_error.context,
+                            ..error
                         }
-                        _ => ErrorCode::create(
-                            serialized_error.code,
-                            serialized_error.message,
-                            Some(ErrorCodeBacktrace::Serialized(Arc::new(
-                                serialized_error.backtrace,
-                            ))),
-                        ),
-                    },
+                    }
                 }
             }
             _ => ErrorCode::UnImplement(status.to_string()),
@@ -532,6 +563,7 @@ impl From<ErrorCode> for Status {
                 str.truncate(2 * 1024);
                 str
             },
+            context: err.context(),
         });
 
         match rst_json {
@@ -547,6 +579,9 @@ impl From<ErrorCode> for Status {
 
 impl Clone for ErrorCode {
     fn clone(&self) -> Self {
-        ErrorCode::create(self.code(), self.message(), self.backtrace())
+        ErrorCode {
+            context: self.context(),
+            ..ErrorCode::create(self.code(), self.message(), self.backtrace())
+        }
     }
 }
@@ -110,3 +110,21 @@ fn test_from_and_to_status() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_error_context() {
+    use crate::exception::*;
+
+    let e = ErrorCode::IllegalDataType("foo");
+    assert_eq!(e.context(), None);
+
+    // The context is kept by the message changes, the clone and the status.
+    let e = e.set_context("FilterTransform").add_message_back(" bar");
+    assert_eq!(e.message(), "foo bar");
+    assert_eq!(e.clone().context(), Some("FilterTransform".to_string()));
+
+    let status: Status = e.into();
+    let e2: ErrorCode = status.into();
+    assert_eq!(e2.message(), "foo bar");
+    assert_eq!(e2.context(), Some("FilterTransform".to_string()));
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use bumpalo::Bump;
+use bytes::BytesMut;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use pretty_assertions::assert_eq;
 
@@ -396,3 +398,108 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_aggregate_sum_overflow() -> Result<()> {
+    let arena = Bump::new();
+    let factory = AggregateFunctionFactory::instance();
+    let overflow = |result: Result<DataValue>, name: &str| match result {
+        Ok(_) => panic!("{} should overflow", name),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::Overflow("").code(), "{}", name),
+    };
+
+    let int64 = vec![DataField::new("a", DataType::Int64, false)];
+    let func = factory.get("sum", vec![], int64.clone())?;
+
+    // Only the final sum must fit in Int64, both by block and by the group by keys.
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i64::MAX, 1, -2]);
+    func.accumulate(place, &[block], 3)?;
+    assert_eq!(
+        func.merge_result(place)?,
+        DataValue::Int64(Some(i64::MAX - 1))
+    );
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i64::MAX, 1, -2]);
+    func.accumulate_keys(&[place, place, place], 0, &[block], 3)?;
+    assert_eq!(
+        func.merge_result(place)?,
+        DataValue::Int64(Some(i64::MAX - 1))
+    );
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i64::MAX - 1, 1, 1]);
+    func.accumulate(place, &[block], 3)?;
+    overflow(func.merge_result(place), "sum(block)");
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i64::MIN, -1]);
+    func.accumulate(place, &[block], 2)?;
+    overflow(func.merge_result(place), "sum(negative block)");
+
+    // The sum of the blocks, it overflows only in between.
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    func.accumulate(place, &[Series::new(vec![i64::MAX])], 1)?;
+    func.accumulate(place, &[Series::new(vec![1i64])], 1)?;
+    overflow(func.merge_result(place), "sum(blocks)");
+    func.accumulate(place, &[Series::new(vec![-1i64])], 1)?;
+    assert_eq!(func.merge_result(place)?, DataValue::Int64(Some(i64::MAX)));
+
+    // The group by keys.
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i64::MAX, 1]);
+    func.accumulate_keys(&[place, place], 0, &[block], 2)?;
+    overflow(func.merge_result(place), "sum(keys)");
+    let result = func.serialize(place, &mut BytesMut::new());
+    overflow(result.map(|_| DataValue::Null), "sum(serialize)");
+
+    // The merge of the partial states, e.g. from two nodes.
+    let mut partials = vec![];
+    for _ in 0..2 {
+        let place = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(place);
+        func.accumulate(place, &[Series::new(vec![i64::MAX / 2 + 1])], 1)?;
+
+        let mut bytes = BytesMut::new();
+        func.serialize(place, &mut bytes)?;
+        partials.push(bytes);
+    }
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    func.deserialize(place, &mut partials[0].as_ref())?;
+    let rhs = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(rhs);
+    func.deserialize(rhs, &mut partials[1].as_ref())?;
+    func.merge(place, rhs)?;
+    overflow(func.merge_result(place), "sum(merge)");
+
+    // Int32 is summed as Int64.
+    let int32 = vec![DataField::new("a", DataType::Int32, false)];
+    let func = factory.get("sum", vec![], int32)?;
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![i32::MAX, i32::MAX, i32::MIN]);
+    func.accumulate(place, &[block], 3)?;
+    assert_eq!(
+        func.merge_result(place)?,
+        DataValue::Int64(Some(i32::MAX as i64 - 1))
+    );
+
+    let uint64 = vec![DataField::new("a", DataType::UInt64, false)];
+    let func = factory.get("sum", vec![], uint64)?;
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![u64::MAX, 1]);
+    func.accumulate(place, &[block], 2)?;
+    overflow(func.merge_result(place), "sum(uint64)");
+
+    Ok(())
+}
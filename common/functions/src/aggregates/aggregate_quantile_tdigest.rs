@@ -0,0 +1,387 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+use num::cast::AsPrimitive;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::with_match_primitive_type;
+
+/// The compression of the digest, it keeps at most about `compression` centroids.
+pub const TDIGEST_DEFAULT_COMPRESSION: f64 = 100.0;
+
+#[derive(Clone, Copy, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// A merging t-digest with the k1 scale function, see Dunning & Ertl,
+/// "Computing Extremely Accurate Quantiles Using t-Digests".
+///
+/// The values are buffered and merged into the sorted centroids in batches, so a block
+/// is inserted with one sort. The centroids near both ends of the distribution are kept
+/// small, which makes the tail quantiles (e.g. 0.99) much more accurate than the median.
+struct AggregateQuantileTDigestState {
+    compression: f64,
+    // Sorted by mean and compressed.
+    centroids: Vec<Centroid>,
+    unmerged: Vec<Centroid>,
+    min: f64,
+    max: f64,
+}
+
+impl AggregateQuantileTDigestState {
+    fn new(compression: f64) -> Self {
+        Self {
+            compression,
+            centroids: vec![],
+            unmerged: vec![],
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    fn add(&mut self, value: f64) {
+        self.add_values(std::iter::once(value))
+    }
+
+    /// Insert the values of a block, they are merged at most once.
+    fn add_values(&mut self, values: impl Iterator<Item = f64>) {
+        // NaN has no rank.
+        for value in values.filter(|value| !value.is_nan()) {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+            self.unmerged.push(Centroid {
+                mean: value,
+                weight: 1.0,
+            });
+        }
+        if self.unmerged.len() >= self.buffer_size() {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.unmerged.extend_from_slice(&other.centroids);
+        self.unmerged.extend_from_slice(&other.unmerged);
+        if self.unmerged.len() >= self.buffer_size() {
+            self.compress();
+        }
+    }
+
+    fn buffer_size(&self) -> usize {
+        (self.compression as usize).max(1) * 5
+    }
+
+    /// The largest quantile a centroid starting at `q` can reach: one unit in the k space.
+    fn quantile_limit(&self, q: f64) -> f64 {
+        let k = self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin() + 1.0;
+        match k >= self.compression / 4.0 {
+            true => 1.0,
+            false => ((k * 2.0 * PI / self.compression).sin() + 1.0) / 2.0,
+        }
+    }
+
+    fn compress(&mut self) {
+        if self.unmerged.is_empty() {
+            return;
+        }
+
+        let mut centroids = std::mem::take(&mut self.unmerged);
+        centroids.extend_from_slice(&self.centroids);
+        centroids.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(Ordering::Equal));
+
+        let total_weight = centroids.iter().map(|c| c.weight).sum::<f64>();
+        let mut merged = Vec::with_capacity(self.compression as usize);
+        let mut weight_so_far = 0.0;
+        let mut weight_limit = total_weight * self.quantile_limit(0.0);
+
+        let mut centroids = centroids.into_iter();
+        let mut current = centroids.next().unwrap();
+        for centroid in centroids {
+            if weight_so_far + current.weight + centroid.weight <= weight_limit {
+                current.weight += centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / current.weight;
+            } else {
+                weight_so_far += current.weight;
+                weight_limit = total_weight * self.quantile_limit(weight_so_far / total_weight);
+                merged.push(current);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Returns None if no value has been added.
+    fn quantile(&mut self, q: f64) -> Option<f64> {
+        self.compress();
+
+        let first = self.centroids.first()?;
+        let last = self.centroids.last()?;
+        let total_weight = self.centroids.iter().map(|c| c.weight).sum::<f64>();
+        let target = q * total_weight;
+
+        // The centroids at both ends are interpolated with the exact min and max.
+        if target <= first.weight / 2.0 {
+            return match first.weight > 1.0 {
+                true => Some(self.min + (first.mean - self.min) * target / (first.weight / 2.0)),
+                false => Some(first.mean),
+            };
+        }
+        if target >= total_weight - last.weight / 2.0 {
+            let distance = total_weight - target;
+            return match last.weight > 1.0 {
+                true => Some(self.max - (self.max - last.mean) * distance / (last.weight / 2.0)),
+                false => Some(last.mean),
+            };
+        }
+
+        // The weight of a centroid is spread evenly around its mean.
+        let mut cumulative = first.weight / 2.0;
+        for pair in self.centroids.windows(2) {
+            let step = (pair[0].weight + pair[1].weight) / 2.0;
+            if target <= cumulative + step {
+                let ratio = (target - cumulative) / step;
+                return Some(pair[0].mean + (pair[1].mean - pair[0].mean) * ratio);
+            }
+            cumulative += step;
+        }
+        Some(last.mean)
+    }
+
+    fn serialize(&mut self, writer: &mut BytesMut) -> Result<()> {
+        self.compress();
+
+        self.compression.serialize_to_buf(writer)?;
+        self.min.serialize_to_buf(writer)?;
+        self.max.serialize_to_buf(writer)?;
+        writer.write_uvarint(self.centroids.len() as u64)?;
+        for centroid in self.centroids.iter() {
+            centroid.mean.serialize_to_buf(writer)?;
+            centroid.weight.serialize_to_buf(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        self.compression = f64::deserialize(reader)?;
+        self.min = f64::deserialize(reader)?;
+        self.max = f64::deserialize(reader)?;
+        let size: u64 = reader.read_uvarint()?;
+
+        self.unmerged.clear();
+        self.centroids = Vec::with_capacity(size as usize);
+        for _i in 0..size {
+            let mean = f64::deserialize(reader)?;
+            let weight = f64::deserialize(reader)?;
+            self.centroids.push(Centroid { mean, weight });
+        }
+        Ok(())
+    }
+}
+
+/// Approximate quantiles of a numeric column: `quantile_tdigest(0.5, 0.99)(col)`.
+///
+/// With a single level (0.5 if no level is given) the result is a Float64, otherwise a list
+/// of Float64 in the order of the levels. NULLs are skipped, and the result is NULL if there
+/// is no value.
+#[derive(Clone)]
+pub struct AggregateQuantileTDigestFunction<T> {
+    display_name: String,
+    _arguments: Vec<DataField>,
+    levels: Vec<f64>,
+    compression: f64,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateFunction for AggregateQuantileTDigestFunction<T>
+where T: DFPrimitiveType + AsPrimitive<f64>
+{
+    fn name(&self) -> &str {
+        "AggregateQuantileTDigestFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        match self.levels.len() {
+            1 => Ok(DataType::Float64),
+            _ => Ok(DataType::List(Box::new(DataField::new(
+                "item",
+                DataType::Float64,
+                true,
+            )))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateQuantileTDigestState::new(self.compression));
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateQuantileTDigestState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        let array: &DFPrimitiveArray<T> = arrays[0].static_cast();
+
+        if array.null_count() == 0 {
+            state.add_values(array.into_no_null_iter().map(|value| value.as_()));
+        } else {
+            state.add_values(array.iter().flatten().map(|value| value.as_()));
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let array: &DFPrimitiveArray<T> = arrays[0].static_cast();
+        array.iter().zip(places.iter()).for_each(|(value, place)| {
+            if let Some(value) = value {
+                let place = place.next(offset);
+                let state = place.get::<AggregateQuantileTDigestState>();
+                state.add(value.as_());
+            }
+        });
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        let rhs = rhs.get::<AggregateQuantileTDigestState>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateQuantileTDigestState>();
+        if self.levels.len() == 1 {
+            return Ok(DataValue::Float64(state.quantile(self.levels[0])));
+        }
+
+        let values = self
+            .levels
+            .iter()
+            .map(|level| state.quantile(*level).map(|v| DataValue::Float64(Some(v))))
+            .collect::<Option<Vec<_>>>();
+        Ok(DataValue::List(values, DataType::Float64))
+    }
+}
+
+impl<T> fmt::Display for AggregateQuantileTDigestFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl<T> AggregateQuantileTDigestFunction<T>
+where T: DFPrimitiveType + AsPrimitive<f64>
+{
+    pub fn try_create(
+        display_name: &str,
+        levels: Vec<f64>,
+        compression: f64,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            _arguments: arguments,
+            levels,
+            compression,
+            t: PhantomData,
+        }))
+    }
+}
+
+pub fn try_create_aggregate_quantile_tdigest_function(
+    display_name: &str,
+    params: Vec<DataValue>,
+    arguments: Vec<DataField>,
+) -> Result<Arc<dyn AggregateFunction>> {
+    assert_unary_arguments(display_name, arguments.len())?;
+
+    let mut levels = Vec::with_capacity(params.len());
+    for param in params.iter() {
+        let level = param.as_f64()?;
+        if !(0.0..=1.0).contains(&level) {
+            return Err(ErrorCode::BadArguments(format!(
+                "The quantile level of {} must be in [0, 1], but got {}",
+                display_name, level
+            )));
+        }
+        levels.push(level);
+    }
+    if levels.is_empty() {
+        levels.push(0.5);
+    }
+
+    let data_type = arguments[0].data_type();
+    with_match_primitive_type!(data_type, |$T| {
+        AggregateQuantileTDigestFunction::<$T>::try_create(
+            display_name,
+            levels,
+            TDIGEST_DEFAULT_COMPRESSION,
+            arguments,
+        )
+    },
+
+    {
+        Err(ErrorCode::BadDataValueType(format!(
+            "AggregateQuantileTDigestFunction does not support type '{:?}'",
+            data_type
+        )))
+    })
+}
+
+pub fn aggregate_quantile_tdigest_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantile_tdigest_function))
+}
@@ -0,0 +1,229 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bumpalo::Bump;
+use bytes::BytesMut;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+const LEVELS: [f64; 7] = [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999];
+
+struct Lcg(u64);
+
+impl Lcg {
+    // Uniform in [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn uniform(rows: usize) -> Vec<f64> {
+    let mut rng = Lcg(7);
+    (0..rows).map(|_| rng.next() * 1_000_000.0).collect()
+}
+
+// Zipf like: a discrete power law with a long tail and many duplicates.
+fn zipf(rows: usize) -> Vec<f64> {
+    let mut rng = Lcg(11);
+    (0..rows)
+        .map(|_| (1.0 / (1.0 - rng.next())).powf(1.0 / 1.1).floor())
+        .collect()
+}
+
+fn quantile_func(levels: &[f64]) -> Result<AggregateFunctionRef> {
+    let params = levels
+        .iter()
+        .map(|l| DataValue::Float64(Some(*l)))
+        .collect();
+    let args = vec![DataField::new("a", DataType::Float64, true)];
+    AggregateFunctionFactory::instance().get("quantile_tdigest", params, args)
+}
+
+fn result_values(value: DataValue) -> Vec<f64> {
+    match value {
+        DataValue::List(Some(values), DataType::Float64) => values
+            .iter()
+            .map(|v| match v {
+                DataValue::Float64(Some(v)) => *v,
+                other => panic!("Unexpected quantile: {:?}", other),
+            })
+            .collect(),
+        other => panic!("Unexpected quantiles: {:?}", other),
+    }
+}
+
+/// The estimate must be a value whose rank range in the sorted data covers `level`, up to `error`.
+fn assert_rank(sorted: &[f64], level: f64, estimate: f64, error: f64, name: &str) {
+    let rows = sorted.len() as f64;
+    let lower = sorted.partition_point(|v| *v < estimate) as f64 / rows;
+    let upper = sorted.partition_point(|v| *v <= estimate) as f64 / rows;
+    assert!(
+        lower <= level + error && upper >= level - error,
+        "{}: level {} estimate {} has rank [{}, {}]",
+        name,
+        level,
+        estimate,
+        lower,
+        upper
+    );
+}
+
+#[test]
+fn test_quantile_tdigest_accuracy() -> Result<()> {
+    let arena = Bump::new();
+    let func = quantile_func(&LEVELS)?;
+    assert_eq!(
+        func.return_type()?,
+        DataType::List(Box::new(DataField::new("item", DataType::Float64, true)))
+    );
+
+    for (name, data) in [
+        ("uniform", uniform(200_000)),
+        ("zipf", zipf(200_000)),
+        ("constant", vec![42.0; 100_000]),
+    ] {
+        let place = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(place);
+        for block in data.chunks(8192) {
+            func.accumulate(place, &[Series::new(block.to_vec())], block.len())?;
+        }
+        let estimates = result_values(func.merge_result(place)?);
+
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (level, estimate) in LEVELS.iter().zip(estimates.iter()) {
+            // The digest is more accurate at the tails.
+            let error = (0.01 * level * (1.0 - level) * 4.0).max(0.002);
+            assert_rank(&sorted, *level, *estimate, error, name);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_quantile_tdigest_min_max() -> Result<()> {
+    let arena = Bump::new();
+    let func = quantile_func(&[0.0, 1.0])?;
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let data = uniform(50_000);
+    func.accumulate(place, &[Series::new(data.clone())], data.len())?;
+
+    let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    assert_eq!(result_values(func.merge_result(place)?), vec![min, max]);
+    Ok(())
+}
+
+#[test]
+fn test_quantile_tdigest_distributed() -> Result<()> {
+    let arena = Bump::new();
+    let func = quantile_func(&LEVELS)?;
+    let data = zipf(100_000);
+
+    let single = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(single);
+    func.accumulate(single, &[Series::new(data.clone())], data.len())?;
+    let expect = result_values(func.merge_result(single)?);
+
+    // Four partial states, each fed with the group by path, sent through the wire and merged.
+    let partials = (0..4)
+        .map(|_| {
+            let place = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(place);
+            place
+        })
+        .collect::<Vec<StateAddr>>();
+    for block in data.chunks(4096) {
+        let places = (0..block.len())
+            .map(|i| partials[i % 4])
+            .collect::<Vec<_>>();
+        func.accumulate_keys(&places, 0, &[Series::new(block.to_vec())], block.len())?;
+    }
+
+    let merged = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(merged);
+    for partial in partials {
+        let mut writer = BytesMut::new();
+        func.serialize(partial, &mut writer)?;
+
+        let received = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(received);
+        func.deserialize(received, &mut writer.as_ref())?;
+        func.merge(merged, received)?;
+    }
+    let actual = result_values(func.merge_result(merged)?);
+
+    let mut sorted = data.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for ((level, expect), actual) in LEVELS.iter().zip(expect.iter()).zip(actual.iter()) {
+        assert_rank(&sorted, *level, *expect, 0.01, "single");
+        assert_rank(&sorted, *level, *actual, 0.01, "merged");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_quantile_tdigest_nulls() -> Result<()> {
+    let arena = Bump::new();
+    let factory = AggregateFunctionFactory::instance();
+    let args = vec![DataField::new("a", DataType::Int64, true)];
+
+    // median is the 0.5 quantile.
+    let func = factory.get("median", vec![], args.clone())?;
+    assert_eq!(func.return_type()?, DataType::Float64);
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![Some(1i64), None, Some(3i64), None]);
+    func.accumulate(place, &[block], 4)?;
+    assert_eq!(func.merge_result(place)?, DataValue::Float64(Some(2.0)));
+
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let block = Series::new(vec![None::<i64>, None]);
+    func.accumulate(place, &[block], 2)?;
+    assert_eq!(func.merge_result(place)?, DataValue::Float64(None));
+
+    let func = factory.get(
+        "quantile_tdigest",
+        vec![DataValue::Float64(Some(0.5)), DataValue::Float64(Some(0.9))],
+        args.clone(),
+    )?;
+    let place = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    assert_eq!(
+        func.merge_result(place)?,
+        DataValue::List(None, DataType::Float64)
+    );
+
+    match factory.get(
+        "quantile_tdigest",
+        vec![DataValue::Float64(Some(1.5))],
+        args,
+    ) {
+        Ok(_) => panic!("quantile level 1.5 must be rejected"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::BadArguments("").code()),
+    }
+    Ok(())
+}
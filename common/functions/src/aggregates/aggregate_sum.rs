@@ -15,11 +15,11 @@
 use std::alloc::Layout;
 use std::fmt;
 use std::marker::PhantomData;
+use std::ops::AddAssign;
 use std::sync::Arc;
 
 use bytes::BytesMut;
 use common_datavalues::prelude::*;
-use common_datavalues::DFTryFrom;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::*;
@@ -32,29 +32,88 @@ use crate::aggregates::aggregator_common::assert_unary_arguments;
 use crate::aggregates::AggregateFunction;
 use crate::with_match_primitive_type;
 
-struct AggregateSumState<T> {
-    pub value: Option<T>,
+/// The result type of sum, whose addition is checked for overflow.
+pub trait SumPrimitiveType: DFPrimitiveType {
+    /// The sum is kept in the wide type and narrowed only when it is taken out of the state,
+    /// so it only has to fit in the result type at the end.
+    type WideType: Copy + Default + AddAssign;
+
+    fn widen(self) -> Self::WideType;
+
+    fn try_narrow(wide: Self::WideType) -> Option<Self>;
+}
+
+macro_rules! impl_sum_integer {
+    ($t: ident, $wide: ident) => {
+        impl SumPrimitiveType for $t {
+            type WideType = $wide;
+
+            #[inline(always)]
+            fn widen(self) -> $wide {
+                self as $wide
+            }
+
+            #[inline(always)]
+            fn try_narrow(wide: $wide) -> Option<$t> {
+                $t::try_from(wide).ok()
+            }
+        }
+    };
+}
+
+impl_sum_integer!(i64, i128);
+impl_sum_integer!(u64, u128);
+
+impl SumPrimitiveType for f64 {
+    type WideType = f64;
+
+    #[inline(always)]
+    fn widen(self) -> f64 {
+        self
+    }
+
+    #[inline(always)]
+    fn try_narrow(wide: f64) -> Option<f64> {
+        Some(wide)
+    }
+}
+
+struct AggregateSumState<T: SumPrimitiveType> {
+    pub value: Option<T::WideType>,
 }
 
 impl<T> AggregateSumState<T>
 where
-    T: std::ops::Add<Output = T> + Copy + Clone,
+    T: SumPrimitiveType,
     Option<T>: BinarySer + BinaryDe,
 {
     #[inline(always)]
-    fn add(&mut self, other: T) {
-        match &self.value {
-            Some(a) => self.value = Some(a.add(other)),
-            None => self.value = Some(other),
+    fn add(&mut self, other: T::WideType) {
+        let value = self.value.get_or_insert_with(T::WideType::default);
+        *value += other;
+    }
+
+    fn narrow(&self) -> Result<Option<T>> {
+        match self.value {
+            Some(value) => T::try_narrow(value).map(Some).ok_or_else(Self::overflow),
+            None => Ok(None),
         }
     }
 
+    fn overflow() -> ErrorCode {
+        let data_type: DataValue = Some(T::default()).into();
+        ErrorCode::Overflow(format!(
+            "Sum overflows the range of {:?}",
+            data_type.data_type()
+        ))
+    }
+
     fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
-        self.value.serialize_to_buf(writer)
+        self.narrow()?.serialize_to_buf(writer)
     }
 
     fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
-        self.value = Option::<T>::deserialize(reader)?;
+        self.value = Option::<T>::deserialize(reader)?.map(T::widen);
         Ok(())
     }
 }
@@ -70,7 +129,7 @@ pub struct AggregateSumFunction<T, SumT> {
 impl<T, SumT> AggregateFunction for AggregateSumFunction<T, SumT>
 where
     T: DFPrimitiveType + AsPrimitive<SumT>,
-    SumT: DFPrimitiveType + std::ops::Add<Output = SumT>,
+    SumT: SumPrimitiveType,
     Option<SumT>: Into<DataValue>,
 {
     fn name(&self) -> &str {
@@ -96,14 +155,24 @@ where
     }
 
     fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
-        let value = arrays[0].sum()?;
-        let opt_sum: Result<SumT> = DFTryFrom::try_from(value);
+        let darray: &DFPrimitiveArray<T> = arrays[0].static_cast();
+        if darray.len() == darray.null_count() {
+            return Ok(());
+        }
 
-        if let Ok(s) = opt_sum {
-            let state = place.get::<AggregateSumState<SumT>>();
-            state.add(s);
+        let mut sum = SumT::WideType::default();
+        if darray.null_count() == 0 {
+            darray.inner().values().as_slice().iter().for_each(|v| {
+                sum += v.as_().widen();
+            });
+        } else {
+            darray.into_iter().flatten().for_each(|v| {
+                sum += v.as_().widen();
+            });
         }
 
+        let state = place.get::<AggregateSumState<SumT>>();
+        state.add(sum);
         Ok(())
     }
 
@@ -125,8 +194,8 @@ where
                 .for_each(|(v, place)| {
                     let place = place.next(offset);
                     let state = place.get::<AggregateSumState<SumT>>();
-                    state.add(v.as_());
-                });
+                    state.add(v.as_().widen())
+                })
         } else {
             darray
                 .into_iter()
@@ -135,11 +204,10 @@ where
                     if let Some(v) = c {
                         let place = place.next(offset);
                         let state = place.get::<AggregateSumState<SumT>>();
-                        state.add(v.as_());
+                        state.add(v.as_().widen())
                     }
-                });
+                })
         }
-
         Ok(())
     }
 
@@ -155,16 +223,16 @@ where
 
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
         let rhs = rhs.get::<AggregateSumState<SumT>>();
-        if let Some(s) = &rhs.value {
+        if let Some(s) = rhs.value {
             let state = place.get::<AggregateSumState<SumT>>();
-            state.add(*s);
+            state.add(s);
         }
         Ok(())
     }
 
     fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
         let state = place.get::<AggregateSumState<SumT>>();
-        Ok(state.value.into())
+        Ok(state.narrow()?.into())
     }
 }
 
@@ -177,7 +245,7 @@ impl<T, SumT> fmt::Display for AggregateSumFunction<T, SumT> {
 impl<T, SumT> AggregateSumFunction<T, SumT>
 where
     T: DFPrimitiveType + AsPrimitive<SumT>,
-    SumT: DFPrimitiveType + std::ops::Add<Output = SumT>,
+    SumT: SumPrimitiveType,
     Option<SumT>: Into<DataValue>,
 {
     pub fn try_create(
@@ -18,6 +18,7 @@ use crate::aggregates::aggregate_avg::aggregate_avg_function_desc;
 use crate::aggregates::aggregate_function_factory::AggregateFunctionFactory;
 use crate::aggregates::aggregate_min_max::aggregate_max_function_desc;
 use crate::aggregates::aggregate_min_max::aggregate_min_function_desc;
+use crate::aggregates::aggregate_quantile_tdigest::aggregate_quantile_tdigest_function_desc;
 use crate::aggregates::aggregate_stddev_pop::aggregate_stddev_pop_function_desc;
 use crate::aggregates::aggregate_sum::aggregate_sum_function_desc;
 use crate::aggregates::aggregate_window_funnel::aggregate_window_funnel_function_desc;
@@ -41,6 +42,11 @@ impl Aggregators {
         factory.register("stddev", aggregate_stddev_pop_function_desc());
         factory.register("stddev_pop", aggregate_stddev_pop_function_desc());
         factory.register("windowFunnel", aggregate_window_funnel_function_desc());
+        factory.register(
+            "quantile_tdigest",
+            aggregate_quantile_tdigest_function_desc(),
+        );
+        factory.register("median", aggregate_quantile_tdigest_function_desc());
         factory.register("uniq", AggregateDistinctCombinator::uniq_desc());
     }
 
@@ -16,6 +16,8 @@
 mod aggregate_combinator_test;
 #[cfg(test)]
 mod aggregate_function_test;
+#[cfg(test)]
+mod aggregate_quantile_tdigest_test;
 
 mod aggregate_arg_min_max;
 mod aggregate_avg;
@@ -26,6 +28,7 @@ mod aggregate_function;
 mod aggregate_function_factory;
 mod aggregate_function_state;
 mod aggregate_min_max;
+mod aggregate_quantile_tdigest;
 mod aggregate_window_funnel;
 
 // mod aggregate_min_max;
@@ -49,6 +52,7 @@ pub use aggregate_function_state::get_layout_offsets;
 pub use aggregate_function_state::StateAddr;
 pub use aggregate_function_state::StateAddrs;
 pub use aggregate_min_max::AggregateMinMaxFunction;
+pub use aggregate_quantile_tdigest::AggregateQuantileTDigestFunction;
 pub use aggregate_stddev_pop::AggregateStddevPopFunction;
 pub use aggregate_sum::AggregateSumFunction;
 pub use aggregator::Aggregators;
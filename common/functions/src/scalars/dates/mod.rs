@@ -20,6 +20,8 @@ mod date_test;
 
 #[cfg(test)]
 mod interval_function_test;
+#[cfg(test)]
+mod number_function_test;
 
 mod interval_function;
 mod now;
@@ -41,6 +41,12 @@ pub trait NumberResultFunction<R> {
     fn return_type() -> Result<DataType>;
     fn to_number(_value: DateTime<Utc>) -> R;
     fn to_constant_value(_value: DateTime<Utc>) -> DataValue;
+
+    /// Same as `to_number` on the seconds since the epoch, computed with integer arithmetic.
+    /// Returns None if the function needs the chrono calendar, `to_number` is used instead.
+    fn seconds_to_number(_seconds: i64) -> Option<R> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -59,6 +65,11 @@ impl NumberResultFunction<u32> for ToYYYYMM {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt32(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u32> {
+        let (year, month, _) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(year as u32 * 100 + month)
+    }
 }
 
 #[derive(Clone)]
@@ -77,6 +88,11 @@ impl NumberResultFunction<u32> for ToYYYYMMDD {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt32(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u32> {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(year as u32 * 10000 + month * 100 + day)
+    }
 }
 
 #[derive(Clone)]
@@ -101,6 +117,19 @@ impl NumberResultFunction<u64> for ToYYYYMMDDhhmmss {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt64(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u64> {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY) as u64;
+        Some(
+            year as u64 * 10000000000
+                + month as u64 * 100000000
+                + day as u64 * 1000000
+                + seconds_of_day / 3600 * 10000
+                + seconds_of_day % 3600 / 60 * 100
+                + seconds_of_day % 60,
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -120,6 +149,11 @@ impl NumberResultFunction<u16> for ToStartOfYear {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt16(Some(Self::to_number(value) as u16))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u16> {
+        let (year, _, _) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(days_from_civil(year, 1, 1) as u16)
+    }
 }
 
 #[derive(Clone)]
@@ -164,6 +198,11 @@ impl NumberResultFunction<u16> for ToStartOfQuarter {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt16(Some(Self::to_number(value) as u16))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u16> {
+        let (year, month, _) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(days_from_civil(year, (month - 1) / 3 * 3 + 1, 1) as u16)
+    }
 }
 
 #[derive(Clone)]
@@ -183,6 +222,11 @@ impl NumberResultFunction<u16> for ToStartOfMonth {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt16(Some(Self::to_number(value) as u16))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u16> {
+        let (year, month, _) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(days_from_civil(year, month, 1) as u16)
+    }
 }
 
 #[derive(Clone)]
@@ -201,6 +245,11 @@ impl NumberResultFunction<u8> for ToMonth {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt8(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u8> {
+        let (_, month, _) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(month as u8)
+    }
 }
 
 #[derive(Clone)]
@@ -219,6 +268,12 @@ impl NumberResultFunction<u16> for ToDayOfYear {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt16(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u16> {
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        let (year, _, _) = civil_from_days(days);
+        Some((days - days_from_civil(year, 1, 1) + 1) as u16)
+    }
 }
 
 #[derive(Clone)]
@@ -237,6 +292,11 @@ impl NumberResultFunction<u8> for ToDayOfMonth {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt8(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u8> {
+        let (_, _, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        Some(day as u8)
+    }
 }
 
 #[derive(Clone)]
@@ -255,6 +315,12 @@ impl NumberResultFunction<u8> for ToDayOfWeek {
     fn to_constant_value(value: DateTime<Utc>) -> DataValue {
         DataValue::UInt8(Some(Self::to_number(value)))
     }
+
+    fn seconds_to_number(seconds: i64) -> Option<u8> {
+        // 1970-01-01 is a Thursday.
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        Some((days + 3).rem_euclid(7) as u8 + 1)
+    }
 }
 
 impl<T, R> NumberFunction<T, R>
@@ -271,6 +337,14 @@ where
         }))
     }
 
+    #[inline]
+    fn number_of_seconds(seconds: i64) -> R {
+        match T::seconds_to_number(seconds) {
+            Some(number) => number,
+            None => T::to_number(Utc.timestamp(seconds, 0_u32)),
+        }
+    }
+
     pub fn desc() -> FunctionDescription {
         let mut features = FunctionFeatures::default();
 
@@ -316,11 +390,7 @@ where
                     let result: DFPrimitiveArray<R> = columns[0].column()
                         .to_array()?
                         .u16()?
-                        .apply_cast_numeric(|v| {
-                            let date_time = Utc.timestamp(v as i64 * 24 * 3600, 0_u32);
-                            T::to_number(date_time)
-                        }
-                        );
+                        .apply_cast_numeric(|v| Self::number_of_seconds(v as i64 * SECONDS_PER_DAY));
                     Ok(result.into())
                 }
             }
@@ -333,11 +403,7 @@ where
                     let result = columns[0].column()
                         .to_array()?
                         .i32()?
-                        .apply_cast_numeric(|v| {
-                            let date_time = Utc.timestamp(v as i64 * 24 * 3600, 0_u32);
-                            T::to_number(date_time)
-                        }
-                        );
+                        .apply_cast_numeric(|v| Self::number_of_seconds(v as i64 * SECONDS_PER_DAY));
                     Ok(result.into())
                 }
             }
@@ -350,11 +416,7 @@ where
                     let result = columns[0].column()
                         .to_array()?
                         .u32()?
-                        .apply_cast_numeric(|v| {
-                            let date_time = Utc.timestamp(v as i64, 0_u32);
-                            T::to_number(date_time)
-                        }
-                        );
+                        .apply_cast_numeric(|v| Self::number_of_seconds(v as i64));
                     Ok(result.into())
                 }
             }
@@ -373,6 +435,43 @@ impl<T, R> fmt::Display for NumberFunction<T, R> {
     }
 }
 
+const SECONDS_PER_DAY: i64 = 24 * 3600;
+
+/// Returns the (year, month, day) of the days since 1970-01-01 in the proleptic Gregorian
+/// calendar, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[inline]
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Shift the epoch to 0000-03-01, so that the leap day is the last day of a year.
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Returns the days since 1970-01-01 of a date in the proleptic Gregorian calendar,
+/// the inverse of `civil_from_days`.
+#[inline]
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = ((month + 9) % 12) as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
 fn get_day(date: DateTime<Utc>) -> u32 {
     let start: DateTime<Utc> = Utc.ymd(1970, 1, 1).and_hms(0, 0, 0);
     let duration = date.signed_duration_since(start);
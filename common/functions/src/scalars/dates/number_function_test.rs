@@ -0,0 +1,109 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+
+use common_datavalues::chrono::TimeZone;
+use common_datavalues::chrono::Utc;
+use common_exception::Result;
+
+use crate::scalars::dates::number_function::*;
+
+const SECONDS_PER_DAY: i64 = 24 * 3600;
+
+// 0001-01-01 and 9999-12-31.
+const MIN_DAYS: i64 = -719162;
+const MAX_DAYS: i64 = 2932896;
+
+/// Sampled days: every day around the epoch, leap days and year boundaries,
+/// and a stride over the whole supported range.
+fn sampled_days() -> Vec<i64> {
+    let mut days = (-30000..30000).collect::<Vec<_>>();
+    days.extend((MIN_DAYS..=MAX_DAYS).step_by(97));
+    for year in [
+        1, 4, 100, 400, 1600, 1900, 1969, 1970, 2000, 2020, 2100, 9999,
+    ] {
+        let start = days_from_civil(year, 1, 1);
+        days.extend(start - 3..start + 3);
+        let march = days_from_civil(year, 3, 1);
+        days.extend(march - 3..march + 3);
+    }
+    days.retain(|day| (MIN_DAYS..=MAX_DAYS).contains(day));
+    days
+}
+
+fn assert_same_as_chrono<T, R>(seconds: &[i64])
+where
+    T: NumberResultFunction<R>,
+    R: PartialEq + Debug,
+{
+    for seconds in seconds {
+        let expect = T::to_number(Utc.timestamp(*seconds, 0));
+        assert_eq!(
+            T::seconds_to_number(*seconds),
+            Some(expect),
+            "seconds: {}",
+            seconds
+        );
+    }
+}
+
+#[test]
+fn test_civil_from_days() -> Result<()> {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    assert_eq!(civil_from_days(11016), (2000, 2, 29));
+    assert_eq!(civil_from_days(MIN_DAYS), (1, 1, 1));
+    assert_eq!(civil_from_days(MAX_DAYS), (9999, 12, 31));
+
+    for days in sampled_days() {
+        let (year, month, day) = civil_from_days(days);
+        assert_eq!(days_from_civil(year, month, day), days);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_date_kernels_same_as_chrono() -> Result<()> {
+    let days = sampled_days()
+        .iter()
+        .map(|days| days * SECONDS_PER_DAY)
+        .collect::<Vec<_>>();
+
+    assert_same_as_chrono::<ToYYYYMM, u32>(&days);
+    assert_same_as_chrono::<ToYYYYMMDD, u32>(&days);
+    assert_same_as_chrono::<ToStartOfYear, u16>(&days);
+    assert_same_as_chrono::<ToStartOfQuarter, u16>(&days);
+    assert_same_as_chrono::<ToStartOfMonth, u16>(&days);
+    assert_same_as_chrono::<ToMonth, u8>(&days);
+    assert_same_as_chrono::<ToDayOfYear, u16>(&days);
+    assert_same_as_chrono::<ToDayOfMonth, u8>(&days);
+    assert_same_as_chrono::<ToDayOfWeek, u8>(&days);
+
+    // Seconds within the day, including the negative ones.
+    let seconds = (-(SECONDS_PER_DAY * 400)..SECONDS_PER_DAY * 400)
+        .step_by(617)
+        .chain((0..u32::MAX as i64).step_by(104729))
+        .collect::<Vec<_>>();
+    assert_same_as_chrono::<ToYYYYMMDDhhmmss, u64>(&seconds);
+    assert_same_as_chrono::<ToYYYYMMDD, u32>(&seconds);
+    assert_same_as_chrono::<ToDayOfWeek, u8>(&seconds);
+    Ok(())
+}
+
+#[test]
+fn test_date_kernels_fallback() -> Result<()> {
+    assert_eq!(ToStartOfISOYear::seconds_to_number(0), None);
+    Ok(())
+}
@@ -21,6 +21,7 @@ mod udf_example_test;
 #[cfg(test)]
 mod version_test;
 
+mod count_me;
 mod crash_me;
 mod database;
 mod exists;
@@ -30,6 +31,7 @@ mod udf;
 mod udf_example;
 mod version;
 
+pub use count_me::CountMeFunction;
 pub use crash_me::CrashMeFunction;
 pub use database::DatabaseFunction;
 pub use sleep::SleepFunction;
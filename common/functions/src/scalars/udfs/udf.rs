@@ -14,6 +14,7 @@
 
 use crate::scalars::function_factory::FunctionFactory;
 use crate::scalars::udfs::exists::ExistsFunction;
+use crate::scalars::CountMeFunction;
 use crate::scalars::CrashMeFunction;
 use crate::scalars::DatabaseFunction;
 use crate::scalars::SleepFunction;
@@ -32,6 +33,7 @@ impl UdfFunction {
         factory.register("version", VersionFunction::desc());
         factory.register("sleep", SleepFunction::desc());
         factory.register("crashme", CrashMeFunction::desc());
+        factory.register("countme", CountMeFunction::desc());
         factory.register("exists", ExistsFunction::desc());
     }
 }
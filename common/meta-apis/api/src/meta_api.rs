@@ -19,6 +19,7 @@ use common_meta_api_vo::CreateTableReply;
 use common_meta_api_vo::DatabaseInfo;
 use common_meta_api_vo::GetDatabasesReply;
 use common_meta_api_vo::GetTablesReply;
+use common_meta_api_vo::MGetTablesReply;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
@@ -49,6 +50,9 @@ pub trait MetaApi: Send + Sync {
 
     async fn get_tables(&self, db: &str) -> Result<GetTablesReply>;
 
+    /// Get the tables of `db` by names, `None` for the table not found.
+    async fn mget_tables(&self, db: &str, tables: &[String]) -> Result<MGetTablesReply>;
+
     async fn get_table_by_id(
         &self,
         table_id: MetaId,
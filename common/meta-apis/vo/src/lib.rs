@@ -46,3 +46,4 @@ pub struct TableInfo {
 
 pub type GetDatabasesReply = Vec<DatabaseInfo>;
 pub type GetTablesReply = Vec<TableInfo>;
+pub type MGetTablesReply = Vec<Option<TableInfo>>;
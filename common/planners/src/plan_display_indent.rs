@@ -28,6 +28,7 @@ use crate::LimitPlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
+use crate::RemotePlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::SubQueriesSetPlan;
@@ -67,6 +68,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
             PlanNode::ReadSource(plan) => Self::format_read_source(f, plan),
+            PlanNode::Remote(plan) => Self::format_remote(f, plan),
             PlanNode::CreateDatabase(plan) => Self::format_create_database(f, plan),
             PlanNode::DropDatabase(plan) => Self::format_drop_database(f, plan),
             PlanNode::CreateTable(plan) => Self::format_create_table(f, plan),
@@ -214,6 +216,15 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         )
     }
 
+    fn format_remote(f: &mut Formatter, plan: &RemotePlan) -> fmt::Result {
+        write!(
+            f,
+            "Remote: stream: {}, fetch nodes: [{}]",
+            plan.stream_id,
+            plan.fetch_nodes.join(", ")
+        )
+    }
+
     fn format_create_database(f: &mut Formatter, plan: &CreateDatabasePlan) -> fmt::Result {
         write!(f, "Create database {:},", plan.db)?;
         write!(f, " engine: {},", plan.engine.to_string())?;
@@ -33,6 +33,7 @@ fn test_plan_display_indent() -> Result<()> {
 
     let plan_create = PlanNode::CreateTable(CreateTablePlan {
         if_not_exists: true,
+        temporary: false,
         db: "foo".into(),
         table: "bar".into(),
         schema,
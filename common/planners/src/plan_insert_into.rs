@@ -16,11 +16,14 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::Result;
 use common_infallible::Mutex;
 use common_metatypes::MetaId;
 
-type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+/// The blocks to insert, the stream may parse or receive them lazily, so an item may fail.
+type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct InsertIntoPlan {
@@ -21,6 +21,9 @@ pub type TableOptions = HashMap<String, String>;
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct CreateTablePlan {
     pub if_not_exists: bool,
+    /// Create a session temporary table, which is never sent to the meta service
+    #[serde(default)]
+    pub temporary: bool,
     pub db: String,
     /// The table name
     pub table: String,
@@ -27,6 +27,10 @@ use crate::store_do_action::StoreDoAction;
 use crate::RequestFor;
 use crate::StoreClient;
 
+/// Max number of tables fetched by one `MGetTablesAction`, to keep the reply
+/// under the flight message size limit.
+pub const MGET_TABLES_BATCH_SIZE: usize = 256;
+
 #[async_trait::async_trait]
 impl MetaApi for StoreClient {
     /// Create database call.
@@ -85,6 +89,25 @@ impl MetaApi for StoreClient {
     async fn get_tables(&self, db: &str) -> common_exception::Result<GetTablesReply> {
         self.do_action(GetTablesAction { db: db.to_string() }).await
     }
+
+    /// Get tables by names, in batches of `MGET_TABLES_BATCH_SIZE`.
+    async fn mget_tables(
+        &self,
+        db: &str,
+        tables: &[String],
+    ) -> common_exception::Result<MGetTablesReply> {
+        let mut res = Vec::with_capacity(tables.len());
+        for chunk in tables.chunks(MGET_TABLES_BATCH_SIZE) {
+            let reply = self
+                .do_action(MGetTablesAction {
+                    db: db.to_string(),
+                    tables: chunk.to_vec(),
+                })
+                .await?;
+            res.extend(reply);
+        }
+        Ok(res)
+    }
 }
 
 // == database actions ==
@@ -155,6 +178,15 @@ pub struct GetTablesAction {
 
 action_declare!(GetTablesAction, GetTablesReply, StoreDoAction::GetTables);
 
+// - mget tables
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct MGetTablesAction {
+    pub db: String,
+    pub tables: Vec<String>,
+}
+
+action_declare!(MGetTablesAction, MGetTablesReply, StoreDoAction::MGetTables);
+
 // -get databases
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
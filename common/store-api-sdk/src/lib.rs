@@ -43,3 +43,5 @@ pub mod protobuf {
 
 #[cfg(test)]
 mod dns_resolver_test;
+#[cfg(test)]
+mod store_client_test;
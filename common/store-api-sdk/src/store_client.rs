@@ -156,8 +156,14 @@ impl StoreClient {
 
         req.set_timeout(self.timeout);
 
-        let mut stream = self.client.clone().do_action(req).await?.into_inner();
-        match stream.message().await? {
+        let mut stream = self
+            .client
+            .clone()
+            .do_action(req)
+            .await
+            .map_err(status_to_error)?
+            .into_inner();
+        match stream.message().await.map_err(status_to_error)? {
             None => Err(ErrorCode::EmptyData(format!(
                 "Can not receive data from dfs flight server, action: {:?}",
                 act
@@ -171,6 +177,14 @@ impl StoreClient {
     }
 }
 
+/// The meta service is unreachable or has no leader for now, the request may succeed on retry.
+pub(crate) fn status_to_error(status: tonic::Status) -> ErrorCode {
+    match status.code() {
+        tonic::Code::Unavailable => ErrorCode::MetaServiceUnavailable(status.message()),
+        _ => ErrorCode::from(status),
+    }
+}
+
 #[derive(Clone)]
 pub struct AuthInterceptor {
     pub token: Vec<u8>,
@@ -0,0 +1,30 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_exception::ErrorCode;
+use tonic::Status;
+
+use crate::store_client::status_to_error;
+
+#[test]
+fn test_status_to_error() {
+    let error = status_to_error(Status::unavailable("no leader"));
+    assert_eq!(error.code(), ErrorCode::MetaServiceUnavailable("").code());
+    assert_eq!(error.message(), "no leader");
+
+    let error = status_to_error(ErrorCode::UnknownTable("t1").into());
+    assert_eq!(error.code(), ErrorCode::UnknownTable("").code());
+    assert_eq!(error.message(), "t1");
+}
@@ -34,6 +34,7 @@ use crate::impl_flights::meta_api_impl::GetDatabasesAction;
 use crate::impl_flights::meta_api_impl::GetTableAction;
 use crate::impl_flights::meta_api_impl::GetTableExtReq;
 use crate::impl_flights::meta_api_impl::GetTablesAction;
+use crate::impl_flights::meta_api_impl::MGetTablesAction;
 use crate::protobuf::FlightStoreRequest;
 
 pub trait RequestFor {
@@ -67,6 +68,7 @@ pub enum StoreDoAction {
     GetTable(GetTableAction),
     GetTableExt(GetTableExtReq),
     GetTables(GetTablesAction),
+    MGetTables(MGetTablesAction),
     GetDatabases(GetDatabasesAction),
 
     // general purpose kv
@@ -24,12 +24,16 @@ mod stream_skip_test;
 #[cfg(test)]
 mod stream_limit_by_test;
 
+#[cfg(test)]
+mod stream_limit_bytes_test;
+
 mod sources;
 mod stream;
 mod stream_abort;
 mod stream_correct_with_schema;
 mod stream_datablock;
 mod stream_limit_by;
+mod stream_limit_bytes;
 mod stream_parquet;
 mod stream_progress;
 mod stream_skip;
@@ -44,6 +48,7 @@ pub use stream_abort::AbortStream;
 pub use stream_correct_with_schema::CorrectWithSchemaStream;
 pub use stream_datablock::DataBlockStream;
 pub use stream_limit_by::LimitByStream;
+pub use stream_limit_bytes::LimitBytesStream;
 pub use stream_parquet::ParquetStream;
 pub use stream_progress::ProgressStream;
 pub use stream_skip::SkipStream;
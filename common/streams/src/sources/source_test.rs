@@ -78,3 +78,43 @@ fn test_parse_csvs() {
     let block = values_source.read().unwrap();
     assert!(block.is_none());
 }
+
+#[test]
+fn test_parse_values_error_tuple() {
+    let buffer = "(1, 'a'), (2, 'b'), (x, 'c')";
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int8, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let mut values_source = ValueSource::new(buffer.as_bytes(), schema, 10);
+    let error = values_source.read().unwrap_err();
+    assert!(error.message().ends_with(" (at VALUES tuple 3)"));
+}
+
+#[test]
+fn test_parse_values_spaces() {
+    let buffer = "(1 , 2 ,'a' ),\n(\n 3,\n\t4\n,\n 'b'\n)\n";
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int8, false),
+        DataField::new("b", DataType::Float64, false),
+        DataField::new("c", DataType::String, false),
+    ]);
+    let mut values_source = ValueSource::new(buffer.as_bytes(), schema, 10);
+    let block = values_source.read().unwrap().unwrap();
+    assert_blocks_eq(
+        vec![
+            "+---+---+---+",
+            "| a | b | c |",
+            "+---+---+---+",
+            "| 1 | 2 | a |",
+            "| 3 | 4 | b |",
+            "+---+---+---+",
+        ],
+        &[block],
+    );
+
+    let block = values_source.read().unwrap();
+    assert!(block.is_none());
+}
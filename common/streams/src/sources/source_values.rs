@@ -99,14 +99,16 @@ where R: io::Read + Send + Sync
                         Ok(res)
                     } else if col != col_size - 1 {
                         reader.until(b',', &mut buf)?;
-                        Ok(&buf.as_slice()[0..buf.len() - 1])
+                        Ok(trim_end_spaces(&buf.as_slice()[0..buf.len() - 1]))
                     } else {
                         reader.until(b')', &mut buf)?;
-                        Ok(&buf.as_slice()[0..buf.len() - 1])
+                        Ok(trim_end_spaces(&buf.as_slice()[0..buf.len() - 1]))
                     }
                 };
                 let bs = bs?;
-                deser.de_text(bs)?;
+                deser.de_text(bs).map_err(|e| {
+                    e.add_message_back(format!(" (at VALUES tuple {})", self.rows + rows + 1))
+                })?;
             }
             rows += 1;
         }
@@ -126,3 +128,12 @@ where R: io::Read + Send + Sync
         )))
     }
 }
+
+// The spaces before an unquoted value are skipped by the reader, the ones before the delimiter are not.
+fn trim_end_spaces(bs: &[u8]) -> &[u8] {
+    let len = bs
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(0, |pos| pos + 1);
+    &bs[..len]
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::SendableDataBlockStream;
+
+/// Fails the stream once the blocks passed through it exceed `max_bytes` in total.
+///
+/// The bytes of every block already returned are accounted, the block crossing the
+/// limit is replaced by the error and the stream ends after it.
+pub struct LimitBytesStream {
+    input: SendableDataBlockStream,
+    max_bytes: usize,
+    bytes: usize,
+    exceeded: bool,
+}
+
+impl LimitBytesStream {
+    pub fn new(input: SendableDataBlockStream, max_bytes: usize) -> Self {
+        LimitBytesStream {
+            input,
+            max_bytes,
+            bytes: 0,
+            exceeded: false,
+        }
+    }
+}
+
+impl Stream for LimitBytesStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.exceeded {
+            return Poll::Ready(None);
+        }
+
+        self.input.poll_next_unpin(ctx).map(|x| match x {
+            Some(Ok(block)) => {
+                self.bytes = self.bytes.saturating_add(block.memory_size());
+                if self.bytes > self.max_bytes {
+                    self.exceeded = true;
+                    Some(Err(ErrorCode::ResultSizeExceeded(format!(
+                        "Result size exceeds max_result_size: {} bytes, the limit is {} bytes",
+                        self.bytes, self.max_bytes
+                    ))))
+                } else {
+                    Some(Ok(block))
+                }
+            }
+            other => other,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::stream::StreamExt;
+
+use crate::*;
+
+fn test_blocks() -> (DataSchemaRef, Vec<DataBlock>) {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("id", DataType::Int64, false)]);
+    let blocks = (0..4)
+        .map(|n| {
+            let ids = (n * 10..n * 10 + 10).collect::<Vec<i64>>();
+            DataBlock::create_by_array(schema.clone(), vec![Series::new(ids)])
+        })
+        .collect::<Vec<_>>();
+    (schema, blocks)
+}
+
+#[tokio::test]
+async fn test_limit_bytes_stream() -> Result<()> {
+    let (schema, blocks) = test_blocks();
+    let block_size = blocks[0].memory_size();
+
+    // Enough for all the blocks.
+    let stream = DataBlockStream::create(schema.clone(), None, blocks.clone());
+    let stream = LimitBytesStream::new(Box::pin(stream), block_size * 4);
+    let results = stream.collect::<Vec<_>>().await;
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|result| result.is_ok()));
+
+    // The third block crosses the limit, the blocks already returned are accounted.
+    let stream = DataBlockStream::create(schema, None, blocks);
+    let stream = LimitBytesStream::new(Box::pin(stream), block_size * 2 + 1);
+    let results = stream.collect::<Vec<_>>().await;
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_ok());
+    match &results[2] {
+        Err(cause) => {
+            assert_eq!(cause.code(), ErrorCode::ResultSizeExceeded("").code());
+            assert_eq!(
+                cause.message(),
+                format!(
+                    "Result size exceeds max_result_size: {} bytes, the limit is {} bytes",
+                    block_size * 3,
+                    block_size * 2 + 1
+                )
+            );
+        }
+        Ok(_) => panic!("The result size limit is not enforced"),
+    }
+    Ok(())
+}
@@ -66,6 +66,7 @@ impl ActionHandler {
             StoreDoAction::DropTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTables(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::MGetTables(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTableExt(a) => s.serialize(self.handle(a).await?),
         }
     }
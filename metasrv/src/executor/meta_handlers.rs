@@ -40,6 +40,7 @@ use common_store_api_sdk::meta_api_impl::GetDatabasesAction;
 use common_store_api_sdk::meta_api_impl::GetTableAction;
 use common_store_api_sdk::meta_api_impl::GetTableExtReq;
 use common_store_api_sdk::meta_api_impl::GetTablesAction;
+use common_store_api_sdk::meta_api_impl::MGetTablesAction;
 use log::info;
 
 use crate::executor::action_handler::RequestHandler;
@@ -351,32 +352,54 @@ impl RequestHandler<GetDatabasesAction> for ActionHandler {
 impl RequestHandler<GetTablesAction> for ActionHandler {
     async fn handle(&self, req: GetTablesAction) -> common_exception::Result<GetTablesReply> {
         let res = self.meta_node.get_tables(req.db.as_str()).await?;
-        Ok(res
-            .iter()
-            .try_fold(Vec::new(), |mut acc, (id, name, tbl)| {
-                let arrow_schema = ArrowSchema::try_from(&FlightData {
-                    data_header: tbl.schema.clone(),
-                    ..Default::default()
-                })
-                .map_err(|e| {
-                    ErrorCode::IllegalSchema(format!(
-                        "invalid schema of table id {}, error: {}",
-                        *id,
-                        e.to_string()
-                    ))
-                })?;
-
-                let tbl_info = TableInfo {
-                    db: req.db.to_string(),
-                    table_id: *id,
-                    name: name.to_string(),
-                    schema: Arc::new(arrow_schema.into()),
-                    engine: tbl.table_engine.to_string(),
-                    options: tbl.table_options.clone(),
-                };
+        res.iter()
+            .map(|(id, name, tbl)| to_table_info(&req.db, name, *id, tbl))
+            .collect()
+    }
+}
 
-                acc.push(tbl_info);
-                Ok::<_, ErrorCode>(acc)
-            })?)
+#[async_trait::async_trait]
+impl RequestHandler<MGetTablesAction> for ActionHandler {
+    async fn handle(&self, req: MGetTablesAction) -> common_exception::Result<MGetTablesReply> {
+        let res = self
+            .meta_node
+            .mget_tables(req.db.as_str(), &req.tables)
+            .await?;
+        res.iter()
+            .zip(req.tables.iter())
+            .map(|(tbl, name)| {
+                tbl.as_ref()
+                    .map(|(id, tbl)| to_table_info(&req.db, name, *id, tbl))
+                    .transpose()
+            })
+            .collect()
     }
 }
+
+fn to_table_info(
+    db: &str,
+    name: &str,
+    id: u64,
+    tbl: &Table,
+) -> common_exception::Result<TableInfo> {
+    let arrow_schema = ArrowSchema::try_from(&FlightData {
+        data_header: tbl.schema.clone(),
+        ..Default::default()
+    })
+    .map_err(|e| {
+        ErrorCode::IllegalSchema(format!(
+            "invalid schema of table id {}, error: {}",
+            id,
+            e.to_string()
+        ))
+    })?;
+
+    Ok(TableInfo {
+        db: db.to_string(),
+        table_id: id,
+        name: name.to_string(),
+        schema: Arc::new(arrow_schema.into()),
+        engine: tbl.table_engine.to_string(),
+        options: tbl.table_options.clone(),
+    })
+}
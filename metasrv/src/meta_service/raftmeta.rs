@@ -1044,6 +1044,34 @@ impl MetaNode {
         }
     }
 
+    /// Get the tables of a database by names, `None` for the name not found.
+    /// All the tables are read from the same state of the local state machine.
+    #[tracing::instrument(level = "debug", skip(self, tbl_names))]
+    pub async fn mget_tables(
+        &self,
+        db_name: &str,
+        tbl_names: &[String],
+    ) -> common_exception::Result<Vec<Option<(u64, Table)>>> {
+        // inconsistent get: from local state machine
+        let sm = self.sto.state_machine.read().await;
+        let db = sm
+            .get_database(db_name)
+            .ok_or_else(|| ErrorCode::UnknownDatabase(format!("unknown database {}", db_name)))?;
+
+        tbl_names
+            .iter()
+            .map(|tbl_name| match db.tables.get(tbl_name) {
+                None => Ok(None),
+                Some(tbl_id) => {
+                    let tbl = sm.tables.get(tbl_id).ok_or_else(|| {
+                        ErrorCode::IllegalMetaState(format!(" table of id {}, not found", tbl_id))
+                    })?;
+                    Ok(Some((*tbl_id, tbl.clone())))
+                }
+            })
+            .collect()
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn get_table(&self, tid: &u64) -> Option<Table> {
         // inconsistent get: from local state machine
@@ -18,11 +18,16 @@ use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
 use common_base::tokio;
+use common_datavalues::prelude::*;
 use common_kv_api::KVApi;
 use common_kv_api_vo::UpsertKVActionResult;
+use common_meta_api::MetaApi;
 use common_metatypes::KVMeta;
 use common_metatypes::KVValue;
 use common_metatypes::MatchSeq;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_store_api_sdk::meta_api_impl::MGET_TABLES_BATCH_SIZE;
 use common_store_api_sdk::StoreClient;
 use common_tracing::tracing;
 use metasrv::init_meta_ut;
@@ -168,6 +173,71 @@ async fn test_generic_kv_mget() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_api_mget_tables() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+    {
+        let span = tracing::span!(tracing::Level::INFO, "test_meta_api_mget_tables");
+        let _ent = span.enter();
+
+        let (_tc, addr) = metasrv::tests::start_metasrv().await?;
+
+        let client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+
+        client
+            .create_database(CreateDatabasePlan {
+                if_not_exists: false,
+                db: "db1".to_string(),
+                engine: "Default".to_string(),
+                options: Default::default(),
+            })
+            .await?;
+
+        let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+        for table in ["t1", "t2", "t3"] {
+            client
+                .create_table(CreateTablePlan {
+                    if_not_exists: false,
+                    temporary: false,
+                    db: "db1".to_string(),
+                    table: table.to_string(),
+                    schema: schema.clone(),
+                    engine: "Null".to_string(),
+                    options: Default::default(),
+                })
+                .await?;
+        }
+
+        // More names than one batch, with the tables on both sides of the batch boundary.
+        let mut names = (0..MGET_TABLES_BATCH_SIZE * 2 + 1)
+            .map(|i| format!("no_exist_{}", i))
+            .collect::<Vec<_>>();
+        names[0] = "t1".to_string();
+        names[MGET_TABLES_BATCH_SIZE - 1] = "t2".to_string();
+        names[MGET_TABLES_BATCH_SIZE * 2] = "t3".to_string();
+
+        let res = client.mget_tables("db1", &names).await?;
+        assert_eq!(res.len(), names.len());
+        for (i, (tbl, name)) in res.iter().zip(names.iter()).enumerate() {
+            match tbl {
+                Some(tbl) => {
+                    assert_eq!(tbl, &client.get_table("db1", name).await?);
+                    assert_eq!(tbl.name, *name);
+                    assert_eq!(tbl.db, "db1");
+                }
+                None => assert!(name.starts_with("no_exist_"), "table {}: {}", i, name),
+            }
+        }
+        assert_eq!(res.iter().filter(|tbl| tbl.is_some()).count(), 3);
+
+        // Unknown database.
+        let res = client.mget_tables("db2", &names).await;
+        assert!(res.is_err());
+    }
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_generic_kv_list() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_meta_ut!();
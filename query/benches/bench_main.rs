@@ -18,7 +18,9 @@ mod suites;
 
 criterion_main! {
     suites::bench_aggregate_query_sql::benches,
+    suites::bench_date_query_sql::benches,
     suites::bench_filter_query_sql::benches,
+    suites::bench_group_by_dictionary::benches,
     suites::bench_limit_query_sql::benches,
     suites::bench_sort_query_sql::benches,
 }
@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::sum;
+use common_streams::DataBlockStream;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use databend_query::pipelines::transforms::group_by::Aggregator;
+use databend_query::pipelines::transforms::group_by::AggregatorParams;
+use databend_query::pipelines::transforms::group_by::DictionaryKeys;
+use databend_query::pipelines::transforms::group_by::GROUP_BY_DICTIONARY_MAX_ENTRIES;
+
+const BLOCKS: usize = 16;
+const BLOCK_ROWS: usize = 65536;
+
+fn bench_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![
+        DataField::new("country", DataType::String, false),
+        DataField::new("hour", DataType::UInt16, false),
+    ])
+}
+
+// Low-cardinality strings, the case the dictionary is built for.
+fn bench_blocks() -> Vec<DataBlock> {
+    let countries = ["China", "United States", "Germany", "Japan", "Brazil"];
+    (0..BLOCKS)
+        .map(|block| {
+            let rows = (0..BLOCK_ROWS).map(|row| block * BLOCK_ROWS + row);
+            DataBlock::create_by_array(bench_schema(), vec![
+                Series::new(
+                    rows.clone()
+                        .map(|row| countries[row % countries.len()])
+                        .collect::<Vec<_>>(),
+                ),
+                Series::new(rows.map(|row| (row % 24) as u16).collect::<Vec<_>>()),
+            ])
+        })
+        .collect()
+}
+
+async fn group_by(blocks: Vec<DataBlock>, cols: Vec<String>, dictionary: bool) -> Result<()> {
+    let schema = bench_schema();
+    let params = AggregatorParams::try_create(schema.clone(), &[sum(col("hour"))])?;
+    let aggregator = Aggregator::create(HashMethodSerializer::default(), params);
+    let stream = Box::pin(DataBlockStream::create(schema.clone(), None, blocks));
+
+    match dictionary {
+        false => aggregator.aggregate(cols, stream).await?,
+        true => {
+            let keys = DictionaryKeys::try_create(&schema, &cols, GROUP_BY_DICTIONARY_MAX_ENTRIES)?;
+            aggregator
+                .aggregate_with_dictionary(cols, stream, keys.unwrap())
+                .await?
+        }
+    };
+    Ok(())
+}
+
+fn criterion_benchmark_group_by_dictionary(c: &mut Criterion) {
+    let blocks = bench_blocks();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+
+    let cases = [vec!["country"], vec!["country", "hour"]];
+    for cols in cases {
+        let cols = cols.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        for dictionary in [false, true] {
+            let name = format!("GROUP BY {} (dictionary: {})", cols.join(", "), dictionary);
+            c.bench_function(&name, |b| {
+                b.iter(|| {
+                    runtime
+                        .block_on(group_by(blocks.clone(), cols.clone(), dictionary))
+                        .unwrap()
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(benches, criterion_benchmark_group_by_dictionary);
+criterion_main!(benches);
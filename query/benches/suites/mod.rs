@@ -22,7 +22,9 @@ use databend_query::tests::SessionManagerBuilder;
 use futures::StreamExt;
 
 pub mod bench_aggregate_query_sql;
+pub mod bench_date_query_sql;
 pub mod bench_filter_query_sql;
+pub mod bench_group_by_dictionary;
 pub mod bench_limit_query_sql;
 pub mod bench_sort_query_sql;
 
@@ -21,6 +21,8 @@ use common_planners::Expression;
 use common_planners::PlanNode;
 use tonic::Status;
 
+use crate::api::rpc::flight_compression::ExchangeCompression;
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ShuffleAction {
     pub query_id: String,
@@ -28,6 +30,8 @@ pub struct ShuffleAction {
     pub plan: PlanNode,
     pub sinks: Vec<String>,
     pub scatters_expression: Expression,
+    #[serde(default)]
+    pub compression: ExchangeCompression,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -36,6 +40,8 @@ pub struct BroadcastAction {
     pub stage_id: String,
     pub plan: PlanNode,
     pub sinks: Vec<String>,
+    #[serde(default)]
+    pub compression: ExchangeCompression,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -162,6 +168,14 @@ impl FlightAction {
             _ => unimplemented!(),
         }
     }
+
+    pub fn get_compression(&self) -> ExchangeCompression {
+        match self {
+            FlightAction::BroadcastAction(action) => action.compression,
+            FlightAction::PrepareShuffleAction(action) => action.compression,
+            _ => unimplemented!(),
+        }
+    }
 }
 
 impl TryInto<FlightAction> for Action {
@@ -21,6 +21,7 @@ use common_exception::Result;
 use common_planners::Expression;
 
 use crate::api::rpc::flight_actions::FlightAction;
+use crate::api::ExchangeCompression;
 use crate::api::ShuffleAction;
 use crate::tests::parse_query;
 
@@ -32,6 +33,7 @@ async fn test_shuffle_action_try_into() -> Result<()> {
         plan: parse_query("SELECT number FROM numbers(5)")?,
         sinks: vec![String::from("stream_id")],
         scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+        compression: ExchangeCompression::None,
     };
 
     let from_action = FlightAction::PrepareShuffleAction(shuffle_action);
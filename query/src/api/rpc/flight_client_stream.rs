@@ -26,6 +26,8 @@ use tokio_stream::Stream;
 use tokio_stream::StreamExt;
 use tonic::Streaming;
 
+use crate::api::rpc::flight_compression::ExchangeCompression;
+
 #[derive(Debug)]
 pub struct FlightDataStream();
 
@@ -52,6 +54,7 @@ impl FlightDataStream {
                         )
                     }
 
+                    let flight_data = ExchangeCompression::decompress(flight_data)?;
                     let arrow_schema = Arc::new(schema.to_arrow());
                     Ok(
                         flight_data_to_arrow_batch(&flight_data, arrow_schema, true, &[])
@@ -72,6 +75,8 @@ impl FlightDataStream {
         ReceiverStream::new(inner).map(move |flight_data| match flight_data {
             Err(error_code) => Err(error_code),
             Ok(flight_data) => {
+                let flight_data = ExchangeCompression::decompress(flight_data)?;
+
                 fn create_data_block(record_batch: RecordBatch) -> DataBlock {
                     let columns = record_batch
                         .columns()
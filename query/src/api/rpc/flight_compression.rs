@@ -0,0 +1,107 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use common_arrow::arrow_flight::FlightData;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use metrics::counter;
+
+/// The bodies smaller than this are sent as is, compressing them is not worth the cost.
+pub const EXCHANGE_COMPRESSION_MIN_BYTES: usize = 4 * 1024;
+
+// The codec tag at the head of the app_metadata, followed by the uncompressed body size.
+const CODEC_LZ4: u8 = 1;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ExchangeCompression {
+    None,
+    Lz4,
+}
+
+impl Default for ExchangeCompression {
+    fn default() -> Self {
+        ExchangeCompression::None
+    }
+}
+
+impl ExchangeCompression {
+    pub fn from_setting(value: &str) -> Result<ExchangeCompression> {
+        match value.to_lowercase().as_str() {
+            "none" => Ok(ExchangeCompression::None),
+            "lz4" => Ok(ExchangeCompression::Lz4),
+            "zstd" => Err(ErrorCode::UnImplement(
+                "The zstd exchange compression is not supported, use 'lz4' or 'none'.",
+            )),
+            other => Err(ErrorCode::BadArguments(format!(
+                "Unknown exchange compression {:?}, expect 'none' or 'lz4'.",
+                other
+            ))),
+        }
+    }
+
+    /// Compress the body of a packet, the codec is recorded in its app_metadata.
+    /// The packet is sent as is if it is small or does not shrink.
+    pub fn compress(&self, mut data: FlightData) -> Result<FlightData> {
+        let body_size = data.data_body.len();
+        counter!(
+            super::metrics::METRIC_EXCHANGE_UNCOMPRESSED_BYTES,
+            body_size as u64
+        );
+
+        if *self == ExchangeCompression::Lz4
+            && body_size >= EXCHANGE_COMPRESSION_MIN_BYTES
+            && body_size <= i32::MAX as usize
+        {
+            let compressed = lz4::block::compress(&data.data_body, None, false)
+                .map_err_to_code(ErrorCode::BadBytes, || "Cannot compress the exchange data")?;
+
+            if compressed.len() < body_size {
+                let mut metadata = Vec::with_capacity(5);
+                metadata.push(CODEC_LZ4);
+                metadata.extend_from_slice(&(body_size as u32).to_le_bytes());
+                data.app_metadata = metadata;
+                data.data_body = compressed;
+            }
+        }
+
+        counter!(
+            super::metrics::METRIC_EXCHANGE_COMPRESSED_BYTES,
+            data.data_body.len() as u64
+        );
+        Ok(data)
+    }
+
+    /// Decompress a packet with the codec it records, an empty app_metadata means uncompressed.
+    pub fn decompress(mut data: FlightData) -> Result<FlightData> {
+        match data.app_metadata.split_first() {
+            None => Ok(data),
+            Some((&CODEC_LZ4, size)) if size.len() == 4 => {
+                let size = u32::from_le_bytes(size.try_into().unwrap());
+                data.data_body = lz4::block::decompress(&data.data_body, Some(size as i32))
+                    .map_err_to_code(ErrorCode::BadBytes, || {
+                        "Cannot decompress the exchange data"
+                    })?;
+                data.app_metadata = vec![];
+                Ok(data)
+            }
+            Some((codec, _)) => Err(ErrorCode::BadBytes(format!(
+                "Unknown exchange compression codec {}",
+                codec
+            ))),
+        }
+    }
+}
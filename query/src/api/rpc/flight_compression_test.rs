@@ -0,0 +1,300 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::arrow_flight::utils::flight_data_from_arrow_batch;
+use common_arrow::arrow_flight::FlightData;
+use common_base::tokio;
+use common_base::tokio::sync::mpsc;
+use common_base::tokio::sync::Notify;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::StageKind;
+use common_planners::StagePlan;
+use futures::TryStreamExt;
+use metrics_exporter_prometheus::PrometheusHandle;
+use tokio_stream::StreamExt;
+
+use crate::api::rpc::flight_client_stream;
+use crate::api::rpc::flight_compression::EXCHANGE_COMPRESSION_MIN_BYTES;
+use crate::api::rpc::flight_service_stream;
+use crate::api::rpc::DatabendQueryFlightDispatcher;
+use crate::api::ExchangeCompression;
+use crate::api::RpcService;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::PlanScheduler;
+use crate::metrics::MetricService;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::servers::Server;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
+use crate::tests::try_create_cluster_context;
+use crate::tests::try_create_context;
+use crate::tests::ClusterDescriptor;
+use crate::tests::SessionManagerBuilder;
+
+fn numbers_block(rows: u64) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("number", DataType::UInt64, false)]);
+    DataBlock::create_by_array(schema, vec![Series::new((0..rows).collect::<Vec<u64>>())])
+}
+
+fn numbers_flight_data(rows: u64) -> Result<FlightData> {
+    let record_batch: RecordBatch = numbers_block(rows).try_into()?;
+    let (_, flight_data) = flight_data_from_arrow_batch(&record_batch, &IpcWriteOptions::default());
+    Ok(flight_data)
+}
+
+#[test]
+fn test_exchange_compression_from_setting() -> Result<()> {
+    assert_eq!(
+        ExchangeCompression::from_setting("none")?,
+        ExchangeCompression::None
+    );
+    assert_eq!(
+        ExchangeCompression::from_setting("LZ4")?,
+        ExchangeCompression::Lz4
+    );
+
+    match ExchangeCompression::from_setting("zstd") {
+        Ok(_) => panic!("zstd must be rejected"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::UnImplement("").code()),
+    }
+    match ExchangeCompression::from_setting("gzip") {
+        Ok(_) => panic!("gzip must be rejected"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::BadArguments("").code()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_exchange_compression_lz4() -> Result<()> {
+    let flight_data = numbers_flight_data(10000)?;
+    let body_size = flight_data.data_body.len();
+    assert!(body_size >= EXCHANGE_COMPRESSION_MIN_BYTES);
+
+    let compressed = ExchangeCompression::Lz4.compress(flight_data.clone())?;
+    assert_eq!(compressed.app_metadata[0], 1);
+    assert!(compressed.data_body.len() < body_size);
+    assert_eq!(compressed.data_header, flight_data.data_header);
+
+    let decompressed = ExchangeCompression::decompress(compressed)?;
+    assert_eq!(decompressed, flight_data);
+
+    // Corrupted bodies are reported instead of decoded.
+    let mut corrupted = ExchangeCompression::Lz4.compress(flight_data)?;
+    corrupted.data_body.truncate(16);
+    assert!(ExchangeCompression::decompress(corrupted).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_exchange_compression_uncompressed() -> Result<()> {
+    // Small blocks are sent as is.
+    let small = numbers_flight_data(10)?;
+    assert!(small.data_body.len() < EXCHANGE_COMPRESSION_MIN_BYTES);
+    let sent = ExchangeCompression::Lz4.compress(small.clone())?;
+    assert_eq!(sent, small);
+    assert_eq!(ExchangeCompression::decompress(sent)?, small);
+
+    // So are all the blocks without compression, they are what the older versions send.
+    let large = numbers_flight_data(10000)?;
+    let sent = ExchangeCompression::None.compress(large.clone())?;
+    assert!(sent.app_metadata.is_empty());
+    assert_eq!(ExchangeCompression::decompress(sent)?, large);
+
+    let mut unknown = large;
+    unknown.app_metadata = vec![42, 0, 0, 0, 0];
+    match ExchangeCompression::decompress(unknown) {
+        Ok(_) => panic!("Unknown codec must be rejected"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::BadBytes("").code()),
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_exchange_compression_stream() -> Result<()> {
+    for compression in [ExchangeCompression::None, ExchangeCompression::Lz4] {
+        let (tx, rx) = mpsc::channel(2);
+        tx.send(Ok(numbers_block(10000))).await.ok();
+        tx.send(Ok(numbers_block(10))).await.ok();
+        drop(tx);
+
+        let sent = flight_service_stream::FlightDataStream::create(rx, compression)
+            .collect::<Vec<_>>()
+            .await;
+
+        let (tx, rx) = mpsc::channel(2);
+        for flight_data in sent {
+            let flight_data = flight_data.map_err(ErrorCode::from)?;
+            tx.send(Ok(flight_data)).await.ok();
+        }
+        drop(tx);
+
+        let schema = numbers_block(0).schema().clone();
+        let received = flight_client_stream::FlightDataStream::from_receiver(schema, rx)
+            .collect::<Result<Vec<_>>>()
+            .await?;
+
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].num_rows(), 10000);
+        assert_eq!(received[1].num_rows(), 10);
+        assert!(received[0]
+            .column(0)
+            .to_array()?
+            .series_equal(&numbers_block(10000).column(0).to_array()?));
+    }
+    Ok(())
+}
+
+/// Merges the partial aggregations of all the nodes in the final aggregation of the local node.
+struct ConvergentAggregation;
+
+impl PlanRewriter for ConvergentAggregation {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorPartial(plan.clone()))
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorFinal(AggregatorFinalPlan {
+            input: Arc::new(PlanNode::Stage(StagePlan {
+                kind: StageKind::Convergent,
+                scatters_expr: Expression::create_literal(DataValue::UInt64(Some(0))),
+                input: plan.input.clone(),
+            })),
+            ..plan.clone()
+        }))
+    }
+}
+
+async fn start_rpc_service() -> Result<(RpcService, SocketAddr)> {
+    let mut rpc_service = RpcService {
+        abort_notify: Arc::new(Notify::new()),
+        dispatcher: Arc::new(DatabendQueryFlightDispatcher::create()),
+        sessions: SessionManagerBuilder::create().build()?,
+    };
+    let address = rpc_service
+        .start(SocketAddr::from_str("127.0.0.1:0")?)
+        .await?;
+    Ok((rpc_service, address))
+}
+
+async fn execute_distributed(ctx: &DatabendQueryContextRef, query: &str) -> Result<Vec<DataBlock>> {
+    let plan = match PlanParser::create(ctx.clone()).build_from_sql(query)? {
+        PlanNode::Select(plan) => ConvergentAggregation.rewrite_plan_node(&plan.input)?,
+        other => {
+            return Err(ErrorCode::LogicalError(format!(
+                "Not a select: {:?}",
+                other
+            )))
+        }
+    };
+
+    let scheduled_tasks = PlanScheduler::try_create(ctx.clone())?.reschedule(&plan)?;
+    let timeout = ctx.get_settings().get_flight_client_timeout()?;
+    for (node, action) in scheduled_tasks.get_tasks()? {
+        let mut flight_client = ctx
+            .get_cluster()
+            .create_node_conn(&node.id, &ctx.get_config())
+            .await?;
+        flight_client.execute_action(action, timeout).await?;
+    }
+
+    let mut pipeline =
+        PipelineBuilder::create(ctx.clone()).build(&scheduled_tasks.get_local_task())?;
+    pipeline.execute().await?.try_collect::<Vec<_>>().await
+}
+
+fn sorted_lines(blocks: &[DataBlock]) -> Result<Vec<String>> {
+    let mut lines = common_datablocks::pretty_format_blocks(blocks)?
+        .lines()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    lines.sort();
+    Ok(lines)
+}
+
+fn counter_value(handle: &PrometheusHandle, name: &str) -> u64 {
+    let prefix = format!("{} ", name);
+    handle
+        .render()
+        .lines()
+        .find_map(|line| line.strip_prefix(prefix.as_str())?.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_exchange_compression_distributed_group_by() -> Result<()> {
+    let query = "SELECT number % 10000 AS k, SUM(number) AS s, COUNT(*) AS c FROM numbers(100000) GROUP BY k";
+
+    let ctx = try_create_context()?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let expected = executor.execute().await?.try_collect::<Vec<_>>().await?;
+    let expected = sorted_lines(&expected)?;
+    // 10000 rows, the header and the borders.
+    assert_eq!(expected.len(), 10004);
+
+    let handle = MetricService::try_prometheus_handle()?;
+    let (mut local_service, local_address) = start_rpc_service().await?;
+    let (mut remote_service, remote_address) = start_rpc_service().await?;
+
+    for compression in ["none", "lz4"] {
+        let ctx = try_create_cluster_context(
+            ClusterDescriptor::new()
+                .with_node("local", local_address.to_string())
+                .with_node("remote", remote_address.to_string())
+                .with_local_id("local"),
+        )?;
+        ctx.get_settings()
+            .set_exchange_compression(compression.to_string())?;
+
+        let uncompressed_before = counter_value(&handle, "exchange_uncompressed_bytes");
+        let compressed_before = counter_value(&handle, "exchange_compressed_bytes");
+
+        let blocks = execute_distributed(&ctx, query).await?;
+        assert_eq!(
+            sorted_lines(&blocks)?,
+            expected,
+            "compression: {}",
+            compression
+        );
+
+        // The partial aggregations are larger than EXCHANGE_COMPRESSION_MIN_BYTES.
+        let uncompressed =
+            counter_value(&handle, "exchange_uncompressed_bytes") - uncompressed_before;
+        let compressed = counter_value(&handle, "exchange_compressed_bytes") - compressed_before;
+        assert!(uncompressed > 0, "compression: {}", compression);
+        assert!(compressed > 0, "compression: {}", compression);
+        if compression == "lz4" {
+            assert!(compressed < uncompressed);
+        }
+    }
+
+    local_service.shutdown().await;
+    remote_service.shutdown().await;
+    Ok(())
+}
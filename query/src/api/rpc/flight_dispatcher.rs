@@ -28,6 +28,7 @@ use common_exception::ToErrorCode;
 use common_infallible::RwLock;
 use tokio_stream::StreamExt;
 
+use crate::api::rpc::flight_compression::ExchangeCompression;
 use crate::api::rpc::flight_scatter::FlightScatter;
 use crate::api::rpc::flight_scatter_broadcast::BroadcastFlightScatter;
 use crate::api::rpc::flight_scatter_hash::HashFlightScatter;
@@ -42,6 +43,7 @@ struct StreamInfo {
     schema: DataSchemaRef,
     tx: mpsc::Sender<Result<DataBlock>>,
     rx: mpsc::Receiver<Result<DataBlock>>,
+    compression: ExchangeCompression,
 }
 
 pub struct DatabendQueryFlightDispatcher {
@@ -70,7 +72,11 @@ impl DatabendQueryFlightDispatcher {
         self.abort.load(Ordering::Relaxed)
     }
 
-    pub fn get_stream(&self, ticket: &StreamTicket) -> Result<mpsc::Receiver<Result<DataBlock>>> {
+    /// Take the stream of the ticket, with the compression its blocks are sent with.
+    pub fn get_stream(
+        &self,
+        ticket: &StreamTicket,
+    ) -> Result<(mpsc::Receiver<Result<DataBlock>>, ExchangeCompression)> {
         let stage_name = format!("{}/{}", ticket.query_id, ticket.stage_id);
         if let Some(notify) = self.stages_notify.write().remove(&stage_name) {
             notify.notify_waiters();
@@ -78,7 +84,7 @@ impl DatabendQueryFlightDispatcher {
 
         let stream_name = format!("{}/{}", stage_name, ticket.stream);
         match self.streams.write().remove(&stream_name) {
-            Some(stream_info) => Ok(stream_info.rx),
+            Some(stream_info) => Ok((stream_info.rx, stream_info.compression)),
             None => Err(ErrorCode::NotFoundStream("Stream is not found")),
         }
     }
@@ -88,7 +94,14 @@ impl DatabendQueryFlightDispatcher {
         let stage_id = action.get_stage_id();
         let action_sinks = action.get_sinks();
         let data_schema = action.get_plan().schema();
-        self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks);
+        let compression = action.get_compression();
+        self.create_stage_streams(
+            &query_id,
+            &stage_id,
+            &data_schema,
+            &action_sinks,
+            compression,
+        );
 
         match action.get_sinks().len() {
             0 => Err(ErrorCode::LogicalError("")),
@@ -105,7 +118,14 @@ impl DatabendQueryFlightDispatcher {
         let stage_id = action.get_stage_id();
         let action_sinks = action.get_sinks();
         let data_schema = action.get_plan().schema();
-        self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks);
+        let compression = action.get_compression();
+        self.create_stage_streams(
+            &query_id,
+            &stage_id,
+            &data_schema,
+            &action_sinks,
+            compression,
+        );
 
         match action.get_sinks().len() {
             0 => Err(ErrorCode::LogicalError("")),
@@ -256,6 +276,7 @@ impl DatabendQueryFlightDispatcher {
         stage_id: &str,
         schema: &DataSchemaRef,
         streams_name: &[String],
+        compression: ExchangeCompression,
     ) {
         let stage_name = format!("{}/{}", query_id, stage_id);
         self.stages_notify
@@ -272,6 +293,7 @@ impl DatabendQueryFlightDispatcher {
                 schema: schema.clone(),
                 tx,
                 rx,
+                compression,
             });
         }
     }
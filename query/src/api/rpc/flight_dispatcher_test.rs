@@ -22,6 +22,7 @@ use tokio_stream::StreamExt;
 
 use crate::api::rpc::flight_tickets::StreamTicket;
 use crate::api::rpc::DatabendQueryFlightDispatcher;
+use crate::api::ExchangeCompression;
 use crate::api::FlightAction;
 use crate::api::ShuffleAction;
 use crate::tests::parse_query;
@@ -65,12 +66,13 @@ async fn test_run_shuffle_action_with_no_scatters() -> Result<()> {
                     plan: parse_query("SELECT number FROM numbers(5)")?,
                     sinks: vec![stream_id.clone()],
                     scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+                    compression: ExchangeCompression::None,
                 }),
             )
             .await?;
 
         let stream = stream_ticket(&query_id, &stage_id, &stream_id);
-        let receiver = flight_dispatcher.get_stream(&stream)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
@@ -109,12 +111,13 @@ async fn test_run_shuffle_action_with_scatter() -> Result<()> {
                     plan: parse_query("SELECT number FROM numbers(5)")?,
                     sinks: vec!["stream_1".to_string(), "stream_2".to_string()],
                     scatters_expression: Expression::Column("number".to_string()),
+                    compression: ExchangeCompression::None,
                 }),
             )
             .await?;
 
         let stream_1 = stream_ticket(&query_id, &stage_id, "stream_1");
-        let receiver = flight_dispatcher.get_stream(&stream_1)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream_1)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
@@ -131,7 +134,7 @@ async fn test_run_shuffle_action_with_scatter() -> Result<()> {
         assert_blocks_eq(expect, &collect_data_blocks.await?);
 
         let stream_2 = stream_ticket(&query_id, &stage_id, "stream_2");
-        let receiver = flight_dispatcher.get_stream(&stream_2)?;
+        let (receiver, _) = flight_dispatcher.get_stream(&stream_2)?;
         let receiver_stream = ReceiverStream::new(receiver);
         let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
 
@@ -103,10 +103,11 @@ impl FlightService for DatabendQueryFlightService {
 
         match ticket {
             FlightTicket::StreamTicket(steam_ticket) => {
-                let receiver = self.dispatcher.get_stream(&steam_ticket)?;
+                let (receiver, compression) = self.dispatcher.get_stream(&steam_ticket)?;
 
                 Ok(RawResponse::new(
-                    Box::pin(FlightDataStream::create(receiver)) as FlightStream<FlightData>,
+                    Box::pin(FlightDataStream::create(receiver, compression))
+                        as FlightStream<FlightData>,
                 ))
             }
         }
@@ -25,16 +25,23 @@ use futures::task::Context;
 use tokio_stream::Stream;
 use tonic::Status;
 
+use crate::api::rpc::flight_compression::ExchangeCompression;
+
 pub struct FlightDataStream {
     input: Receiver<common_exception::Result<DataBlock>>,
     options: IpcWriteOptions,
+    compression: ExchangeCompression,
 }
 
 impl FlightDataStream {
-    pub fn create(input: Receiver<common_exception::Result<DataBlock>>) -> FlightDataStream {
+    pub fn create(
+        input: Receiver<common_exception::Result<DataBlock>>,
+        compression: ExchangeCompression,
+    ) -> FlightDataStream {
         FlightDataStream {
             input,
             options: IpcWriteOptions::default(),
+            compression,
         }
     }
 }
@@ -53,7 +60,10 @@ impl Stream for FlightDataStream {
                         flight_data_from_arrow_batch(&record_batch, &self.options);
 
                     match dicts.is_empty() {
-                        true => Some(Ok(values)),
+                        true => match self.compression.compress(values) {
+                            Ok(values) => Some(Ok(values)),
+                            Err(error) => Some(Err(Status::from(error))),
+                        },
                         false => Some(Err(Status::unimplemented(
                             "DatabendQuery does not implement dicts.",
                         ))),
@@ -30,6 +30,7 @@ use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::flight_tickets::StreamTicket;
 use crate::api::rpc::DatabendQueryFlightDispatcher;
 use crate::api::rpc::DatabendQueryFlightService;
+use crate::api::ExchangeCompression;
 use crate::api::FlightTicket;
 use crate::api::ShuffleAction;
 use crate::tests::parse_query;
@@ -170,6 +171,7 @@ fn do_action_request(query_id: &str, stage_id: &str) -> Result<Request<Action>>
         plan: parse_query("SELECT number FROM numbers(5)")?,
         sinks: vec![String::from("stream_id")],
         scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+        compression: ExchangeCompression::None,
     });
 
     Ok(Request::new(flight_action.try_into()?))
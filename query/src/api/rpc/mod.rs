@@ -21,6 +21,9 @@ mod flight_service_test;
 #[cfg(test)]
 mod flight_actions_test;
 
+#[cfg(test)]
+mod flight_compression_test;
+
 #[cfg(test)]
 mod flight_tickets_test;
 
@@ -29,6 +32,7 @@ pub use flight_actions::CancelAction;
 pub use flight_actions::FlightAction;
 pub use flight_actions::ShuffleAction;
 pub use flight_client::FlightClient;
+pub use flight_compression::ExchangeCompression;
 pub use flight_dispatcher::DatabendQueryFlightDispatcher;
 pub use flight_service::DatabendQueryFlightService;
 pub use flight_tickets::FlightTicket;
@@ -36,6 +40,7 @@ pub use flight_tickets::FlightTicket;
 mod flight_actions;
 mod flight_client;
 mod flight_client_stream;
+mod flight_compression;
 mod flight_dispatcher;
 mod flight_scatter;
 mod flight_scatter_broadcast;
@@ -43,3 +48,4 @@ mod flight_scatter_hash;
 mod flight_service;
 mod flight_service_stream;
 mod flight_tickets;
+mod metrics;
@@ -38,6 +38,14 @@ pub trait Database: Sync + Send {
         table_version: Option<MetaVersion>,
     ) -> Result<Arc<TableMeta>>;
 
+    /// Get the tables by names, fails if any of them is not found.
+    fn mget_tables(&self, table_names: &[String]) -> Result<Vec<Arc<TableMeta>>> {
+        table_names
+            .iter()
+            .map(|table_name| self.get_table(table_name))
+            .collect()
+    }
+
     /// Get all tables.
     fn get_tables(&self) -> Result<Vec<Arc<TableMeta>>>;
 
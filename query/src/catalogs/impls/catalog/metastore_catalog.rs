@@ -15,6 +15,7 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -28,6 +29,7 @@ use common_planners::DropDatabasePlan;
 
 use crate::catalogs::catalog::Catalog;
 use crate::catalogs::impls::meta_backends::EmbeddedMetaBackend;
+use crate::catalogs::impls::meta_backends::MetaRetryPolicy;
 use crate::catalogs::impls::meta_backends::RemoteMeteStoreClient;
 use crate::catalogs::meta_backend::MetaBackend;
 use crate::catalogs::Database;
@@ -70,7 +72,14 @@ impl MetaStoreCatalog {
             Arc::new(EmbeddedMetaBackend::new())
         } else {
             let store_client_provider = Arc::new(StoreApiProvider::new(&conf));
-            Arc::new(RemoteMeteStoreClient::create(store_client_provider))
+            let retry_policy = MetaRetryPolicy::create(
+                conf.meta.meta_client_max_retries,
+                Duration::from_millis(conf.meta.meta_client_retry_budget_ms),
+            );
+            Arc::new(
+                RemoteMeteStoreClient::create(store_client_provider)
+                    .with_retry_policy(retry_policy),
+            )
         };
 
         let plan = CreateDatabasePlan {
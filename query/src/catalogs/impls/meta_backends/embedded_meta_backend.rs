@@ -165,6 +165,24 @@ impl MetaBackend for EmbeddedMetaBackend {
         Ok(res)
     }
 
+    fn mget_tables(
+        &self,
+        db_name: &str,
+        table_names: &[String],
+    ) -> common_exception::Result<Vec<Option<Arc<TableInfo>>>> {
+        let lock = self.databases.read();
+        match lock.get(db_name) {
+            None => Err(ErrorCode::UnknownDatabase(format!(
+                "Unknown database: {}",
+                db_name
+            ))),
+            Some((_, metas)) => Ok(table_names
+                .iter()
+                .map(|name| metas.name2meta.get(name).cloned())
+                .collect()),
+        }
+    }
+
     fn create_table(&self, plan: CreateTablePlan) -> common_exception::Result<CreateTableReply> {
         let clone = plan.clone();
         let db_name = clone.db.as_str();
@@ -0,0 +1,111 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_tracing::tracing;
+
+const MIN_BACKOFF: Duration = Duration::from_millis(50);
+const MAX_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The retry policy of the read-only requests to the meta service, they are retried
+/// when the meta service is briefly unavailable.
+///
+/// Create and drop are not idempotent, they are sent without the retry policy.
+#[derive(Clone)]
+pub struct MetaRetryPolicy {
+    max_retries: u64,
+    retry_budget: Duration,
+}
+
+impl MetaRetryPolicy {
+    pub fn create(max_retries: u64, retry_budget: Duration) -> MetaRetryPolicy {
+        MetaRetryPolicy {
+            max_retries,
+            retry_budget,
+        }
+    }
+
+    pub fn no_retry() -> MetaRetryPolicy {
+        MetaRetryPolicy::create(0, Duration::from_secs(0))
+    }
+
+    /// Errors caused by an unreachable or slow meta service, but not by the request itself.
+    fn is_transient(error: &ErrorCode) -> bool {
+        let code = error.code();
+        code == ErrorCode::Timeout("").code()
+            || code == ErrorCode::CannotConnectNode("").code()
+            || code == ErrorCode::MetaServiceUnavailable("").code()
+    }
+
+    /// Runs `f` until it succeeds, fails with a non-transient error, or the retries are exhausted.
+    /// Each attempt is bounded by `timeout`, the backoff sleeps without blocking the thread.
+    pub async fn retry<T, F, Fut>(
+        &self,
+        operation: &str,
+        timeout: Option<Duration>,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let start = Instant::now();
+        let mut backoff = MIN_BACKOFF;
+        let mut retries = 0;
+
+        loop {
+            let attempt = match timeout {
+                None => f().await,
+                Some(timeout) => match tokio::time::timeout(timeout, f()).await {
+                    Ok(res) => res,
+                    Err(elapsed) => Err(ErrorCode::Timeout(elapsed.to_string())),
+                },
+            };
+
+            let error = match attempt {
+                Ok(res) => return Ok(res),
+                Err(error) => error,
+            };
+
+            let exhausted =
+                retries >= self.max_retries || start.elapsed() + backoff > self.retry_budget;
+            if !Self::is_transient(&error) || exhausted {
+                return match retries {
+                    0 => Err(error),
+                    _ => Err(error.add_message_back(format!(
+                        " (meta service {} failed after {} retries)",
+                        operation, retries
+                    ))),
+                };
+            }
+
+            retries += 1;
+            tracing::warn!(
+                "Meta service {} failed, retry {} after {:?}: {}",
+                operation,
+                retries,
+                backoff,
+                error
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+        }
+    }
+}
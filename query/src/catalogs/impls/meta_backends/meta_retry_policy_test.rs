@@ -0,0 +1,161 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::catalogs::impls::meta_backends::MetaRetryPolicy;
+
+/// Fails the first `failures` calls with `error`, then succeeds.
+struct FlakyMetaService {
+    failures: u64,
+    error: fn() -> ErrorCode,
+    calls: AtomicU64,
+}
+
+impl FlakyMetaService {
+    fn create(failures: u64, error: fn() -> ErrorCode) -> Arc<FlakyMetaService> {
+        Arc::new(FlakyMetaService {
+            failures,
+            error,
+            calls: AtomicU64::new(0),
+        })
+    }
+
+    async fn call(&self) -> Result<u64> {
+        let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        match calls <= self.failures {
+            true => Err((self.error)()),
+            false => Ok(calls),
+        }
+    }
+
+    fn calls(&self) -> u64 {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+fn timeout() -> ErrorCode {
+    ErrorCode::Timeout("timeout")
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_retry_policy_recover() -> Result<()> {
+    let flaky = FlakyMetaService::create(2, timeout);
+    let policy = MetaRetryPolicy::create(3, Duration::from_secs(10));
+
+    let calls = policy.retry("get_database", None, || flaky.call()).await?;
+    assert_eq!(calls, 3);
+
+    // The meta service is unavailable during the leader election.
+    let flaky = FlakyMetaService::create(1, || ErrorCode::MetaServiceUnavailable("no leader"));
+    let calls = policy.retry("get_database", None, || flaky.call()).await?;
+    assert_eq!(calls, 2);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_retry_policy_exhausted() -> Result<()> {
+    let flaky = FlakyMetaService::create(10, timeout);
+    let policy = MetaRetryPolicy::create(3, Duration::from_secs(10));
+
+    let result = policy.retry("get_databases", None, || flaky.call()).await;
+    assert!(result.is_err());
+    let error = result.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::Timeout("").code());
+    assert_eq!(
+        error.message(),
+        "timeout (meta service get_databases failed after 3 retries)"
+    );
+    assert_eq!(flaky.calls(), 4);
+
+    // The budget stops retrying before max retries is reached.
+    let flaky = FlakyMetaService::create(10, timeout);
+    let policy = MetaRetryPolicy::create(100, Duration::from_millis(120));
+    assert!(policy
+        .retry("get_tables", None, || flaky.call())
+        .await
+        .is_err());
+    assert!(flaky.calls() < 10);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_retry_policy_no_retry() -> Result<()> {
+    // Errors not caused by the meta service availability are returned directly.
+    let flaky = FlakyMetaService::create(1, || ErrorCode::UnknownDatabase("db1"));
+    let policy = MetaRetryPolicy::create(3, Duration::from_secs(10));
+    let result = policy.retry("get_database", None, || flaky.call()).await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().message(), "db1");
+    assert_eq!(flaky.calls(), 1);
+
+    // Non-idempotent operations are sent without retry.
+    let flaky = FlakyMetaService::create(1, timeout);
+    let result = MetaRetryPolicy::no_retry()
+        .retry("drop_database", None, || flaky.call())
+        .await;
+    assert!(result.is_err());
+    assert_eq!(flaky.calls(), 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_retry_policy_attempt_timeout() -> Result<()> {
+    let calls = AtomicU64::new(0);
+    let policy = MetaRetryPolicy::create(1, Duration::from_secs(10));
+
+    // The first attempt hangs and is cut by the timeout, the retry succeeds.
+    let res = policy
+        .retry("get_table", Some(Duration::from_millis(100)), || async {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+            Ok(())
+        })
+        .await;
+    assert!(res.is_ok());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+// A single-threaded runtime, the other tasks only make progress if the backoff yields.
+#[tokio::test(flavor = "current_thread")]
+async fn test_meta_retry_policy_backoff_not_blocking() -> Result<()> {
+    let ticks = Arc::new(AtomicU64::new(0));
+    let ticker = {
+        let ticks = ticks.clone();
+        tokio::spawn(async move {
+            loop {
+                ticks.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+    };
+
+    let flaky = FlakyMetaService::create(2, timeout);
+    let policy = MetaRetryPolicy::create(3, Duration::from_secs(10));
+    policy.retry("get_tables", None, || flaky.call()).await?;
+
+    // Backoff of 50ms and 100ms.
+    assert!(ticks.load(Ordering::SeqCst) > 5);
+    ticker.abort();
+    Ok(())
+}
@@ -15,7 +15,11 @@
 
 // TODO move this mod to catalogs
 
+#[cfg(test)]
+mod meta_retry_policy_test;
+
 pub use embedded_meta_backend::EmbeddedMetaBackend;
+pub use meta_retry_policy::MetaRetryPolicy;
 pub use remote_meta_backend::RemoteMeteStoreClient;
 
 //pub use crate::catalogs::metastore_client::DatabaseInfo;
@@ -23,4 +27,5 @@ pub use remote_meta_backend::RemoteMeteStoreClient;
 //pub use crate::catalogs::metastore_client::TableInfo;
 
 mod embedded_meta_backend;
+mod meta_retry_policy;
 mod remote_meta_backend;
@@ -33,6 +33,7 @@ use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
 
+use crate::catalogs::impls::meta_backends::MetaRetryPolicy;
 use crate::catalogs::meta_backend::MetaBackend;
 use crate::common::StoreApiProvider;
 
@@ -42,6 +43,7 @@ type TableMetaCache = LruCache<(MetaId, MetaVersion), Arc<TableInfo>>;
 pub struct RemoteMeteStoreClient {
     rt: Arc<Runtime>,
     rpc_time_out: Option<Duration>,
+    retry_policy: MetaRetryPolicy,
     table_meta_cache: Arc<Mutex<TableMetaCache>>,
     store_api_provider: Arc<StoreApiProvider>,
 }
@@ -60,10 +62,17 @@ impl RemoteMeteStoreClient {
             rt: Arc::new(rt),
             // TODO configuration
             rpc_time_out: timeout,
+            retry_policy: MetaRetryPolicy::no_retry(),
             table_meta_cache: Arc::new(Mutex::new(LruCache::new(100))),
             store_api_provider: apis_provider,
         }
     }
+
+    /// Retry the read-only requests by `retry_policy`.
+    pub fn with_retry_policy(mut self, retry_policy: MetaRetryPolicy) -> RemoteMeteStoreClient {
+        self.retry_policy = retry_policy;
+        self
+    }
 }
 
 impl MetaBackend for RemoteMeteStoreClient {
@@ -72,12 +81,17 @@ impl MetaBackend for RemoteMeteStoreClient {
         let reply = {
             let tbl_name = table_name.to_string();
             let db_name = db_name.to_string();
+            let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
             self.rt.block_on(
                 async move {
-                    let client = cli_provider.try_get_meta_client().await?;
-                    client.get_table(&db_name, &tbl_name).await
+                    retry_policy
+                        .retry("get_table", timeout, || async {
+                            let client = cli_provider.try_get_meta_client().await?;
+                            client.get_table(&db_name, &tbl_name).await
+                        })
+                        .await
                 },
-                self.rpc_time_out,
+                None,
             )??
         };
 
@@ -106,12 +120,17 @@ impl MetaBackend for RemoteMeteStoreClient {
         }
 
         let cli = self.store_api_provider.clone();
+        let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
         let reply = self.rt.block_on(
             async move {
-                let client = cli.try_get_meta_client().await?;
-                client.get_table_by_id(table_id, table_version).await
+                retry_policy
+                    .retry("get_table_by_id", timeout, || async {
+                        let client = cli.try_get_meta_client().await?;
+                        client.get_table_by_id(table_id, table_version).await
+                    })
+                    .await
             },
-            self.rpc_time_out,
+            None,
         )??;
 
         let res = TableInfo {
@@ -134,12 +153,17 @@ impl MetaBackend for RemoteMeteStoreClient {
         let cli_provider = self.store_api_provider.clone();
         let db = {
             let db_name = db_name.to_owned();
+            let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
             self.rt.block_on(
                 async move {
-                    let client = cli_provider.try_get_meta_client().await?;
-                    client.get_database(&db_name).await
+                    retry_policy
+                        .retry("get_database", timeout, || async {
+                            let client = cli_provider.try_get_meta_client().await?;
+                            client.get_database(&db_name).await
+                        })
+                        .await
                 },
-                self.rpc_time_out,
+                None,
             )??
         };
 
@@ -154,12 +178,17 @@ impl MetaBackend for RemoteMeteStoreClient {
 
     fn get_databases(&self) -> Result<Vec<Arc<DatabaseInfo>>> {
         let cli_provider = self.store_api_provider.clone();
+        let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
         let dbs = self.rt.block_on(
             async move {
-                let client = cli_provider.try_get_meta_client().await?;
-                client.get_databases().await
+                retry_policy
+                    .retry("get_databases", timeout, || async {
+                        let client = cli_provider.try_get_meta_client().await?;
+                        client.get_databases().await
+                    })
+                    .await
             },
-            self.rpc_time_out,
+            None,
         )??;
         Ok(dbs.into_iter().map(Arc::new).collect())
     }
@@ -167,16 +196,44 @@ impl MetaBackend for RemoteMeteStoreClient {
     fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>> {
         let cli = self.store_api_provider.clone();
         let db_name = db_name.to_owned();
+        let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
         let tbls = self.rt.block_on(
             async move {
-                let client = cli.try_get_meta_client().await?;
-                client.get_tables(&db_name).await
+                retry_policy
+                    .retry("get_tables", timeout, || async {
+                        let client = cli.try_get_meta_client().await?;
+                        client.get_tables(&db_name).await
+                    })
+                    .await
             },
-            self.rpc_time_out,
+            None,
         )??;
         Ok(tbls.into_iter().map(Arc::new).collect())
     }
 
+    fn mget_tables(
+        &self,
+        db_name: &str,
+        table_names: &[String],
+    ) -> Result<Vec<Option<Arc<TableInfo>>>> {
+        let cli = self.store_api_provider.clone();
+        let db_name = db_name.to_owned();
+        let table_names = table_names.to_vec();
+        let (retry_policy, timeout) = (self.retry_policy.clone(), self.rpc_time_out);
+        let tbls = self.rt.block_on(
+            async move {
+                retry_policy
+                    .retry("mget_tables", timeout, || async {
+                        let client = cli.try_get_meta_client().await?;
+                        client.mget_tables(&db_name, &table_names).await
+                    })
+                    .await
+            },
+            None,
+        )??;
+        Ok(tbls.into_iter().map(|tbl| tbl.map(Arc::new)).collect())
+    }
+
     fn create_table(&self, plan: CreateTablePlan) -> Result<CreateTableReply> {
         // TODO validate plan by table engine first
         let cli = self.store_api_provider.clone();
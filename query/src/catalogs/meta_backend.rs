@@ -48,6 +48,13 @@ pub trait MetaBackend: Send + Sync {
 
     fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>>;
 
+    /// Get the tables by names in bulk, `None` for the table not found.
+    fn mget_tables(
+        &self,
+        db_name: &str,
+        table_names: &[String],
+    ) -> Result<Vec<Option<Arc<TableInfo>>>>;
+
     fn get_table_by_id(
         &self,
         db_name: &str,
@@ -25,6 +25,8 @@ const META_USERNAME: &str = "META_USERNAME";
 const META_PASSWORD: &str = "META_PASSWORD";
 const META_RPC_TLS_SERVER_ROOT_CA_CERT: &str = "META_RPC_TLS_SERVER_ROOT_CA_CERT";
 const META_RPC_TLS_SERVICE_DOMAIN_NAME: &str = "META_RPC_TLS_SERVICE_DOMAIN_NAME";
+const META_CLIENT_MAX_RETRIES: &str = "META_CLIENT_MAX_RETRIES";
+const META_CLIENT_RETRY_BUDGET_MS: &str = "META_CLIENT_RETRY_BUDGET_MS";
 
 /// Meta config group.
 /// serde(default) make the toml de to default working.
@@ -58,6 +60,24 @@ pub struct MetaConfig {
     )]
     #[serde(default)]
     pub rpc_tls_meta_service_domain_name: String,
+
+    #[structopt(
+        long,
+        env = META_CLIENT_MAX_RETRIES,
+        default_value = "3",
+        help = "Max retries of the read-only requests to MetaStore when it is unavailable"
+    )]
+    #[serde(default = "MetaConfig::default_meta_client_max_retries")]
+    pub meta_client_max_retries: u64,
+
+    #[structopt(
+        long,
+        env = META_CLIENT_RETRY_BUDGET_MS,
+        default_value = "5000",
+        help = "Total time in milliseconds a request to MetaStore is allowed to spend on retries"
+    )]
+    #[serde(default = "MetaConfig::default_meta_client_retry_budget_ms")]
+    pub meta_client_retry_budget_ms: u64,
 }
 
 impl MetaConfig {
@@ -68,9 +88,19 @@ impl MetaConfig {
             meta_password: "".to_string(),
             rpc_tls_meta_server_root_ca_cert: "".to_string(),
             rpc_tls_meta_service_domain_name: "localhost".to_string(),
+            meta_client_max_retries: Self::default_meta_client_max_retries(),
+            meta_client_retry_budget_ms: Self::default_meta_client_retry_budget_ms(),
         }
     }
 
+    fn default_meta_client_max_retries() -> u64 {
+        3
+    }
+
+    fn default_meta_client_retry_budget_ms() -> u64 {
+        5000
+    }
+
     pub fn load_from_env(mut_config: &mut Config) {
         env_helper!(mut_config, meta, meta_address, String, META_ADDRESS);
         env_helper!(mut_config, meta, meta_username, String, META_USERNAME);
@@ -89,6 +119,20 @@ impl MetaConfig {
             String,
             META_RPC_TLS_SERVICE_DOMAIN_NAME
         );
+        env_helper!(
+            mut_config,
+            meta,
+            meta_client_max_retries,
+            u64,
+            META_CLIENT_MAX_RETRIES
+        );
+        env_helper!(
+            mut_config,
+            meta,
+            meta_client_retry_budget_ms,
+            u64,
+            META_CLIENT_RETRY_BUDGET_MS
+        );
     }
 }
 
@@ -30,10 +30,16 @@ const S3_STORAGE_ACCESS_KEY_ID: &str = "S3_STORAGE_ACCESS_KEY_ID";
 const S3_STORAGE_SECRET_ACCESS_KEY: &str = "S3_STORAGE_SECRET_ACCESS_KEY";
 const S3_STORAGE_BUCKET: &str = "S3_STORAGE_BUCKET";
 
+// HDFS Storage env.
+const HDFS_STORAGE_NAME_NODE: &str = "HDFS_STORAGE_NAME_NODE";
+const HDFS_STORAGE_ROOT: &str = "HDFS_STORAGE_ROOT";
+const HDFS_STORAGE_USER: &str = "HDFS_STORAGE_USER";
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 pub enum StorageType {
     Disk,
     S3,
+    Hdfs,
 }
 
 #[derive(
@@ -91,13 +97,40 @@ impl fmt::Debug for S3StorageConfig {
     }
 }
 
+#[derive(
+    Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml,
+)]
+pub struct HdfsStorageConfig {
+    #[structopt(long, env = HDFS_STORAGE_NAME_NODE, default_value = "", help = "WebHDFS address of the HDFS name node, e.g. http://127.0.0.1:9870")]
+    #[serde(default)]
+    pub name_node: String,
+
+    #[structopt(long, env = HDFS_STORAGE_ROOT, default_value = "", help = "Root directory in HDFS for storage")]
+    #[serde(default)]
+    pub root: String,
+
+    #[structopt(long, env = HDFS_STORAGE_USER, default_value = "", help = "User name of the HDFS simple authentication")]
+    #[serde(default)]
+    pub user: String,
+}
+
+impl HdfsStorageConfig {
+    pub fn default() -> Self {
+        HdfsStorageConfig {
+            name_node: "".to_string(),
+            root: "".to_string(),
+            user: "".to_string(),
+        }
+    }
+}
+
 /// Storage config group.
 /// serde(default) make the toml de to default working.
 #[derive(
     Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml,
 )]
 pub struct StorageConfig {
-    #[structopt(long, env = STORAGE_TYPE, default_value = "", help = "Current storage type: dfs|disk|s3")]
+    #[structopt(long, env = STORAGE_TYPE, default_value = "", help = "Current storage type: dfs|disk|s3|hdfs")]
     #[serde(default)]
     pub storage_type: String,
 
@@ -108,6 +141,10 @@ pub struct StorageConfig {
     // S3 storage backend config.
     #[structopt(flatten)]
     pub s3: S3StorageConfig,
+
+    // HDFS storage backend config.
+    #[structopt(flatten)]
+    pub hdfs: HdfsStorageConfig,
 }
 
 impl StorageConfig {
@@ -116,6 +153,7 @@ impl StorageConfig {
             storage_type: "disk".to_string(),
             disk: DiskStorageConfig::default(),
             s3: S3StorageConfig::default(),
+            hdfs: HdfsStorageConfig::default(),
         }
     }
 
@@ -148,5 +186,16 @@ impl StorageConfig {
             S3_STORAGE_SECRET_ACCESS_KEY
         );
         env_helper!(mut_config.storage, s3, bucket, String, S3_STORAGE_BUCKET);
+
+        // HDFS.
+        env_helper!(
+            mut_config.storage,
+            hdfs,
+            name_node,
+            String,
+            HDFS_STORAGE_NAME_NODE
+        );
+        env_helper!(mut_config.storage, hdfs, root, String, HDFS_STORAGE_ROOT);
+        env_helper!(mut_config.storage, hdfs, user, String, HDFS_STORAGE_USER);
     }
 }
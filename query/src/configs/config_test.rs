@@ -66,6 +66,8 @@ meta_username = \"root\"
 meta_password = \"\"
 rpc_tls_meta_server_root_ca_cert = \"\"
 rpc_tls_meta_service_domain_name = \"localhost\"
+meta_client_max_retries = 3
+meta_client_retry_budget_ms = 5000
 
 [storage]
 storage_type = \"disk\"
@@ -78,6 +80,11 @@ region = \"\"
 access_key_id = \"\"
 secret_access_key = \"\"
 bucket = \"\"
+
+[storage.hdfs]
+name_node = \"\"
+root = \"\"
+user = \"\"
 ";
 
     let tom_actual = toml::to_string(&actual).unwrap();
@@ -99,12 +106,17 @@ fn test_env_config() -> Result<()> {
     std::env::set_var("QUERY_FLIGHT_API_ADDRESS", "1.2.3.4:9091");
     std::env::set_var("QUERY_HTTP_API_ADDRESS", "1.2.3.4:8081");
     std::env::set_var("QUERY_METRIC_API_ADDRESS", "1.2.3.4:7071");
+    std::env::set_var("META_CLIENT_MAX_RETRIES", "5");
+    std::env::set_var("META_CLIENT_RETRY_BUDGET_MS", "10000");
     std::env::set_var("STORAGE_TYPE", "s3");
     std::env::set_var("DISK_STORAGE_DATA_PATH", "/tmp/test");
     std::env::set_var("S3_STORAGE_REGION", "us.region");
     std::env::set_var("S3_STORAGE_ACCESS_KEY_ID", "us.key.id");
     std::env::set_var("S3_STORAGE_SECRET_ACCESS_KEY", "us.key");
     std::env::set_var("S3_STORAGE_BUCKET", "us.bucket");
+    std::env::set_var("HDFS_STORAGE_NAME_NODE", "http://127.0.0.1:9870");
+    std::env::set_var("HDFS_STORAGE_ROOT", "/databend");
+    std::env::set_var("HDFS_STORAGE_USER", "hdfs");
     std::env::remove_var("CONFIG_FILE");
 
     let default = Config::default();
@@ -123,6 +135,9 @@ fn test_env_config() -> Result<()> {
     assert_eq!("1.2.3.4:8081", configured.query.http_api_address);
     assert_eq!("1.2.3.4:7071", configured.query.metric_api_address);
 
+    assert_eq!(5, configured.meta.meta_client_max_retries);
+    assert_eq!(10000, configured.meta.meta_client_retry_budget_ms);
+
     assert_eq!("s3", configured.storage.storage_type);
 
     assert_eq!("/tmp/test", configured.storage.disk.data_path);
@@ -132,6 +147,10 @@ fn test_env_config() -> Result<()> {
     assert_eq!("us.key", configured.storage.s3.secret_access_key);
     assert_eq!("us.bucket", configured.storage.s3.bucket);
 
+    assert_eq!("http://127.0.0.1:9870", configured.storage.hdfs.name_node);
+    assert_eq!("/databend", configured.storage.hdfs.root);
+    assert_eq!("hdfs", configured.storage.hdfs.user);
+
     // clean up
     std::env::remove_var("LOG_LEVEL");
     std::env::remove_var("QUERY_TENANT");
@@ -145,12 +164,17 @@ fn test_env_config() -> Result<()> {
     std::env::remove_var("QUERY_FLIGHT_API_ADDRESS");
     std::env::remove_var("QUERY_HTTP_API_ADDRESS");
     std::env::remove_var("QUERY_METRIC_API_ADDRESS");
+    std::env::remove_var("META_CLIENT_MAX_RETRIES");
+    std::env::remove_var("META_CLIENT_RETRY_BUDGET_MS");
     std::env::remove_var("STORAGE_TYPE");
     std::env::remove_var("DISK_STORAGE_DATA_PATH");
     std::env::remove_var("S3_STORAGE_REGION");
     std::env::remove_var("S3_STORAGE_ACCESS_KEY_ID");
     std::env::remove_var("S3_STORAGE_SECRET_ACCESS_KEY");
     std::env::remove_var("S3_STORAGE_BUCKET");
+    std::env::remove_var("HDFS_STORAGE_NAME_NODE");
+    std::env::remove_var("HDFS_STORAGE_ROOT");
+    std::env::remove_var("HDFS_STORAGE_USER");
     Ok(())
 }
 
@@ -160,3 +184,18 @@ fn test_fuse_commit_version() -> Result<()> {
     assert!(v.len() > 0);
     Ok(())
 }
+
+#[test]
+fn test_meta_client_retry_default_from_toml() -> Result<()> {
+    let toml_str = r#"
+[meta]
+meta_address = "127.0.0.1:9191"
+meta_username = "root"
+"#;
+
+    let conf = Config::load_from_toml_str(toml_str)?;
+    assert_eq!("127.0.0.1:9191", conf.meta.meta_address);
+    assert_eq!(3, conf.meta.meta_client_max_retries);
+    assert_eq!(5000, conf.meta.meta_client_retry_budget_ms);
+    Ok(())
+}
@@ -130,6 +130,46 @@ impl Database for DefaultDatabase {
         })
     }
 
+    fn mget_tables(&self, table_names: &[String]) -> common_exception::Result<Vec<Arc<TableMeta>>> {
+        let mut tables = Vec::with_capacity(table_names.len());
+        let mut missed = vec![];
+        {
+            let cache = self.stateful_table_cache.read();
+            for table_name in table_names {
+                let cached = cache.get_by_name(table_name);
+                if cached.is_none() {
+                    missed.push(table_name.clone());
+                }
+                tables.push(cached);
+            }
+        }
+
+        // Fetch the tables not cached with one bulk call instead of one call per table.
+        let mut table_infos = if missed.is_empty() {
+            vec![].into_iter()
+        } else {
+            self.meta_store_client
+                .mget_tables(self.name(), &missed)?
+                .into_iter()
+        };
+
+        tables
+            .into_iter()
+            .zip(table_names.iter())
+            .map(|(cached, table_name)| match cached {
+                Some(table) => Ok(table),
+                None => match table_infos.next().flatten() {
+                    Some(table_info) => self.build_table_instance(table_info.as_ref()),
+                    None => Err(ErrorCode::UnknownTable(format!(
+                        "Unknown table: '{}.{}'",
+                        self.name(),
+                        table_name
+                    ))),
+                },
+            })
+            .collect()
+    }
+
     fn create_table(&self, plan: CreateTablePlan) -> common_exception::Result<()> {
         // TODO validate table parameters by using TableFactory
         self.meta_store_client.create_table(plan)?;
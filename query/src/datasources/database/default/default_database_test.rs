@@ -0,0 +1,168 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api_vo::CreateDatabaseReply;
+use common_meta_api_vo::CreateTableReply;
+use common_meta_api_vo::DatabaseInfo;
+use common_meta_api_vo::TableInfo;
+use common_metatypes::MetaId;
+use common_metatypes::MetaVersion;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+use common_planners::DropDatabasePlan;
+use common_planners::DropTablePlan;
+
+use crate::catalogs::impls::meta_backends::EmbeddedMetaBackend;
+use crate::catalogs::meta_backend::MetaBackend;
+use crate::catalogs::Database;
+use crate::common::StoreApiProvider;
+use crate::configs::Config;
+use crate::datasources::database::default::default_database::DefaultDatabase;
+use crate::datasources::table::register_prelude_tbl_engines;
+use crate::datasources::table_engine_registry::TableEngineRegistry;
+
+/// Counts the table lookups sent to the meta backend.
+struct CountingMetaBackend {
+    inner: EmbeddedMetaBackend,
+    get_table_calls: AtomicU64,
+    mget_tables_calls: AtomicU64,
+}
+
+impl MetaBackend for CountingMetaBackend {
+    fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        self.inner.create_database(plan)
+    }
+
+    fn drop_database(&self, plan: DropDatabasePlan) -> Result<()> {
+        self.inner.drop_database(plan)
+    }
+
+    fn get_database(&self, db_name: &str) -> Result<Arc<DatabaseInfo>> {
+        self.inner.get_database(db_name)
+    }
+
+    fn get_databases(&self) -> Result<Vec<Arc<DatabaseInfo>>> {
+        self.inner.get_databases()
+    }
+
+    fn create_table(&self, plan: CreateTablePlan) -> Result<CreateTableReply> {
+        self.inner.create_table(plan)
+    }
+
+    fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
+        self.inner.drop_table(plan)
+    }
+
+    fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableInfo>> {
+        self.get_table_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.get_table(db_name, table_name)
+    }
+
+    fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>> {
+        self.inner.get_tables(db_name)
+    }
+
+    fn mget_tables(
+        &self,
+        db_name: &str,
+        table_names: &[String],
+    ) -> Result<Vec<Option<Arc<TableInfo>>>> {
+        self.mget_tables_calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.mget_tables(db_name, table_names)
+    }
+
+    fn get_table_by_id(
+        &self,
+        db_name: &str,
+        table_id: MetaId,
+        table_version: Option<MetaVersion>,
+    ) -> Result<Arc<TableInfo>> {
+        self.inner.get_table_by_id(db_name, table_id, table_version)
+    }
+
+    fn name(&self) -> String {
+        "counting metastore backend".to_string()
+    }
+}
+
+#[test]
+fn test_default_database_mget_tables() -> Result<()> {
+    let backend = Arc::new(CountingMetaBackend {
+        inner: EmbeddedMetaBackend::new(),
+        get_table_calls: AtomicU64::new(0),
+        mget_tables_calls: AtomicU64::new(0),
+    });
+    backend.create_database(CreateDatabasePlan {
+        if_not_exists: false,
+        db: "db1".to_string(),
+        engine: "default".to_string(),
+        options: Default::default(),
+    })?;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let names = (0..2000).map(|i| format!("t{}", i)).collect::<Vec<_>>();
+    for name in &names {
+        backend.create_table(CreateTablePlan {
+            if_not_exists: false,
+            temporary: false,
+            db: "db1".to_string(),
+            table: name.clone(),
+            schema: schema.clone(),
+            engine: "Null".to_string(),
+            options: Default::default(),
+        })?;
+    }
+
+    let registry = Arc::new(TableEngineRegistry::new());
+    register_prelude_tbl_engines(&registry)?;
+    let database = DefaultDatabase::new(
+        "db1",
+        "default",
+        backend.clone(),
+        registry,
+        StoreApiProvider::new(&Config::default()),
+    );
+
+    // All the tables are fetched with one bulk lookup.
+    let tables = database.mget_tables(&names)?;
+    assert_eq!(backend.mget_tables_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(backend.get_table_calls.load(Ordering::SeqCst), 0);
+
+    // Same as getting the tables one by one.
+    assert_eq!(tables.len(), names.len());
+    for (table, name) in tables.iter().zip(names.iter()) {
+        let expect = database.get_table(name)?;
+        assert_eq!(table.raw().name(), name);
+        assert_eq!(table.meta_id(), expect.meta_id());
+    }
+    assert_eq!(backend.get_table_calls.load(Ordering::SeqCst), 2000);
+
+    // Unknown table.
+    let names = vec!["t1".to_string(), "t2000".to_string()];
+    match database.mget_tables(&names) {
+        Ok(_) => panic!("table t2000 must not be found"),
+        Err(cause) => {
+            assert_eq!(cause.code(), ErrorCode::UnknownTable("").code());
+            assert_eq!(cause.message(), "Unknown table: 'db1.t2000'");
+        }
+    }
+    Ok(())
+}
@@ -13,5 +13,8 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod default_database_test;
+
 mod default_database;
 pub mod default_database_factory;
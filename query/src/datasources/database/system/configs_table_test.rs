@@ -39,7 +39,7 @@ async fn test_configs_table() -> Result<()> {
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
     assert_eq!(block.num_columns(), 4);
-    assert_eq!(block.num_rows(), 25);
+    assert_eq!(block.num_rows(), 27);
 
     let expected = vec![
         "+-----------------------------------+----------------+-------+-------------+",
@@ -56,6 +56,8 @@ async fn test_configs_table() -> Result<()> {
         "| log_level                         | INFO           | log   |             |",
         "| max_active_sessions               | 256            | query |             |",
         "| meta_address                      |                | meta  |             |",
+        "| meta_client_max_retries           | 3              | meta  |             |",
+        "| meta_client_retry_budget_ms       | 5000           | meta  |             |",
         "| meta_password                     |                | meta  |             |",
         "| meta_username                     | root           | meta  |             |",
         "| metric_api_address                | 127.0.0.1:7070 | query |             |",
@@ -42,6 +42,8 @@ mod engines_table_test;
 #[cfg(test)]
 mod functions_table_test;
 #[cfg(test)]
+mod processes_table_test;
+#[cfg(test)]
 mod settings_table_test;
 #[cfg(test)]
 mod tables_table_test;
@@ -49,6 +49,10 @@ impl ProcessesTable {
                 DataField::new("state", DataType::String, false),
                 DataField::new("database", DataType::String, false),
                 DataField::new("extra_info", DataType::String, true),
+                DataField::new("read_rows", DataType::UInt64, false),
+                DataField::new("read_bytes", DataType::UInt64, false),
+                DataField::new("total_rows_to_read", DataType::UInt64, false),
+                DataField::new("scan_progress", DataType::Float64, true),
             ]),
         }
     }
@@ -64,6 +68,14 @@ impl ProcessesTable {
             .clone()
             .map(|s| s.into_bytes())
     }
+
+    /// Percentage of the scanned rows, only known when the sources have estimated the total rows.
+    fn process_scan_progress(read_rows: usize, total_rows_to_read: usize) -> Option<f64> {
+        match total_rows_to_read {
+            0 => None,
+            total => Some(f64::min(100.0, read_rows as f64 * 100.0 / total as f64)),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -131,6 +143,10 @@ impl Table for ProcessesTable {
         let mut processes_state = Vec::with_capacity(processes_info.len());
         let mut processes_database = Vec::with_capacity(processes_info.len());
         let mut processes_extra_info = Vec::with_capacity(processes_info.len());
+        let mut processes_read_rows = Vec::with_capacity(processes_info.len());
+        let mut processes_read_bytes = Vec::with_capacity(processes_info.len());
+        let mut processes_total_rows_to_read = Vec::with_capacity(processes_info.len());
+        let mut processes_scan_progress = Vec::with_capacity(processes_info.len());
 
         for process_info in &processes_info {
             processes_id.push(process_info.id.clone().into_bytes());
@@ -139,6 +155,19 @@ impl Table for ProcessesTable {
             processes_database.push(process_info.database.clone().into_bytes());
             processes_host.push(ProcessesTable::process_host(process_info));
             processes_extra_info.push(ProcessesTable::process_extra_info(process_info));
+
+            let (read_rows, read_bytes, total_rows_to_read) =
+                match &process_info.scan_progress_value {
+                    None => (0, 0, 0),
+                    Some(value) => (value.read_rows, value.read_bytes, value.total_rows_to_read),
+                };
+            processes_read_rows.push(read_rows as u64);
+            processes_read_bytes.push(read_bytes as u64);
+            processes_total_rows_to_read.push(total_rows_to_read as u64);
+            processes_scan_progress.push(ProcessesTable::process_scan_progress(
+                read_rows,
+                total_rows_to_read,
+            ));
         }
 
         let schema = self.schema.clone();
@@ -149,6 +178,10 @@ impl Table for ProcessesTable {
             Series::new(processes_state),
             Series::new(processes_database),
             Series::new(processes_extra_info),
+            Series::new(processes_read_rows),
+            Series::new(processes_read_bytes),
+            Series::new(processes_total_rows_to_read),
+            Series::new(processes_scan_progress),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -0,0 +1,108 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
+
+async fn execute_query(ctx: &DatabendQueryContextRef, query: &str) -> Result<Vec<DataBlock>> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?.try_collect::<Vec<_>>().await
+}
+
+fn column_u64(block: &DataBlock, name: &str) -> Result<u64> {
+    match block.try_column_by_name(name)?.try_get(0)? {
+        DataValue::UInt64(Some(value)) => Ok(value),
+        other => Err(ErrorCode::LogicalError(format!(
+            "Unexpected {}: {:?}",
+            name, other
+        ))),
+    }
+}
+
+// The query sleeps in a worker thread while the other one polls system.processes.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_processes_table_scan_progress() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let query_session = sessions.create_session("TestSession")?;
+    let query_ctx = query_session.create_context().await?;
+    query_ctx.get_settings().set_max_threads(1)?;
+    query_ctx.get_settings().set_max_block_size(100)?;
+
+    // 20 blocks, the projection of each one sleeps 0.1 second.
+    let running = Arc::new(AtomicBool::new(true));
+    let query = {
+        let running = running.clone();
+        tokio::spawn(async move {
+            let query = "SELECT number, sleep(0.1) FROM numbers(2000)";
+            let result = execute_query(&query_ctx, query).await;
+            running.store(false, Ordering::SeqCst);
+            result
+        })
+    };
+
+    let ctx = sessions
+        .create_session("TestSession")?
+        .create_context()
+        .await?;
+    let processes = format!(
+        "SELECT read_rows, total_rows_to_read FROM system.processes WHERE id = '{}'",
+        query_session.get_id()
+    );
+
+    let mut samples = vec![];
+    while running.load(Ordering::SeqCst) {
+        let blocks = execute_query(&ctx, &processes).await?;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].num_rows(), 1);
+
+        let read_rows = column_u64(&blocks[0], "read_rows")?;
+        if read_rows > 0 && running.load(Ordering::SeqCst) {
+            assert_eq!(column_u64(&blocks[0], "total_rows_to_read")?, 2000);
+            samples.push(read_rows);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let result = query
+        .await
+        .map_err(|cause| ErrorCode::LogicalError(format!("{:?}", cause)))??;
+    assert_eq!(
+        result.iter().map(|block| block.num_rows()).sum::<usize>(),
+        2000
+    );
+
+    // The counters moved while the query was running.
+    assert!(samples.windows(2).all(|w| w[0] <= w[1]), "{:?}", samples);
+    samples.dedup();
+    assert!(samples.len() >= 2, "{:?}", samples);
+    assert!(samples[0] < 2000, "{:?}", samples);
+
+    Ok(())
+}
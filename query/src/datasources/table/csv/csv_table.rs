@@ -25,6 +25,7 @@ use common_planners::Extras;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
+use common_streams::ProgressStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Table;
@@ -95,6 +96,7 @@ impl Table for CsvTable {
         let start_line: usize = if self.has_header { 1 } else { 0 };
         let file = &self.file;
         let lines_count = count_lines(File::open(file.clone())?)?;
+        ctx.add_total_rows_approx(lines_count.saturating_sub(start_line));
 
         let db = &self.tbl_info.db;
         let name = &self.tbl_info.name;
@@ -123,10 +125,12 @@ impl Table for CsvTable {
         ctx: DatabendQueryContextRef,
         _source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
-        Ok(Box::pin(CsvTableStream::try_create(
-            ctx,
-            self.tbl_info.schema.clone(),
-            self.file.clone(),
+        let progress_callback = ctx.progress_callback()?;
+        let stream =
+            CsvTableStream::try_create(ctx, self.tbl_info.schema.clone(), self.file.clone())?;
+        Ok(Box::pin(ProgressStream::try_create(
+            Box::pin(stream),
+            progress_callback,
         )?))
     }
 }
@@ -37,33 +37,44 @@ use uuid::Uuid;
 use crate::datasources::table::fuse::block_location;
 use crate::datasources::table::fuse::column_stats_reduce;
 use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::DatabendQueryContextRef;
 
 // TODO A better name, we already have a SendableDataBlockStream
-pub type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+pub type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 impl FuseTable {
-    pub async fn append_blocks(&self, mut stream: BlockStream) -> Result<SegmentInfo> {
+    /// Write the blocks of the stream, returns the segment of them.
+    ///
+    /// Whether the query has been killed is checked before writing each block.
+    pub async fn append_blocks(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        mut stream: BlockStream,
+    ) -> Result<SegmentInfo> {
         let mut block_metas = vec![];
         let mut blocks_stats = vec![];
         let mut summary_row_count = 0u64;
         let mut summary_block_count = 0u64;
         let mut summary_uncompressed_byte_size = 0u64;
         let mut summary_compressed_byte_size = 0u64;
+        let data_accessor = self.query_data_accessor(ctx, "block_write")?;
 
         while let Some(block) = stream.next().await {
+            let block = block?;
+            ctx.check_aborting()?;
+
             let schema = block.schema().to_arrow();
             let blk_stats = block_stats(&block)?;
 
             let row_count = block.num_rows() as u64;
             let block_in_memory_size = block.memory_size() as u64;
 
-            let data_accessor = self.data_accessor()?;
-
             let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
             let location = block_location(&part_uuid);
 
-            let file_size = save_block(&schema, block, data_accessor, &location)?;
+            let file_size = save_block(&schema, block, data_accessor.clone(), &location).await?;
 
             // TODO gather parquet meta
             let meta_size = 0u64;
@@ -150,7 +161,10 @@ pub fn block_stats(data_block: &DataBlock) -> Result<HashMap<ColumnId, (DataType
     )
 }
 
-pub(crate) fn save_block(
+/// Encode the block as parquet and put it to the location, returns the size of the file.
+///
+/// The file is put in one request, so that the writes go through the accessor of the query.
+pub(crate) async fn save_block(
     arrow_schema: &ArrowSchema,
     block: DataBlock,
     data_accessor: Arc<dyn DataAccessor>,
@@ -165,17 +179,18 @@ pub(crate) fn save_block(
     use std::iter::repeat;
     let encodings: Vec<_> = repeat(Encoding::Plain).take(block.num_columns()).collect();
 
+    let block_size_hint = block.memory_size();
     let batch = RecordBatch::try_from(block)?;
     let iter = vec![Ok(batch)];
     let row_groups = RowGroupIterator::try_new(iter.into_iter(), arrow_schema, options, encodings)?;
     let parquet_schema = row_groups.parquet_schema().clone();
-    let mut writer = data_accessor.get_writer(location)?;
+    let mut buf = Vec::with_capacity(block_size_hint);
 
     // arrow2 convert schema to metadata, is it required?
     // -- let key_value_metadata = Some(vec![schema_to_metadata_key(schema)]);
 
     let len = common_arrow::parquet::write::write_file(
-        &mut writer,
+        &mut buf,
         row_groups,
         parquet_schema,
         options,
@@ -184,5 +199,6 @@ pub(crate) fn save_block(
     )
     .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
 
+    data_accessor.put(location, buf).await?;
     Ok(len)
 }
@@ -20,7 +20,6 @@ use common_dal::DataAccessor;
 use common_dal::ObjectAccessor;
 use common_exception::Result;
 
-#[allow(dead_code)]
 pub async fn read_segment_async(da: Arc<dyn DataAccessor>, loc: &str) -> Result<SegmentInfo> {
     ObjectAccessor::new(da).read_obj(loc).await
 }
@@ -13,6 +13,9 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod table_test;
+
 mod io;
 mod meta;
 mod table;
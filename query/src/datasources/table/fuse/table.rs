@@ -19,8 +19,7 @@ use std::sync::Arc;
 use common_catalog::BlockLocation;
 use common_catalog::TableSnapshot;
 use common_dal::DataAccessor;
-use common_dal::DataAccessorBuilder;
-use common_dal::DefaultDataAccessorBuilder;
+use common_dal::DataAccessorWithTrace;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -37,9 +36,13 @@ use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
 use crate::catalogs::Table;
+use crate::datasources::table::fuse::build_data_accessor;
+use crate::datasources::table::fuse::merge_statistics;
 use crate::datasources::table::fuse::range_filter;
 use crate::datasources::table::fuse::read_part;
+use crate::datasources::table::fuse::read_segment_async;
 use crate::datasources::table::fuse::read_table_snapshot;
+use crate::datasources::table::fuse::reduce_statistics;
 use crate::datasources::table::fuse::segment_info_location;
 use crate::datasources::table::fuse::snapshot_location;
 use crate::datasources::table::fuse::MetaInfoReader;
@@ -112,11 +115,12 @@ impl Table for FuseTable {
         // primary work to do: partition pruning/elimination
         let tbl_snapshot = self.table_snapshot(&ctx)?;
         if let Some(snapshot) = tbl_snapshot {
-            let da = self.data_accessor()?;
+            let da = self.query_data_accessor(&ctx, "segment")?;
 
-            let meta_reader = MetaInfoReader::new(da, ctx);
+            let meta_reader = MetaInfoReader::new(da, ctx.clone());
             let block_locations = range_filter(&snapshot, &push_downs, meta_reader)?;
             let (statistics, parts) = self.to_partitions(&block_locations);
+            ctx.add_total_rows_approx(statistics.read_rows);
 
             let plan = ReadDataSourcePlan {
                 db: self.tbl_info.db.to_string(),
@@ -177,7 +181,7 @@ impl Table for FuseTable {
             })
             .flatten()
         };
-        let da = self.data_accessor()?;
+        let da = self.query_data_accessor(&ctx, "block_read")?;
         let arrow_schema = self.tbl_info.schema.to_arrow();
         let _h = common_base::tokio::task::spawn_local(async move {
             // TODO error handling is buggy
@@ -214,10 +218,13 @@ impl Table for FuseTable {
             }
         };
 
-        let da = self.data_accessor()?;
+        let da = self.query_data_accessor(&ctx, "meta_write")?;
 
         // 2. Append blocks to storage
-        let segment_info = self.append_blocks(block_stream).await?;
+        let segment_info = self.append_blocks(&ctx, block_stream).await?;
+
+        // The written blocks are not referenced by any snapshot yet, stop here if killed.
+        ctx.check_aborting()?;
 
         let seg_loc = {
             let uuid = Uuid::new_v4().to_simple().to_string();
@@ -234,9 +241,16 @@ impl Table for FuseTable {
             .table_snapshot(&ctx)?
             .unwrap_or_else(TableSnapshot::new);
         let _snapshot_id = tbl_snapshot.snapshot_id;
-        let new_snapshot = tbl_snapshot.append_segment(seg_loc);
+        // Only the new segment is folded into the summary, instead of all the segments.
+        let summary = merge_statistics(&tbl_snapshot.summary, &segment_info.summary)?;
+        let mut new_snapshot = tbl_snapshot.append_segment(seg_loc);
+        new_snapshot.summary = summary;
         let _new_snapshot_id = new_snapshot.snapshot_id;
 
+        if ctx.get_settings().get_enable_fuse_statistics_check()? == 1 {
+            self.check_statistics(&ctx, &new_snapshot).await?;
+        }
+
         {
             let uuid = Uuid::new_v4().to_simple().to_string();
             let snapshot_loc = snapshot_location(&uuid);
@@ -271,13 +285,36 @@ impl FuseTable {
     fn table_snapshot(&self, ctx: &DatabendQueryContextRef) -> Result<Option<TableSnapshot>> {
         let schema = self.schema()?;
         if let Some(loc) = schema.meta().get("META_SNAPSHOT_LOCATION") {
-            let r = read_table_snapshot(self.data_accessor()?, ctx, loc)?;
+            let r = read_table_snapshot(self.query_data_accessor(ctx, "snapshot")?, ctx, loc)?;
             Ok(Some(r))
         } else {
             Ok(None)
         }
     }
 
+    /// Recompute the summary statistics of the snapshot from its segments, fails if they differ.
+    async fn check_statistics(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        snapshot: &TableSnapshot,
+    ) -> Result<()> {
+        let da = self.query_data_accessor(ctx, "segment")?;
+        let mut segment_stats = Vec::with_capacity(snapshot.segments.len());
+        for location in &snapshot.segments {
+            let segment = read_segment_async(da.clone(), location).await?;
+            segment_stats.push(segment.summary);
+        }
+
+        let recomputed = reduce_statistics(&segment_stats)?;
+        match recomputed == snapshot.summary {
+            true => Ok(()),
+            false => Err(ErrorCode::LogicalError(format!(
+                "The summary statistics of snapshot {} drift from its segments, summary: {:?}, recomputed: {:?}",
+                snapshot.snapshot_id, snapshot.summary, recomputed
+            ))),
+        }
+    }
+
     pub(crate) fn empty_read_source_plan(&self) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
             db: self.tbl_info.name.clone(),
@@ -299,8 +336,21 @@ impl FuseTable {
         todo!()
     }
 
-    pub(crate) fn data_accessor(&self) -> Result<Arc<dyn DataAccessor>> {
-        // TODO(xp): temp impl, a DataAccessor should be built by the caller that uses `Table`, not `Table` itself
-        DefaultDataAccessorBuilder::build(&self.storage_scheme)
+    /// The DataAccessor used by the reads and writes of a query, which respects the query's
+    /// open files and bandwidth limits. It is built from the storage config of the query.
+    ///
+    /// The requests are recorded in the io trace of the query with the `tag` of the caller.
+    pub(crate) fn query_data_accessor(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        tag: &'static str,
+    ) -> Result<Arc<dyn DataAccessor>> {
+        let dal_ctx = ctx.get_dal_context();
+        let da = build_data_accessor(
+            &self.storage_scheme,
+            &ctx.get_config().storage,
+            dal_ctx.clone(),
+        )?;
+        Ok(DataAccessorWithTrace::wrap(da, dal_ctx, tag))
     }
 }
@@ -0,0 +1,281 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_meta_api_vo::TableInfo;
+use common_planners::*;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TableStorageScheme;
+use crate::tests::SessionManagerBuilder;
+
+fn create_table(schema: DataSchemaRef) -> FuseTable {
+    FuseTable {
+        tbl_info: TableInfo {
+            db: "default".into(),
+            name: "a".into(),
+            schema,
+            engine: "FUSE".to_string(),
+            options: TableOptions::default(),
+            table_id: 0,
+        },
+        storage_scheme: TableStorageScheme::LocalFs,
+    }
+}
+
+fn insert_plan(schema: DataSchemaRef, blocks: Vec<DataBlock>) -> InsertIntoPlan {
+    let input_stream =
+        futures::stream::iter::<Vec<Result<DataBlock>>>(blocks.into_iter().map(Ok).collect());
+    InsertIntoPlan {
+        db_name: "default".to_string(),
+        tbl_name: "a".to_string(),
+        tbl_id: 0,
+        schema,
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+    }
+}
+
+fn files_of(root: &Path, dir: &str) -> usize {
+    std::fs::read_dir(root.join(dir)).map_or(0, |entries| entries.count())
+}
+
+/// The parts of all the blocks under `root`, the partitions of a snapshot are not
+/// built by read_plan yet.
+fn parts_of(root: &Path) -> Result<Partitions> {
+    let mut parts = vec![];
+    for entry in std::fs::read_dir(root.join("_b"))? {
+        parts.push(Part {
+            name: entry?.file_name().to_string_lossy().to_string(),
+            version: 0,
+        });
+    }
+    Ok(parts)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_append_throttled() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(root.path().display().to_string())
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    // 1MB/s for writes, the DalContext of a query is built from the settings of the session.
+    session
+        .get_settings()
+        .set_max_storage_write_bandwidth_mb(1)?;
+    let ctx = session.create_context().await?;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let table = create_table(schema.clone());
+
+    // Two blocks of 256KB.
+    let blocks = (0..2)
+        .map(|_| {
+            DataBlock::create_by_array(schema.clone(), vec![Series::new(
+                (0..32 * 1024).collect::<Vec<u64>>(),
+            )])
+        })
+        .collect::<Vec<_>>();
+
+    table
+        .append_data(ctx.clone(), insert_plan(schema, blocks))
+        .await?;
+
+    assert_eq!(files_of(root.path(), "_b"), 2);
+    assert_eq!(files_of(root.path(), "_sg"), 1);
+    assert_eq!(files_of(root.path(), "_ss"), 1);
+    assert!(ctx.get_dal_context().get_throttle_wait() >= Duration::from_millis(200));
+
+    // The INSERT reads nothing, the wait is still reported.
+    let stats = ctx.get_dal_context().get_stats_summary().unwrap();
+    assert!(stats.contains("reads: 0,"), "{}", stats);
+    assert!(stats.contains("throttle_wait: "), "{}", stats);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_append_killed() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(root.path().display().to_string())
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let table = create_table(schema.clone());
+
+    // The query is killed after the first block is written.
+    let killer = session.clone();
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1u64, 2])]);
+    let input_stream = futures::stream::iter(0..3).map(move |n| {
+        if n == 1 {
+            killer.force_kill_query();
+        }
+        Result::Ok(block.clone())
+    });
+    let plan = InsertIntoPlan {
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        ..insert_plan(schema, vec![])
+    };
+
+    match table.append_data(ctx.clone(), plan).await {
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::AbortedQuery("").code()),
+        Ok(_) => panic!("append_data must fail after the query is killed"),
+    }
+
+    // The written block is left unreferenced, no segment or snapshot is written.
+    assert_eq!(files_of(root.path(), "_b"), 1);
+    assert_eq!(files_of(root.path(), "_sg"), 0);
+    assert_eq!(files_of(root.path(), "_ss"), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_scan_open_files_limit() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(root.path().display().to_string())
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    session.get_settings().set_max_open_files_per_query(1)?;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let table = create_table(schema.clone());
+    let blocks = (0..3)
+        .map(|n| DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![n as u64; 10])]))
+        .collect::<Vec<_>>();
+    let ctx = session.create_context().await?;
+    table
+        .append_data(ctx.clone(), insert_plan(schema, blocks))
+        .await?;
+
+    let ctx = session.create_context().await?;
+    let dal_ctx = ctx.get_dal_context();
+    let parts = parts_of(root.path())?;
+    ctx.try_set_partitions(parts.clone())?;
+    let plan = ReadDataSourcePlan {
+        parts,
+        ..table.empty_read_source_plan()?
+    };
+
+    // FuseTable::read reads the blocks in a task of spawn_local.
+    let local = tokio::task::LocalSet::new();
+    let rows = local
+        .run_until({
+            let dal_ctx = dal_ctx.clone();
+            async move {
+                // Hold the only permit, the scan has to wait for it.
+                let guard = dal_ctx.acquire_open_file().await?;
+                let stream = table.read(ctx.clone(), &plan).await?;
+                while dal_ctx.get_open_files_wait_count() == 0 {
+                    tokio::task::yield_now().await;
+                }
+                drop(guard);
+
+                let blocks = stream.try_collect::<Vec<_>>().await?;
+                Result::Ok(blocks.iter().map(|block| block.num_rows()).sum::<usize>())
+            }
+        })
+        .await?;
+    assert_eq!(rows, 30);
+
+    // The wait and the max open files are reported in the stats of the query.
+    assert_eq!(dal_ctx.get_max_open_files(), 1);
+    assert_eq!(dal_ctx.get_open_files_wait_count(), 1);
+    let stats = dal_ctx.get_stats_summary().unwrap();
+    assert!(stats.contains("reads: "), "{}", stats);
+    assert!(
+        stats.contains("max_open_files: 1, open_files_wait_count: 1"),
+        "{}",
+        stats
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_scan_io_trace() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(root.path().display().to_string())
+        .build()?;
+    let session = sessions.create_session("TestSession")?;
+    session.get_settings().set_enable_storage_io_trace(1)?;
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let table = create_table(schema.clone());
+    let blocks = (0..3)
+        .map(|n| DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![n as u64; 10])]))
+        .collect::<Vec<_>>();
+    let ctx = session.create_context().await?;
+    table
+        .append_data(ctx.clone(), insert_plan(schema, blocks))
+        .await?;
+
+    let dal_ctx = ctx.get_dal_context();
+    let parts = parts_of(root.path())?;
+    assert_eq!(parts.len(), 3);
+    ctx.try_set_partitions(parts.clone())?;
+    let plan = ReadDataSourcePlan {
+        parts: parts.clone(),
+        ..table.empty_read_source_plan()?
+    };
+
+    // FuseTable::read reads the blocks in a task of spawn_local.
+    let local = tokio::task::LocalSet::new();
+    let rows = local
+        .run_until(async move {
+            let stream = table.read(ctx.clone(), &plan).await?;
+            let blocks = stream.try_collect::<Vec<_>>().await?;
+            Result::Ok(blocks.iter().map(|block| block.num_rows()).sum::<usize>())
+        })
+        .await?;
+    assert_eq!(rows, 30);
+
+    // Each block is opened and read by the scan, with the tag of the block reads.
+    let entries = dal_ctx.get_io_trace().unwrap().entries();
+    for part in &parts {
+        let reads = entries
+            .iter()
+            .filter(|entry| entry.tag == "block_read" && entry.path.ends_with(&part.name))
+            .collect::<Vec<_>>();
+        assert!(
+            reads.iter().any(|entry| entry.operation == "open"),
+            "{}",
+            part.name
+        );
+        assert!(
+            reads
+                .iter()
+                .any(|entry| entry.operation == "read" && entry.bytes > 0),
+            "{}",
+            part.name
+        );
+    }
+    assert!(entries.iter().any(|entry| entry.tag == "block_write"));
+    Ok(())
+}
@@ -13,6 +13,11 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod statistic_helper_test;
+#[cfg(test)]
+mod storage_scheme_helper_test;
+
 mod index_helpers;
 mod location_gen;
 mod projection_helper;
@@ -23,4 +28,6 @@ pub use index_helpers::range_filter;
 pub use location_gen::*;
 pub use projection_helper::project_col_idx;
 pub use statistic_helper::column_stats_reduce;
+pub use statistic_helper::merge_statistics;
+pub use statistic_helper::reduce_statistics;
 pub use storage_scheme_helper::*;
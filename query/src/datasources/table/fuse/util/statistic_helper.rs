@@ -18,6 +18,7 @@ use std::collections::HashMap;
 
 use common_catalog::ColStats;
 use common_catalog::ColumnId;
+use common_catalog::Stats;
 use common_datavalues::DataType;
 use common_exception::Result;
 
@@ -64,7 +65,7 @@ pub fn column_stats_reduce(
                     .min()?;
 
             let max =
-                common_datavalues::DataValue::try_into_data_array(min_stats.as_slice(), data_type)?
+                common_datavalues::DataValue::try_into_data_array(max_stats.as_slice(), data_type)?
                     .max()?;
 
             acc.insert(*id, ColStats {
@@ -77,3 +78,42 @@ pub fn column_stats_reduce(
         },
     )
 }
+
+/// Merge the statistics of two disjoint sets of blocks, e.g. the summary of a snapshot and
+/// the summary of a segment appended to it.
+pub fn merge_statistics(l: &Stats, r: &Stats) -> Result<Stats> {
+    let with_data_type = |col_stats: &HashMap<ColumnId, ColStats>| {
+        col_stats
+            .iter()
+            .map(|(id, stats)| (*id, (stats.min.data_type(), stats.clone())))
+            .collect::<HashMap<_, _>>()
+    };
+
+    let col_stats = column_stats_reduce(vec![
+        with_data_type(&l.col_stats),
+        with_data_type(&r.col_stats),
+    ])?;
+
+    Ok(Stats {
+        row_count: l.row_count + r.row_count,
+        block_count: l.block_count + r.block_count,
+        uncompressed_byte_size: l.uncompressed_byte_size + r.uncompressed_byte_size,
+        compressed_byte_size: l.compressed_byte_size + r.compressed_byte_size,
+        col_stats,
+    })
+}
+
+/// Compute the statistics of a table from scratch, by folding the summaries of all its segments.
+pub fn reduce_statistics<'a>(stats: impl IntoIterator<Item = &'a Stats>) -> Result<Stats> {
+    let empty = Stats {
+        row_count: 0,
+        block_count: 0,
+        uncompressed_byte_size: 0,
+        compressed_byte_size: 0,
+        col_stats: HashMap::new(),
+    };
+
+    stats
+        .into_iter()
+        .try_fold(empty, |acc, item| merge_statistics(&acc, item))
+}
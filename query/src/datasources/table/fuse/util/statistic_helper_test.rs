@@ -0,0 +1,105 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+
+use common_catalog::ColStats;
+use common_catalog::Stats;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::table::fuse::column_stats_reduce;
+use crate::datasources::table::fuse::merge_statistics;
+use crate::datasources::table::fuse::reduce_statistics;
+
+fn segment_stats(seed: u64) -> Stats {
+    let a = (seed * 7919 % 1000) as i32 - 500;
+    let b = format!("s{}", seed * 104729 % 97);
+    let mut col_stats = HashMap::new();
+    col_stats.insert(0, ColStats {
+        min: DataValue::Int32(Some(a)),
+        max: DataValue::Int32(Some(a + (seed % 13) as i32)),
+        null_count: (seed % 3) as usize,
+        row_count: 10,
+    });
+    col_stats.insert(1, ColStats {
+        min: DataValue::String(Some(b.clone().into_bytes())),
+        max: DataValue::String(Some(format!("{}z", b).into_bytes())),
+        null_count: 0,
+        row_count: 10,
+    });
+    Stats {
+        row_count: 10,
+        block_count: 1 + seed % 2,
+        uncompressed_byte_size: 100 + seed,
+        compressed_byte_size: 50 + seed,
+        col_stats,
+    }
+}
+
+#[test]
+fn test_merge_statistics() -> Result<()> {
+    let l = segment_stats(1);
+    let r = segment_stats(2);
+    let merged = merge_statistics(&l, &r)?;
+
+    assert_eq!(merged.row_count, 20);
+    assert_eq!(merged.block_count, l.block_count + r.block_count);
+    assert_eq!(merged.compressed_byte_size, 103);
+    let col = &merged.col_stats[&0];
+    assert_eq!(col.min, DataValue::Int32(Some(338)));
+    assert_eq!(col.max, DataValue::Int32(Some(420)));
+    assert_eq!(col.null_count, 3);
+    assert_eq!(col.row_count, 20);
+    Ok(())
+}
+
+#[test]
+fn test_incremental_statistics_same_as_recomputed() -> Result<()> {
+    let segments = (0..200).map(segment_stats).collect::<Vec<_>>();
+
+    // One commit per segment.
+    let mut summary = reduce_statistics(std::iter::empty())?;
+    for segment in &segments {
+        summary = merge_statistics(&summary, segment)?;
+    }
+
+    // From scratch, all the segments at once.
+    let col_stats = column_stats_reduce(
+        segments
+            .iter()
+            .map(|segment| {
+                segment
+                    .col_stats
+                    .iter()
+                    .map(|(id, stats)| (*id, (stats.min.data_type(), stats.clone())))
+                    .collect::<HashMap<_, (DataType, ColStats)>>()
+            })
+            .collect(),
+    )?;
+    let expected = Stats {
+        row_count: segments.iter().map(|s| s.row_count).sum(),
+        block_count: segments.iter().map(|s| s.block_count).sum(),
+        uncompressed_byte_size: segments.iter().map(|s| s.uncompressed_byte_size).sum(),
+        compressed_byte_size: segments.iter().map(|s| s.compressed_byte_size).sum(),
+        col_stats,
+    };
+
+    assert_eq!(summary, expected);
+    assert_eq!(reduce_statistics(&segments)?, expected);
+    Ok(())
+}
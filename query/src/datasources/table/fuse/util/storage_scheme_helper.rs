@@ -13,10 +13,21 @@
 //  limitations under the License.
 //
 
+use std::sync::Arc;
+
+use common_dal::DalContext;
+use common_dal::DataAccessor;
+use common_dal::DataAccessorBuilder;
+use common_dal::DataAccessorWithMetric;
+use common_dal::DefaultDataAccessorBuilder;
+use common_dal::Local;
 use common_dal::StorageScheme;
+use common_dal::WebHdfs;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::configs::StorageConfig;
+
 pub type TableStorageScheme = StorageScheme;
 
 #[allow(dead_code)]
@@ -27,6 +38,7 @@ pub fn parse_storage_scheme(value: Option<&String>) -> Result<StorageScheme> {
             "LOCAL_FS" | "LOCAL" => Ok(TableStorageScheme::LocalFs),
             "DATABEND_DFS" => Ok(TableStorageScheme::FuseDfs),
             "S3" => Ok(TableStorageScheme::S3),
+            "HDFS" | "WEBHDFS" => Ok(TableStorageScheme::Hdfs),
             _ => Err(ErrorCode::IllegalSchema(format!("unknown scheme {}", v))),
         }
     } else {
@@ -35,3 +47,44 @@ pub fn parse_storage_scheme(value: Option<&String>) -> Result<StorageScheme> {
         ))
     }
 }
+
+/// Build the DataAccessor of the scheme from the storage config.
+///
+/// Local files are opened within the limits of the query's `ctx`, the IO of all the
+/// schemes is throttled by its bandwidth limits and the reads are recorded into it.
+pub fn build_data_accessor(
+    scheme: &StorageScheme,
+    conf: &StorageConfig,
+    ctx: Arc<DalContext>,
+) -> Result<Arc<dyn DataAccessor>> {
+    let da: Arc<dyn DataAccessor> = match scheme {
+        StorageScheme::LocalFs if !conf.disk.data_path.is_empty() => {
+            Arc::new(Local::with_context(&conf.disk.data_path, ctx.clone()))
+        }
+        StorageScheme::Hdfs => {
+            let hdfs = &conf.hdfs;
+            if hdfs.name_node.is_empty() {
+                return Err(ErrorCode::BadArguments(
+                    "The HDFS storage needs the name node, see storage.hdfs.name_node",
+                ));
+            }
+            let user = match hdfs.user.is_empty() {
+                true => None,
+                false => Some(hdfs.user.clone()),
+            };
+            Arc::new(WebHdfs::try_create(&hdfs.name_node, &hdfs.root, user)?)
+        }
+        _ => DefaultDataAccessorBuilder::build_with_context(scheme, ctx.clone())?,
+    };
+    Ok(DataAccessorWithMetric::wrap(da, scheme_name(scheme), ctx))
+}
+
+/// The name of the scheme in the metrics.
+fn scheme_name(scheme: &StorageScheme) -> &'static str {
+    match scheme {
+        StorageScheme::LocalFs => "local",
+        StorageScheme::FuseDfs => "dfs",
+        StorageScheme::S3 => "s3",
+        StorageScheme::Hdfs => "hdfs",
+    }
+}
@@ -0,0 +1,48 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+use std::sync::Arc;
+
+use common_dal::DalContext;
+use common_dal::StorageScheme;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::configs::StorageConfig;
+use crate::datasources::table::fuse::build_data_accessor;
+
+#[test]
+fn test_build_hdfs_data_accessor() -> Result<()> {
+    let ctx = Arc::new(DalContext::create());
+    let mut conf = StorageConfig::default();
+
+    // No name node configured.
+    match build_data_accessor(&StorageScheme::Hdfs, &conf, ctx.clone()) {
+        Ok(_) => panic!("the HDFS data accessor needs a name node"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::BadArguments("").code()),
+    }
+
+    // The configured name node is used.
+    conf.hdfs.name_node = "not a name node".to_string();
+    match build_data_accessor(&StorageScheme::Hdfs, &conf, ctx.clone()) {
+        Ok(_) => panic!("the name node must be a url"),
+        Err(cause) => assert!(cause.message().contains("not a name node")),
+    }
+
+    conf.hdfs.name_node = "http://name-node:9870".to_string();
+    conf.hdfs.root = "/databend".to_string();
+    conf.hdfs.user = "databend".to_string();
+    build_data_accessor(&StorageScheme::Hdfs, &conf, ctx)?;
+    Ok(())
+}
@@ -139,6 +139,7 @@ impl Table for MemoryTable {
         }
 
         while let Some(block) = s.next().await {
+            let block = block?;
             let mut blocks = self.blocks.write();
             blocks.push(block);
         }
@@ -55,7 +55,9 @@ async fn test_memorytable() -> Result<()> {
         ]);
         let blocks = vec![block, block2];
 
-        let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(
+            blocks.clone().into_iter().map(Ok).collect(),
+        );
         let insert_plan = InsertIntoPlan {
             db_name: "default".to_string(),
             tbl_name: "a".to_string(),
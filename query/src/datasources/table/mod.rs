@@ -17,7 +17,7 @@ pub mod fuse;
 mod prelude;
 
 mod csv;
-mod memory;
+pub mod memory;
 mod null;
 mod parquet;
 
@@ -28,6 +28,7 @@ use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_streams::ParquetStream;
+use common_streams::ProgressStream;
 use common_streams::SendableDataBlockStream;
 use crossbeam::channel::bounded;
 use crossbeam::channel::Receiver;
@@ -144,7 +145,7 @@ impl Table for ParquetTable {
 
     async fn read(
         &self,
-        _ctx: DatabendQueryContextRef,
+        ctx: DatabendQueryContextRef,
         _source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
         type BlockSender = Sender<Option<Result<DataBlock>>>;
@@ -160,6 +161,11 @@ impl Table for ParquetTable {
             }
         });
 
-        Ok(Box::pin(ParquetStream::try_create(response_rx)?))
+        let progress_callback = ctx.progress_callback()?;
+        let stream = ParquetStream::try_create(response_rx)?;
+        Ok(Box::pin(ProgressStream::try_create(
+            Box::pin(stream),
+            progress_callback,
+        )?))
     }
 }
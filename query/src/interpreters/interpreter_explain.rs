@@ -17,11 +17,19 @@ use std::sync::Arc;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_planners::EmptyPlan;
 use common_planners::ExplainPlan;
 use common_planners::ExplainType;
+use common_planners::PlanNode;
+use common_planners::PlanVisitor;
+use common_planners::ReadDataSourcePlan;
+use common_planners::RemotePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::api::FlightAction;
+use crate::interpreters::plan_scheduler::FragmentExchange;
+use crate::interpreters::plan_scheduler::PlanScheduler;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::optimizers::Optimizers;
@@ -46,6 +54,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Fragments => self.explain_fragments(),
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -101,4 +110,114 @@ impl ExplainInterpreter {
         );
         Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
     }
+
+    fn explain_fragments(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = Optimizers::create(self.ctx.clone()).optimize(&self.explain.input)?;
+        let scheduler = PlanScheduler::try_create(self.ctx.clone())?;
+        let tasks = scheduler.reschedule(&plan)?;
+        let fragments = tasks.get_fragments()?;
+
+        // The plans of each fragment, the local task is the last one.
+        let mut fragment_plans = fragments
+            .iter()
+            .map(|fragment| match fragment.actions.first() {
+                Some(action) => action.get_plan(),
+                // No node executes the fragment.
+                None => PlanNode::Empty(EmptyPlan::create()),
+            })
+            .collect::<Vec<_>>();
+        fragment_plans.push(tasks.get_local_task());
+
+        // The fragments reading the result of each fragment through the remote plans.
+        let mut readers = vec![None; fragments.len()];
+        let mut estimates = Vec::with_capacity(fragment_plans.len());
+        for (fragment_id, fragment_plan) in fragment_plans.iter().enumerate() {
+            let mut inputs = FragmentInputs::default();
+            inputs.visit_plan_node(fragment_plan)?;
+
+            // The fragments are scheduled bottom up, the estimates of the inputs are known.
+            let (mut rows, mut bytes) = (inputs.read_rows, inputs.read_bytes);
+            for stage_id in &inputs.remote_stages {
+                if let Some(input_id) = tasks.get_fragment_id(stage_id) {
+                    if input_id >= estimates.len() {
+                        continue;
+                    }
+                    readers[input_id] = Some(fragment_id);
+                    rows += estimates[input_id].0;
+                    bytes += estimates[input_id].1;
+                }
+            }
+            estimates.push((rows, bytes));
+        }
+
+        let mut lines = vec![];
+        for fragment in &fragments {
+            let mut header = format!(
+                "Fragment {}: {:?} exchange",
+                fragment.fragment_id, fragment.exchange
+            );
+            if let Some(reader) = readers[fragment.fragment_id] {
+                header.push_str(&format!(" to fragment {}", reader));
+            }
+
+            let first_action = fragment.actions.first();
+            if let Some(FlightAction::PrepareShuffleAction(action)) = first_action {
+                if fragment.exchange != FragmentExchange::Merge {
+                    header.push_str(&format!(", partition by: {:?}", action.scatters_expression));
+                }
+            }
+
+            let sinks = first_action
+                .map(|action| action.get_sinks())
+                .unwrap_or_default();
+            let (rows, bytes) = estimates[fragment.fragment_id];
+            header.push_str(&format!(
+                ", destinations: [{}], estimated rows: {}, estimated bytes: {}",
+                sinks.join(", "),
+                rows,
+                bytes
+            ));
+            lines.push(header);
+            lines.push(format!("  Executors: [{}]", fragment.executors.join(", ")));
+            Self::push_plan_lines(&mut lines, &fragment_plans[fragment.fragment_id]);
+        }
+
+        let local_fragment_id = tasks.get_local_fragment_id();
+        lines.push(format!("Fragment {}: Local", local_fragment_id));
+        Self::push_plan_lines(&mut lines, &fragment_plans[local_fragment_id]);
+
+        let formatted_fragments =
+            Series::new(lines.iter().map(|s| s.as_bytes()).collect::<Vec<_>>());
+        Ok(DataBlock::create_by_array(schema, vec![
+            formatted_fragments,
+        ]))
+    }
+
+    fn push_plan_lines(lines: &mut Vec<String>, plan: &PlanNode) {
+        for line in format!("{:?}", plan).lines() {
+            lines.push(format!("  {}", line));
+        }
+    }
+}
+
+/// The tables and the remote fragments a fragment reads.
+#[derive(Default)]
+struct FragmentInputs {
+    read_rows: usize,
+    read_bytes: usize,
+    remote_stages: Vec<String>,
+}
+
+impl PlanVisitor for FragmentInputs {
+    fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+        self.read_rows += plan.statistics.read_rows;
+        self.read_bytes += plan.statistics.read_bytes;
+        Ok(())
+    }
+
+    fn visit_remote(&mut self, plan: &RemotePlan) -> Result<()> {
+        self.remote_stages.push(plan.stage_id.clone());
+        Ok(())
+    }
 }
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::*;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
 use crate::interpreters::*;
+use crate::sessions::DatabendQueryContextRef;
 use crate::sql::*;
+use crate::tests::try_create_cluster_context;
+use crate::tests::ClusterDescriptor;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_explain_interpreter() -> Result<()> {
@@ -54,3 +58,89 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+async fn explain_lines(ctx: DatabendQueryContextRef, query: &str) -> Result<Vec<String>> {
+    let plan = match PlanParser::create(ctx.clone()).build_from_sql(query)? {
+        PlanNode::Explain(plan) => plan,
+        other => panic!("Expect explain plan, but got {:?}", other),
+    };
+
+    let executor = ExplainInterpreter::try_create(ctx, plan)?;
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let mut lines = vec![];
+    for value in result[0].column(0).to_values()? {
+        match value {
+            DataValue::String(Some(line)) => lines.push(String::from_utf8(line).unwrap()),
+            other => panic!("Unexpected explain line {:?}", other),
+        }
+    }
+    Ok(lines)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_fragments_interpreter() -> Result<()> {
+    // Single node, one fragment without exchanges.
+    let ctx = crate::tests::try_create_context()?;
+    let lines = explain_lines(
+        ctx,
+        "explain fragments select number from numbers_mt(10) where (number+1)=4",
+    )
+    .await?;
+    assert_eq!(lines, vec![
+        "Fragment 0: Local",
+        "  Projection: number:UInt64",
+        "    Filter: ((number + 1) = 4)",
+        "      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+    ]);
+
+    // Merge exchange.
+    let lines = explain_lines(
+        create_cluster_env()?,
+        "explain fragments SELECT SUM(number) FROM numbers(100000000)",
+    )
+    .await?;
+    assert_eq!(lines, vec![
+        "Fragment 0: Merge exchange to fragment 1, destinations: [dummy_local], estimated rows: 100000000, estimated bytes: 800000000",
+        "  Executors: [dummy_local, dummy]",
+        "  AggregatorPartial: groupBy=[[]], aggr=[[SUM(number)]]",
+        "    ReadDataSource: scan partitions: [4], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        "Fragment 1: Local",
+        "  Projection: SUM(number):UInt64",
+        "    AggregatorFinal: groupBy=[[]], aggr=[[SUM(number)]]",
+        "      Remote: stream: dummy_local, fetch nodes: [dummy_local, dummy]",
+    ]);
+
+    // Shuffle exchange, then merge exchange.
+    let lines = explain_lines(
+        create_cluster_env()?,
+        "explain fragments SELECT SUM(number) FROM numbers(100000000) GROUP BY number % 3",
+    )
+    .await?;
+    assert_eq!(lines, vec![
+        "Fragment 0: Shuffle exchange to fragment 1, partition by: sipHash(_group_by_key), destinations: [dummy_local, dummy], estimated rows: 100000000, estimated bytes: 800000000",
+        "  Executors: [dummy_local, dummy]",
+        "  AggregatorPartial: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]",
+        "    Expression: (number % 3):UInt8, number:UInt64 (Before GroupBy)",
+        "      ReadDataSource: scan partitions: [4], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        "Fragment 1: Merge exchange to fragment 2, destinations: [dummy_local], estimated rows: 100000000, estimated bytes: 800000000",
+        "  Executors: [dummy_local, dummy]",
+        "  Projection: SUM(number):UInt64",
+        "    AggregatorFinal: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]",
+        "      Remote: stream: dummy_local, fetch nodes: [dummy_local, dummy]",
+        "Fragment 2: Local",
+        "  Remote: stream: dummy_local, fetch nodes: [dummy_local, dummy]",
+    ]);
+
+    Ok(())
+}
+
+fn create_cluster_env() -> Result<DatabendQueryContextRef> {
+    try_create_cluster_context(
+        ClusterDescriptor::new()
+            .with_node("dummy_local", "localhost:9090")
+            .with_node("dummy", "github.com:9090")
+            .with_local_id("dummy_local"),
+    )
+}
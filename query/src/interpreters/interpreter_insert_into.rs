@@ -19,7 +19,6 @@ use common_planners::InsertIntoPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
-use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -45,9 +44,9 @@ impl Interpreter for InsertIntoInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let datasource = self.ctx.get_catalog();
-        let database = datasource.get_database(self.plan.db_name.as_str())?;
-        let table = database.get_table_by_id(self.plan.tbl_id, None)?;
+        let table = self
+            .ctx
+            .get_table_by_id(&self.plan.db_name, self.plan.tbl_id, None)?;
         table
             .raw()
             .append_data(self.ctx.clone(), self.plan.clone())
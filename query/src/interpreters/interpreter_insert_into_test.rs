@@ -0,0 +1,102 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+const TUPLES: usize = 1_000_000;
+
+fn insert_values_sql(tail: &str) -> String {
+    let mut sql = String::from("insert into default.a values ");
+    for i in 0..TUPLES {
+        sql.push_str(&format!("({}, 'v{}'),", i, i % 10));
+    }
+    sql.push_str(tail);
+    sql
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_insert_into_interpreter_values_streamed() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let block_size = ctx.get_settings().get_max_block_size()? as usize;
+
+    if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("create table default.a(a Int64, b String) Engine = Memory")?
+    {
+        CreateTableInterpreter::try_create(ctx.clone(), plan)?
+            .execute()
+            .await?;
+    }
+
+    // A bad last tuple is only found when the stream reaches it, the plan parses nothing.
+    let sql = insert_values_sql("(1, 'v1', 'extra')");
+    if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone()).build_from_sql(&sql)? {
+        let mut stream = plan.input_stream.lock().take().unwrap();
+
+        // One block is live at a time, each of at most max_block_size tuples.
+        let (mut rows, mut max_block_bytes) = (0, 0);
+        let mut error = None;
+        while let Some(block) = stream.next().await {
+            match block {
+                Ok(block) => {
+                    assert!(block.num_rows() <= block_size);
+                    rows += block.num_rows();
+                    max_block_bytes = max_block_bytes.max(block.memory_size());
+                }
+                Err(cause) => error = Some(cause),
+            }
+        }
+
+        assert_eq!(rows, TUPLES);
+        assert!(error.is_some());
+        assert!(max_block_bytes < 1024 * 1024);
+    } else {
+        panic!("Expect an insert plan");
+    }
+
+    // Insert all the tuples.
+    let sql = insert_values_sql("(0, 'v0')");
+    if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone()).build_from_sql(&sql)? {
+        InsertIntoInterpreter::try_create(ctx.clone(), plan)?
+            .execute()
+            .await?;
+    }
+
+    if let PlanNode::Select(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("select count(*) as c from default.a")?
+    {
+        let stream = SelectInterpreter::try_create(ctx.clone(), plan)?
+            .execute()
+            .await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---------+",
+            "| c       |",
+            "+---------+",
+            "| 1000001 |",
+            "+---------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    } else {
+        panic!("Expect a select plan");
+    }
+    Ok(())
+}
@@ -89,6 +89,15 @@ impl SelectInterpreter {
         let cluster = self.ctx.get_cluster();
         let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
         for (node, action) in remote_stage_actions {
+            if let Some(fragment_id) = scheduled_tasks.get_fragment_id(&action.get_stage_id()) {
+                log::info!(
+                    "Send fragment {} of query {} to node {}",
+                    fragment_id,
+                    action.get_query_id(),
+                    node.id
+                );
+            }
+
             let mut flight_client = cluster.create_node_conn(&node.id, &config).await?;
             let executing_action = flight_client.execute_action(action.clone(), timeout);
 
@@ -22,6 +22,7 @@ use common_planners::SettingPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::api::ExchangeCompression;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -53,6 +54,13 @@ impl Interpreter for SettingInterpreter {
                     let threads: u64 = var.value.parse()?;
                     self.ctx.get_settings().set_max_threads(threads)?;
                 }
+                "exchange_compression" => {
+                    // Reject an unknown codec here rather than in every query after it.
+                    ExchangeCompression::from_setting(&var.value)?;
+                    self.ctx
+                        .get_settings()
+                        .update_settings(&var.variable, var.value)?;
+                }
                 _ => {
                     self.ctx
                         .get_settings()
@@ -13,9 +13,11 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::stream::StreamExt;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
 use crate::interpreters::*;
@@ -58,3 +60,38 @@ async fn test_setting_interpreter_error() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_exchange_compression() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("set exchange_compression='lz4'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?.try_collect::<Vec<_>>().await?;
+    assert_eq!(ctx.get_settings().get_exchange_compression()?, "lz4");
+
+    // The queries after the SET are scheduled with the codec.
+    let plan =
+        PlanParser::create(ctx.clone()).build_from_sql("select sum(number) from numbers_mt(10)")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let result = executor.execute().await?.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-------------+",
+        "| sum(number) |",
+        "+-------------+",
+        "| 45          |",
+        "+-------------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    // An unknown codec is rejected by the SET and the setting is unchanged.
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("set exchange_compression='gzip'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    match executor.execute().await {
+        Ok(_) => panic!("set exchange_compression to gzip must fail"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::BadArguments("").code()),
+    }
+    assert_eq!(ctx.get_settings().get_exchange_compression()?, "lz4");
+
+    Ok(())
+}
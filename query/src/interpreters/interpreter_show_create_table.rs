@@ -26,7 +26,6 @@ use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use log::debug;
 
-use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -52,9 +51,9 @@ impl Interpreter for ShowCreateTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let datasource = self.ctx.get_catalog();
-        let database = datasource.get_database(self.plan.db.as_str())?;
-        let table_meta = database.get_table(self.plan.table.as_str())?;
+        let table_meta = self
+            .ctx
+            .get_table(self.plan.db.as_str(), self.plan.table.as_str())?;
         let table = table_meta.raw();
 
         let name = table.name();
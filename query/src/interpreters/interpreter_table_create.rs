@@ -14,12 +14,16 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::CreateTablePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Catalog;
+use crate::catalogs::TableMeta;
+use crate::datasources::table::memory::memory_table::MemoryTable;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::DatabendQueryContextRef;
@@ -36,6 +40,41 @@ impl CreateTableInterpreter {
     ) -> Result<InterpreterPtr> {
         Ok(Arc::new(CreateTableInterpreter { ctx, plan }))
     }
+
+    fn create_temporary_table(&self) -> Result<()> {
+        let plan = &self.plan;
+        if !plan.engine.eq_ignore_ascii_case("MEMORY") {
+            return Err(ErrorCode::UnImplement(format!(
+                "Temporary table only supports the Memory engine, but got {}",
+                plan.engine
+            )));
+        }
+
+        // Make sure the database exists.
+        self.ctx.get_catalog().get_database(&plan.db)?;
+
+        let table_id = self.ctx.next_temporary_table_id();
+        let table = MemoryTable::try_create(TableInfo {
+            table_id,
+            db: plan.db.clone(),
+            name: plan.table.clone(),
+            schema: plan.schema.clone(),
+            engine: plan.engine.clone(),
+            options: plan.options.clone(),
+        })?;
+
+        let table_meta = Arc::new(TableMeta::create(table.into(), table_id));
+        let added = self
+            .ctx
+            .add_temporary_table(&plan.db, &plan.table, table_meta);
+        match added || plan.if_not_exists {
+            true => Ok(()),
+            false => Err(ErrorCode::TableAlreadyExists(format!(
+                "Temporary table: '{}.{}' already exists.",
+                plan.db, plan.table
+            ))),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -45,9 +84,14 @@ impl Interpreter for CreateTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let datasource = self.ctx.get_catalog();
-        let database = datasource.get_database(self.plan.db.as_str())?;
-        database.create_table(self.plan.clone())?;
+        match self.plan.temporary {
+            true => self.create_temporary_table()?,
+            false => {
+                let datasource = self.ctx.get_catalog();
+                let database = datasource.get_database(self.plan.db.as_str())?;
+                database.create_table(self.plan.clone())?;
+            }
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema.clone(),
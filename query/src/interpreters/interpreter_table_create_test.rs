@@ -13,14 +13,20 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_datablocks::DataBlock;
 use common_datavalues::DataType;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::stream::StreamExt;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::catalogs::Catalog;
 use crate::interpreters::*;
+use crate::sessions::DatabendQueryContextRef;
 use crate::sql::*;
+use crate::tests::SessionManagerBuilder;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_create_table_interpreter() -> Result<()> {
@@ -46,3 +52,135 @@ async fn test_create_table_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+async fn execute_sql(ctx: &DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?.try_collect::<Vec<_>>().await?;
+    Ok(())
+}
+
+fn table_rows(ctx: &DatabendQueryContextRef, table: &str) -> Result<usize> {
+    let table_meta = ctx.get_table("default", table)?;
+    let source_plan = table_meta.raw().read_plan(ctx.clone(), None, None)?;
+    Ok(source_plan.statistics.read_rows)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_temporary_table_interpreter() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let other_session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+    let other_ctx = other_session.create_context().await?;
+
+    execute_sql(&ctx, "create temporary table default.t(a bigint)").await?;
+    execute_sql(&ctx, "insert into default.t values(1),(2),(3)").await?;
+    assert_eq!(table_rows(&ctx, "t")?, 3);
+
+    // Not in the catalog, nor visible to the other sessions.
+    assert!(ctx.get_catalog().get_table("default", "t").is_err());
+    assert!(other_ctx.get_table("default", "t").is_err());
+
+    match execute_sql(&ctx, "create temporary table default.t(a bigint)").await {
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::TableAlreadyExists("").code()),
+        Ok(_) => panic!("create an existing temporary table must fail"),
+    }
+    execute_sql(
+        &ctx,
+        "create temporary table if not exists default.t(a bigint)",
+    )
+    .await?;
+    assert!(execute_sql(
+        &ctx,
+        "create temporary table default.t2(a bigint) Engine = Null"
+    )
+    .await
+    .is_err());
+
+    // The temporary table shadows the table of the same name.
+    execute_sql(
+        &other_ctx,
+        "create table default.t(a bigint) Engine = Memory",
+    )
+    .await?;
+    execute_sql(&other_ctx, "insert into default.t values(1)").await?;
+    assert_eq!(table_rows(&ctx, "t")?, 3);
+    assert_eq!(table_rows(&other_ctx, "t")?, 1);
+
+    execute_sql(&ctx, "drop table default.t").await?;
+    assert_eq!(table_rows(&ctx, "t")?, 1);
+
+    // The temporary tables are dropped with the session.
+    execute_sql(&ctx, "create temporary table default.t3(a bigint)").await?;
+    let temporary_table = std::sync::Arc::downgrade(&ctx.get_table("default", "t3")?);
+    drop(ctx);
+    drop(session);
+    assert!(temporary_table.upgrade().is_none());
+    assert!(other_ctx.get_table("default", "t3").is_err());
+
+    Ok(())
+}
+
+async fn query_sql(ctx: &DatabendQueryContextRef, sql: &str) -> Result<Vec<DataBlock>> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_temporary_table_query() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let other_session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+    let other_ctx = other_session.create_context().await?;
+
+    execute_sql(&ctx, "create temporary table default.t(a bigint, b bigint)").await?;
+    execute_sql(&ctx, "insert into default.t values(1, 10),(2, 20),(3, 30)").await?;
+    execute_sql(&ctx, "create table default.d(a bigint) Engine = Memory").await?;
+    execute_sql(&ctx, "insert into default.d values(2)").await?;
+
+    // The ids are issued by the session.
+    execute_sql(&other_ctx, "create temporary table default.t(a bigint)").await?;
+    assert_eq!(
+        ctx.get_table("default", "t")?.meta_id(),
+        other_ctx.get_table("default", "t")?.meta_id()
+    );
+
+    // Scanned through the pipeline.
+    let blocks = query_sql(&ctx, "select a, b from default.t where a > 1").await?;
+    let expected = vec![
+        "+---+----+",
+        "| a | b  |",
+        "+---+----+",
+        "| 2 | 20 |",
+        "| 3 | 30 |",
+        "+---+----+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    // Together with a table of the catalog, the planner has no JOIN so it is a subquery.
+    let blocks = query_sql(
+        &ctx,
+        "select b from default.t where a = (select max(a) from default.d)",
+    )
+    .await?;
+    let expected = vec!["+----+", "| b  |", "+----+", "| 20 |", "+----+"];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    let blocks = query_sql(&ctx, "show create table default.t").await?;
+    let expected = vec![
+        "+-------+--------------------+",
+        "| Table | Create Table       |",
+        "+-------+--------------------+",
+        "| t     | CREATE TABLE `t` ( |",
+        "|       |   `a` Int64,       |",
+        "|       |   `b` Int64,       |",
+        "|       | ) ENGINE=Memory    |",
+        "+-------+--------------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, blocks.as_slice());
+
+    Ok(())
+}
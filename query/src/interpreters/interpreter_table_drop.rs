@@ -42,9 +42,17 @@ impl Interpreter for DropTableInterpreter {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let datasource = self.ctx.get_catalog();
-        let database = datasource.get_database(self.plan.db.as_str())?;
-        database.drop_table(self.plan.clone())?;
+        // The temporary table shadows the table of the same name, so it is dropped first.
+        let plan = &self.plan;
+        if self
+            .ctx
+            .drop_temporary_table(&plan.db, &plan.table)
+            .is_none()
+        {
+            let datasource = self.ctx.get_catalog();
+            let database = datasource.get_database(plan.db.as_str())?;
+            database.drop_table(plan.clone())?;
+        }
 
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
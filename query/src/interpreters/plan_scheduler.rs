@@ -45,6 +45,7 @@ use common_planners::SubQueriesSetPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
+use crate::api::ExchangeCompression;
 use crate::api::FlightAction;
 use crate::api::ShuffleAction;
 use crate::catalogs::TablePtr;
@@ -60,6 +61,29 @@ pub struct Tasks {
     plan: PlanNode,
     context: DatabendQueryContextRef,
     actions: HashMap<String, VecDeque<FlightAction>>,
+    fragments: Vec<(String, FragmentExchange)>,
+}
+
+/// How a fragment sends its result to the fragment reading it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FragmentExchange {
+    /// Every node scatters its blocks to all the nodes.
+    Shuffle,
+    /// The local node scatters its blocks to all the nodes.
+    Expansive,
+    /// Every node sends its blocks to the local node.
+    Merge,
+    /// The blocks are copied to all the nodes.
+    Broadcast,
+}
+
+/// A scheduled stage, running the same action on each of its executors.
+pub struct PlanFragment {
+    pub fragment_id: usize,
+    pub stage_id: String,
+    pub exchange: FragmentExchange,
+    pub executors: Vec<String>,
+    pub actions: Vec<FlightAction>,
 }
 
 pub struct PlanScheduler {
@@ -71,6 +95,7 @@ pub struct PlanScheduler {
     running_mode: RunningMode,
     query_context: DatabendQueryContextRef,
     subqueries_expressions: Vec<Expressions>,
+    compression: ExchangeCompression,
 }
 
 impl PlanScheduler {
@@ -90,8 +115,12 @@ impl PlanScheduler {
             cluster_nodes_name.push(cluster_nodes[index].id.clone());
         }
 
+        let settings = context.get_settings();
+        let compression = ExchangeCompression::from_setting(&settings.get_exchange_compression()?)?;
+
         Ok(PlanScheduler {
             local_pos,
+            compression,
             nodes_plan,
             stage_id: uuid::Uuid::new_v4().to_string(),
             query_context: context,
@@ -123,6 +152,7 @@ impl Tasks {
         Tasks {
             context,
             actions: HashMap::new(),
+            fragments: vec![],
             plan: PlanNode::Empty(EmptyPlan::create()),
         }
     }
@@ -151,6 +181,51 @@ impl Tasks {
         Ok(tasks)
     }
 
+    /// The fragments are numbered in the order their stages are scheduled,
+    /// the local task is the last fragment.
+    pub fn get_fragment_id(&self, stage_id: &str) -> Option<usize> {
+        self.fragments.iter().position(|(id, _)| id == stage_id)
+    }
+
+    pub fn get_local_fragment_id(&self) -> usize {
+        self.fragments.len()
+    }
+
+    pub fn get_fragments(&self) -> Result<Vec<PlanFragment>> {
+        let cluster = self.context.get_cluster();
+        let cluster_nodes = cluster.get_nodes();
+
+        let mut fragments = Vec::with_capacity(self.fragments.len());
+        for (fragment_id, (stage_id, exchange)) in self.fragments.iter().enumerate() {
+            let mut executors = vec![];
+            let mut actions = vec![];
+            for cluster_node in &cluster_nodes {
+                if let Some(node_actions) = self.actions.get(&cluster_node.id) {
+                    for action in node_actions {
+                        if &action.get_stage_id() == stage_id {
+                            executors.push(cluster_node.id.clone());
+                            actions.push(action.clone());
+                        }
+                    }
+                }
+            }
+
+            fragments.push(PlanFragment {
+                fragment_id,
+                stage_id: stage_id.clone(),
+                exchange: *exchange,
+                executors,
+                actions,
+            });
+        }
+
+        Ok(fragments)
+    }
+
+    pub fn add_fragment(&mut self, stage_id: &str, exchange: FragmentExchange) {
+        self.fragments.push((stage_id.to_string(), exchange));
+    }
+
     #[allow(clippy::ptr_arg)]
     pub fn add_task(&mut self, node_name: &String, action: FlightAction) {
         match self.actions.entry(node_name.to_string()) {
@@ -172,6 +247,7 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
             scatters_expression: stage.scatters_expr.clone(),
+            compression: self.compression,
         }
     }
 
@@ -192,6 +268,7 @@ impl PlanScheduler {
             ));
         }
 
+        tasks.add_fragment(&self.stage_id, FragmentExchange::Shuffle);
         for index in 0..self.nodes_plan.len() {
             let node_name = &self.cluster_nodes[index];
             let shuffle_action = self.normal_action(stage, &self.nodes_plan[index]);
@@ -212,6 +289,7 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
             scatters_expression: stage.scatters_expr.clone(),
+            compression: self.compression,
         }
     }
 
@@ -232,6 +310,7 @@ impl PlanScheduler {
             ));
         }
 
+        tasks.add_fragment(&self.stage_id, FragmentExchange::Expansive);
         self.running_mode = RunningMode::Cluster;
         let node_name = &self.cluster_nodes[self.local_pos];
         let shuffle_action = self.expansive_action(stage, &self.nodes_plan[self.local_pos]);
@@ -255,6 +334,7 @@ impl PlanScheduler {
             plan: input.clone(),
             sinks: vec![self.cluster_nodes[self.local_pos].clone()],
             scatters_expression: stage.scatters_expr.clone(),
+            compression: self.compression,
         }
     }
 
@@ -275,6 +355,7 @@ impl PlanScheduler {
             ));
         }
 
+        tasks.add_fragment(&self.stage_id, FragmentExchange::Merge);
         for index in 0..self.nodes_plan.len() {
             let node_name = &self.cluster_nodes[index];
             let shuffle_action = self.converge_action(stage, &self.nodes_plan[index]);
@@ -435,6 +516,7 @@ impl PlanScheduler {
             query_id: self.query_context.get_id(),
             plan: input.clone(),
             sinks: self.cluster_nodes.clone(),
+            compression: self.compression,
         }
     }
 
@@ -449,6 +531,7 @@ impl PlanScheduler {
     }
 
     fn visit_local_broadcast(&mut self, tasks: &mut Tasks) {
+        tasks.add_fragment(&self.stage_id, FragmentExchange::Broadcast);
         self.running_mode = RunningMode::Cluster;
         let node_name = &self.cluster_nodes[self.local_pos];
         let action = self.broadcast_action(&self.nodes_plan[self.local_pos]);
@@ -467,6 +550,7 @@ impl PlanScheduler {
     }
 
     fn visit_cluster_broadcast(&mut self, tasks: &mut Tasks) {
+        tasks.add_fragment(&self.stage_id, FragmentExchange::Broadcast);
         self.running_mode = RunningMode::Cluster;
         for index in 0..self.nodes_plan.len() {
             let node_name = &self.cluster_nodes[index];
@@ -29,11 +29,13 @@ use common_base::tokio;
 use common_base::tokio::task::JoinHandle;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_infallible::Mutex;
 use futures::future::AbortHandle;
 use futures::future::AbortRegistration;
 use futures::future::Abortable;
 use futures::StreamExt;
 use hyper::server::conn::Http;
+use lazy_static::lazy_static;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use metrics_exporter_prometheus::PrometheusHandle;
 use tokio_stream::wrappers::TcpListenerStream;
@@ -41,6 +43,11 @@ use tokio_stream::wrappers::TcpListenerStream;
 use crate::servers::server::ListeningStream;
 use crate::servers::Server;
 
+lazy_static! {
+    // The recorder can be installed only once in a process.
+    static ref PROMETHEUS_HANDLE: Mutex<Option<PrometheusHandle>> = Mutex::new(None);
+}
+
 pub struct MetricService {
     join_handle: Option<JoinHandle<()>>,
     abort_handle: AbortHandle,
@@ -84,6 +91,18 @@ impl MetricService {
         })
     }
 
+    /// The handle of the prometheus recorder of the process, installed at the first call.
+    pub fn try_prometheus_handle() -> Result<PrometheusHandle> {
+        let mut installed = PROMETHEUS_HANDLE.lock();
+        if let Some(handle) = installed.as_ref() {
+            return Ok(handle.clone());
+        }
+
+        let handle = MetricService::create_prometheus_handle()?;
+        *installed = Some(handle.clone());
+        Ok(handle)
+    }
+
     fn create_prometheus_handle() -> Result<PrometheusHandle> {
         let builder = PrometheusBuilder::new();
         let prometheus_recorder = builder.build();
@@ -145,7 +164,7 @@ impl Server for MetricService {
         match self.abort_registration.take() {
             None => Err(ErrorCode::LogicalError("Http Service already running.")),
             Some(registration) => {
-                let handle = MetricService::create_prometheus_handle()?;
+                let handle = MetricService::try_prometheus_handle()?;
                 let (stream, listener) = Self::listener_tcp(listening).await?;
                 let stream = Abortable::new(stream, registration);
                 self.join_handle = Some(tokio::spawn(self.listen_loop(stream, handle)));
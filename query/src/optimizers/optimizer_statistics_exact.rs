@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataValue;
 use common_exception::Result;
 use common_io::prelude::BinaryWrite;
@@ -54,8 +55,9 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
                 }],
                 PlanNode::Expression(ExpressionPlan { input, .. }),
             ) if op == "count" && args.len() == 1 => match (&args[0], input.as_ref()) {
-                (Expression::Literal { .. }, PlanNode::ReadSource(read_source_plan))
-                    if read_source_plan.statistics.is_exact =>
+                (arg, PlanNode::ReadSource(read_source_plan))
+                    if read_source_plan.statistics.is_exact
+                        && Self::count_all_rows(arg, &read_source_plan.schema) =>
                 {
                     let db_name = "system";
                     let table_name = "one";
@@ -99,7 +101,7 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
                     let expr = Expression::create_literal(DataValue::String(Some(body)));
                     PlanBuilder::from(&dummy_read_plan)
                         .expression(&[expr.clone()], "Exact Statistics")?
-                        .project(&[expr.alias("count(0)")])?
+                        .project(&[expr.alias(&plan.aggr_expr[0].column_name())])?
                         .build()?
                 }
                 _ => PlanNode::AggregatorPartial(plan.clone()),
@@ -120,6 +122,21 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
     }
 }
 
+impl StatisticsExactImpl<'_> {
+    /// COUNT(arg) is the row count if arg is never NULL: a non-null literal, e.g. COUNT(*),
+    /// or a column that is not nullable.
+    fn count_all_rows(arg: &Expression, schema: &DataSchemaRef) -> bool {
+        match arg {
+            Expression::Literal { value, .. } => !value.is_null(),
+            Expression::Column(name) => schema
+                .field_with_name(name)
+                .map(|field| !field.is_nullable())
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
 impl Optimizer for StatisticsExactOptimizer {
     fn name(&self) -> &str {
         "StatisticsExact"
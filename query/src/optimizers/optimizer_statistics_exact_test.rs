@@ -87,4 +87,66 @@ mod tests {
         assert_eq!(expect, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_statistics_exact_optimizer_count_column() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        let statistics = Statistics::new_exact(10, 80);
+        let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: "test".to_string(),
+            table_id: 0,
+            table_version: None,
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::UInt64, true),
+            ]),
+            parts: generate_partitions(8, 10),
+            statistics,
+            description: "".to_string(),
+            scan_plan: Arc::new(ScanPlan::empty()),
+            remote: false,
+            tbl_args: None,
+            push_downs: None,
+        });
+
+        let optimize = |arg: Expression| -> Result<String> {
+            let aggr_expr = Expression::AggregateFunction {
+                op: "count".to_string(),
+                distinct: false,
+                params: vec![],
+                args: vec![arg.clone()],
+            };
+            let plan = PlanBuilder::from(&source_plan)
+                .expression(&[arg], "Before GroupBy")?
+                .aggregate_partial(&[aggr_expr.clone()], &[])?
+                .aggregate_final(source_plan.schema(), &[aggr_expr.clone()], &[])?
+                .project(&[Expression::Column(aggr_expr.column_name())])?
+                .build()?;
+
+            let mut statistics_exact = StatisticsExactOptimizer::create(ctx.clone());
+            Ok(format!("{:?}", statistics_exact.optimize(&plan)?))
+        };
+
+        // The column is never NULL, COUNT(a) is the row count.
+        let expect = "\
+        Projection: count(a):UInt64\
+        \n  AggregatorFinal: groupBy=[[]], aggr=[[count(a)]]\
+        \n    Projection: 0a as count(a):String\
+        \n      Expression: 0a:String (Exact Statistics)\
+        \n        ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]";
+        assert_eq!(expect, optimize(Expression::Column("a".to_string()))?);
+
+        // The nullable column and NULL must be counted.
+        for arg in [
+            Expression::Column("b".to_string()),
+            Expression::create_literal(DataValue::Null),
+        ] {
+            let actual = optimize(arg)?;
+            assert!(actual.contains("AggregatorPartial"), "{}", actual);
+            assert!(!actual.contains("Exact Statistics"), "{}", actual);
+        }
+        Ok(())
+    }
 }
@@ -23,6 +23,8 @@ mod pipeline_walker_test;
 #[cfg(test)]
 mod processor_empty_test;
 #[cfg(test)]
+mod processor_error_context_test;
+#[cfg(test)]
 mod processor_merge_test;
 #[cfg(test)]
 mod processor_mixed_test;
@@ -34,6 +36,7 @@ mod pipeline_display;
 mod pipeline_walker;
 mod processor;
 mod processor_empty;
+mod processor_error_context;
 mod processor_merge;
 mod processor_mixed;
 
@@ -43,5 +46,6 @@ pub use pipeline_builder::PipelineBuilder;
 pub use processor::FormatterSettings;
 pub use processor::Processor;
 pub use processor_empty::EmptyProcessor;
+pub use processor_error_context::ErrorContextProcessor;
 pub use processor_merge::MergeProcessor;
 pub use processor_mixed::MixedProcessor;
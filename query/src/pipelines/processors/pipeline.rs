@@ -19,6 +19,7 @@ use common_exception::Result;
 use common_streams::SendableDataBlockStream;
 
 use super::MixedProcessor;
+use crate::pipelines::processors::ErrorContextProcessor;
 use crate::pipelines::processors::MergeProcessor;
 use crate::pipelines::processors::Pipe;
 use crate::pipelines::processors::Processor;
@@ -63,6 +64,8 @@ impl Pipeline {
     }
 
     pub fn add_source(&mut self, source: Arc<dyn Processor>) -> Result<()> {
+        let source: Arc<dyn Processor> =
+            Arc::new(ErrorContextProcessor::create(self.ctx.clone(), source));
         if self.pipes.first().is_none() {
             let mut first = Pipe::create();
             first.add(source);
@@ -90,7 +93,10 @@ impl Pipeline {
         for x in last_pipe.processors() {
             let mut p = f()?;
             p.connect_to(x.clone())?;
-            new_pipe.add(Arc::from(p));
+            new_pipe.add(Arc::new(ErrorContextProcessor::create(
+                self.ctx.clone(),
+                Arc::from(p),
+            )));
         }
         self.pipes.push(new_pipe);
         Ok(())
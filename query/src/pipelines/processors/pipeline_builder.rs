@@ -191,16 +191,18 @@ impl PipelineBuilder {
             })?;
         } else {
             let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+            let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByFinalTransform::create(
                     node.schema(),
                     max_block_size,
+                    max_threads,
                     node.schema_before_group_by.clone(),
                     node.aggr_expr.clone(),
                     node.group_expr.clone(),
                 )))
             })?;
-            pipeline.mixed_processor(self.ctx.get_settings().get_max_threads()? as usize)?;
+            pipeline.mixed_processor(max_threads)?;
         }
         Ok(pipeline)
     }
@@ -0,0 +1,90 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::Processor;
+use crate::sessions::DatabendQueryContextRef;
+
+/// Appends the processor name and the query id to the errors of the inner processor,
+/// so a failure deep inside a pipeline tells which processor raised it.
+///
+/// The error code is kept unchanged. Errors which already carry a context, i.e. raised
+/// by an upstream processor and passed through the inner one, are returned as they are.
+pub struct ErrorContextProcessor {
+    query_id: String,
+    inner: Arc<dyn Processor>,
+}
+
+impl ErrorContextProcessor {
+    pub fn create(ctx: DatabendQueryContextRef, inner: Arc<dyn Processor>) -> Self {
+        ErrorContextProcessor {
+            query_id: ctx.get_id(),
+            inner,
+        }
+    }
+
+    pub fn with_context(error: ErrorCode, processor: &str, query_id: &str) -> ErrorCode {
+        if error.context().is_some() {
+            return error;
+        }
+
+        let context = format!("{}, query {}", processor, query_id);
+        error
+            .add_message_back(format!(" (while in {})", context))
+            .set_context(context)
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ErrorContextProcessor {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::IllegalTransformConnectionState(
+            "Cannot call ErrorContextProcessor connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        self.inner.inputs()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.inner.as_any()
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let name = self.inner.name().to_string();
+        let query_id = self.query_id.clone();
+
+        let stream = self
+            .inner
+            .execute()
+            .await
+            .map_err(|error| Self::with_context(error, &name, &query_id))?;
+
+        Ok(Box::pin(stream.map(move |item| {
+            item.map_err(|error| Self::with_context(error, &name, &query_id))
+        })))
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+
+/// Emits one block, then fails in `execute` or in the stream depending on `fail_on_execute`.
+struct FailingSource {
+    fail_on_execute: bool,
+}
+
+#[async_trait::async_trait]
+impl Processor for FailingSource {
+    fn name(&self) -> &str {
+        "FailingSource"
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Err(ErrorCode::IllegalTransformConnectionState(""))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![Arc::new(EmptyProcessor::create())]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        if self.fail_on_execute {
+            return Err(ErrorCode::BadArguments("source execute failed"));
+        }
+
+        let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+        let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1i64])]);
+        Ok(Box::pin(stream::iter(vec![
+            Ok(block),
+            Err(ErrorCode::BadDataValueType("source stream failed")),
+        ])))
+    }
+}
+
+/// Passes the input through, or fails on every block if `fail` is set.
+struct SimpleTransform {
+    fail: bool,
+    input: Arc<dyn Processor>,
+}
+
+#[async_trait::async_trait]
+impl Processor for SimpleTransform {
+    fn name(&self) -> &str {
+        "SimpleTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let fail = self.fail;
+        let input = self.input.execute().await?;
+        Ok(Box::pin(input.map(move |block| match fail {
+            true => Err(ErrorCode::BadDataValueType("transform failed")),
+            false => block,
+        })))
+    }
+}
+
+async fn execute_pipeline(source_fail_on_execute: bool, transform_fail: bool) -> ErrorCode {
+    let ctx = crate::tests::try_create_context().unwrap();
+    let mut pipeline = Pipeline::create(ctx.clone());
+    pipeline
+        .add_source(Arc::new(FailingSource {
+            fail_on_execute: source_fail_on_execute,
+        }))
+        .unwrap();
+    pipeline
+        .add_simple_transform(|| {
+            Ok(Box::new(SimpleTransform {
+                fail: transform_fail,
+                input: Arc::new(EmptyProcessor::create()),
+            }))
+        })
+        .unwrap();
+
+    let query_id = ctx.get_id();
+    let result = match pipeline.execute().await {
+        Ok(stream) => stream.try_collect::<Vec<_>>().await,
+        Err(error) => Err(error),
+    };
+
+    let error = result.unwrap_err();
+    assert!(error.message().ends_with(&format!(", query {})", query_id)));
+    error
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processor_error_context() -> Result<()> {
+    // Fails in the source execute.
+    let error = execute_pipeline(true, false).await;
+    assert_eq!(error.code(), ErrorCode::BadArguments("").code());
+    assert!(error
+        .message()
+        .starts_with("source execute failed (while in FailingSource, query "));
+
+    // Fails in the source stream, the transform does not wrap it again.
+    let error = execute_pipeline(false, false).await;
+    assert_eq!(error.code(), ErrorCode::BadDataValueType("").code());
+    assert!(error
+        .message()
+        .starts_with("source stream failed (while in FailingSource, query "));
+    assert_eq!(error.message().matches("(while in ").count(), 1);
+    assert!(error
+        .context()
+        .unwrap()
+        .starts_with("FailingSource, query "));
+
+    // Fails in the transform.
+    let error = execute_pipeline(false, true).await;
+    assert_eq!(error.code(), ErrorCode::BadDataValueType("").code());
+    assert!(error
+        .message()
+        .starts_with("transform failed (while in SimpleTransform, query "));
+    Ok(())
+}
+
+#[test]
+fn test_processor_error_context_message() {
+    // The context is tracked by the error, a message which only looks like it is wrapped.
+    let error = ErrorCode::BadArguments("a (while in b)");
+    let error = ErrorContextProcessor::with_context(error, "SimpleTransform", "q1");
+    assert_eq!(
+        error.message(),
+        "a (while in b) (while in SimpleTransform, query q1)"
+    );
+    assert_eq!(
+        error.context(),
+        Some("SimpleTransform, query q1".to_string())
+    );
+
+    let error = ErrorContextProcessor::with_context(error, "FilterTransform", "q1");
+    assert_eq!(
+        error.message(),
+        "a (while in b) (while in SimpleTransform, query q1)"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processor_error_context_delegate() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let processor = ErrorContextProcessor::create(
+        ctx,
+        Arc::new(FailingSource {
+            fail_on_execute: false,
+        }),
+    );
+
+    assert_eq!(processor.name(), "FailingSource");
+    assert!(processor.as_any().downcast_ref::<FailingSource>().is_some());
+    assert_eq!(processor.inputs()[0].name(), "EmptyProcessor");
+    Ok(())
+}
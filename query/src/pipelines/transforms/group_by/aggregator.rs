@@ -14,10 +14,16 @@
 
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
+use common_datablocks::HashMethodFixedKeys;
+use common_datablocks::HashMethodKeysU16;
+use common_datablocks::HashMethodKeysU32;
+use common_datablocks::HashMethodKeysU64;
+use common_datablocks::HashMethodSerializer;
 use common_datavalues::arrays::StringArrayBuilder;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::IntoSeries;
 use common_datavalues::prelude::Series;
+use common_datavalues::DFPrimitiveType;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::Result;
@@ -29,9 +35,11 @@ use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
 
 use crate::pipelines::transforms::group_by::aggregator_keys_builder::KeysArrayBuilder;
+use crate::pipelines::transforms::group_by::aggregator_keys_dictionary::DictionaryKeys;
 use crate::pipelines::transforms::group_by::aggregator_params::AggregatorParams;
 use crate::pipelines::transforms::group_by::aggregator_params::AggregatorParamsRef;
 use crate::pipelines::transforms::group_by::aggregator_state::AggregatorState;
+use crate::pipelines::transforms::group_by::aggregator_state::SerializedKeysAggregatorState;
 use crate::pipelines::transforms::group_by::aggregator_state_entity::StateEntity;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
 
@@ -51,22 +59,32 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
     pub async fn aggregate(
         &self,
         group_cols: Vec<String>,
+        stream: SendableDataBlockStream,
+    ) -> Result<Method::State> {
+        let state = self.method.aggregate_state();
+        self.aggregate_into(&group_cols, stream, state).await
+    }
+
+    /// Aggregate the stream into the `state`.
+    #[inline(never)]
+    async fn aggregate_into(
+        &self,
+        group_cols: &[String],
         mut stream: SendableDataBlockStream,
+        mut state: Method::State,
     ) -> Result<Method::State> {
         // This may be confusing
         // It will help us improve performance ~10% when we declare local references for them.
         let hash_method = &self.method;
         let aggregator_params = self.params.as_ref();
 
-        let mut state = hash_method.aggregate_state();
-
         match aggregator_params.aggregate_functions.is_empty() {
             true => {
                 while let Some(block) = stream.next().await {
                     let block = block?;
 
                     // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
+                    let group_columns = Self::group_columns(group_cols, &block)?;
                     let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
                     self.lookup_key(group_keys, &mut state);
                 }
@@ -76,7 +94,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
                     let block = block?;
 
                     // 1.1 and 1.2.
-                    let group_columns = Self::group_columns(&group_cols, &block)?;
+                    let group_columns = Self::group_columns(group_cols, &block)?;
                     let group_keys = hash_method.build_keys(&group_columns, block.num_rows())?;
 
                     let places = self.lookup_state(group_keys, &mut state);
@@ -225,3 +243,80 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method>> Aggregator<Method> {
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
     }
 }
+
+impl Aggregator<HashMethodSerializer> {
+    /// Aggregate with the string group columns encoded by the dictionary.
+    ///
+    /// The keys are the fixed keys of `dictionary.key_size()` bytes. Once a dictionary exceeds
+    /// its max entries, the keys aggregated so far are decoded and the rest of the stream falls
+    /// back to the serializer keys.
+    pub async fn aggregate_with_dictionary(
+        &self,
+        group_cols: Vec<String>,
+        stream: SendableDataBlockStream,
+        dictionary: DictionaryKeys,
+    ) -> Result<SerializedKeysAggregatorState> {
+        match dictionary.key_size() {
+            2 => {
+                let method = HashMethodKeysU16::default();
+                self.aggregate_with_dictionary_keys(method, group_cols, stream, dictionary)
+                    .await
+            }
+            4 => {
+                let method = HashMethodKeysU32::default();
+                self.aggregate_with_dictionary_keys(method, group_cols, stream, dictionary)
+                    .await
+            }
+            _ => {
+                let method = HashMethodKeysU64::default();
+                self.aggregate_with_dictionary_keys(method, group_cols, stream, dictionary)
+                    .await
+            }
+        }
+    }
+
+    #[inline(never)]
+    async fn aggregate_with_dictionary_keys<T>(
+        &self,
+        method: HashMethodFixedKeys<T>,
+        group_cols: Vec<String>,
+        mut stream: SendableDataBlockStream,
+        mut dictionary: DictionaryKeys,
+    ) -> Result<SerializedKeysAggregatorState>
+    where
+        T: DFPrimitiveType,
+        HashMethodFixedKeys<T>:
+            HashMethod<HashKey = T> + PolymorphicKeysHelper<HashMethodFixedKeys<T>>,
+    {
+        let aggregator_params = self.params.as_ref();
+        let fixed_keys = Aggregator::create(method, self.params.clone());
+        let mut state = fixed_keys.method.aggregate_state();
+
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            let group_columns = Self::group_columns(&group_cols, &block)?;
+
+            let group_keys = match dictionary.build_keys::<T>(&group_columns, block.num_rows())? {
+                Some(group_keys) => group_keys,
+                None => {
+                    let decoded = dictionary
+                        .decode_state::<HashMethodFixedKeys<T>, _>(&state, aggregator_params)?;
+                    let block: Result<DataBlock> = Ok(block);
+                    let stream =
+                        Box::pin(futures::stream::once(async move { block }).chain(stream));
+                    return self.aggregate_into(&group_cols, stream, decoded).await;
+                }
+            };
+
+            match aggregator_params.aggregate_functions.is_empty() {
+                true => fixed_keys.lookup_key(group_keys, &mut state),
+                false => {
+                    let places = fixed_keys.lookup_state(group_keys, &mut state);
+                    Self::execute(aggregator_params, &block, &places)?;
+                }
+            }
+        }
+
+        dictionary.decode_state::<HashMethodFixedKeys<T>, _>(&state, aggregator_params)
+    }
+}
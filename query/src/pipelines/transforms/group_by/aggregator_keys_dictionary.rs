@@ -0,0 +1,260 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use common_datablocks::HashMethod;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DFPrimitiveType;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::BinaryWrite;
+
+use crate::common::HashTable;
+use crate::pipelines::transforms::group_by::aggregator_state::SerializedKeysAggregatorState;
+use crate::pipelines::transforms::group_by::aggregator_state_entity::StateEntity;
+use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::AggregatorState;
+
+/// Max number of distinct values of a string group column before falling back to the serializer.
+///
+/// The ids of the dictionary are u16.
+pub const GROUP_BY_DICTIONARY_MAX_ENTRIES: usize = u16::MAX as usize;
+
+/// Max bytes of the fixed key, i.e. the u64 keys.
+const MAX_KEY_SIZE: usize = 8;
+
+enum KeysColumn {
+    /// A string column, contributes its 2 bytes dictionary id to the key.
+    Dictionary {
+        ids: HashMap<Vec<u8>, u16, ahash::RandomState>,
+        // The serialized (HashMethodSerializer) bytes of each id.
+        values: Vec<Vec<u8>>,
+    },
+    /// An integer column, contributes its bytes to the key as the serializer does.
+    Fixed(usize),
+}
+
+/// Group keys of the serializer method where the string columns are replaced by dictionary ids.
+///
+/// Grouping by low-cardinality string columns pays the string serializing and copying per row,
+/// the dictionary makes every string column a 2 bytes id instead, and the ids and the integer
+/// columns are packed into a u16/u32/u64 key, so the group by runs with the fixed keys states.
+/// The ids are local to one aggregator: `decode_state` rewrites the keys to the serializer's
+/// format before the state is finalized, so the partial results are the same as those of the
+/// serializer.
+///
+/// Only the group by of string and integer columns (with at least one string), which fits in
+/// 8 bytes, is encoded.
+pub struct DictionaryKeys {
+    max_entries: usize,
+    key_size: usize,
+    columns: Vec<KeysColumn>,
+}
+
+impl DictionaryKeys {
+    pub fn try_create(
+        schema: &DataSchemaRef,
+        group_cols: &[String],
+        max_entries: usize,
+    ) -> Result<Option<DictionaryKeys>> {
+        let mut has_string = false;
+        let mut columns = Vec::with_capacity(group_cols.len());
+        for group_col in group_cols {
+            let data_type = schema.field_with_name(group_col)?.data_type();
+            match data_type {
+                DataType::String => {
+                    has_string = true;
+                    columns.push(KeysColumn::Dictionary {
+                        ids: HashMap::with_hasher(ahash::RandomState::new()),
+                        values: vec![],
+                    });
+                }
+                _ if common_datavalues::is_integer(data_type) => {
+                    let size = common_datavalues::numeric_byte_size(data_type)?;
+                    columns.push(KeysColumn::Fixed(size));
+                }
+                _ => return Ok(None),
+            }
+        }
+
+        let key_size = columns
+            .iter()
+            .map(|column| match column {
+                KeysColumn::Dictionary { .. } => 2,
+                KeysColumn::Fixed(size) => *size,
+            })
+            .sum::<usize>();
+
+        match has_string && key_size <= MAX_KEY_SIZE {
+            true => Ok(Some(DictionaryKeys {
+                max_entries: std::cmp::min(max_entries, GROUP_BY_DICTIONARY_MAX_ENTRIES),
+                key_size: key_size.next_power_of_two(),
+                columns,
+            })),
+            false => Ok(None),
+        }
+    }
+
+    /// The bytes of the fixed keys, 2, 4 or 8.
+    pub fn key_size(&self) -> usize {
+        self.key_size
+    }
+
+    /// Build the keys of a block, returns None once a dictionary exceeds the max entries.
+    ///
+    /// The size of `T` must be the `key_size`.
+    pub fn build_keys<T: DFPrimitiveType>(
+        &mut self,
+        group_columns: &[&DataColumn],
+        rows: usize,
+    ) -> Result<Option<Vec<T>>> {
+        let step = std::mem::size_of::<T>();
+        if step != self.key_size {
+            return Err(ErrorCode::LogicalError(format!(
+                "Dictionary keys of {} bytes cannot be built as {} bytes",
+                self.key_size, step
+            )));
+        }
+
+        let mut group_keys: Vec<T> = vec![T::default(); rows];
+        let ptr = group_keys.as_mut_ptr() as *mut u8;
+
+        let mut offset = 0;
+        for (column, group_column) in self.columns.iter_mut().zip(group_columns.iter()) {
+            match column {
+                KeysColumn::Fixed(size) => {
+                    let series = group_column.to_array()?;
+                    series.fixed_hash(unsafe { ptr.add(offset) }, step)?;
+                    offset += *size;
+                }
+                KeysColumn::Dictionary { ids, values } => {
+                    let series = group_column.to_array()?;
+                    let array = series.string()?;
+                    for (row, value) in array.into_no_null_iter().enumerate() {
+                        let id = match ids.get(value) {
+                            Some(id) => *id,
+                            None => {
+                                if values.len() >= self.max_entries {
+                                    return Ok(None);
+                                }
+
+                                let id = values.len() as u16;
+                                let mut serialized = Vec::with_capacity(value.len() + 1);
+                                serialized.write_binary(value)?;
+                                values.push(serialized);
+                                ids.insert(value.to_vec(), id);
+                                id
+                            }
+                        };
+
+                        // The keys are all rows * step bytes, the id is in the row's key.
+                        unsafe {
+                            std::ptr::copy_nonoverlapping(
+                                id.to_le_bytes().as_ptr(),
+                                ptr.add(row * step + offset),
+                                2,
+                            );
+                        }
+                    }
+                    offset += 2;
+                }
+            }
+        }
+
+        Ok(Some(group_keys))
+    }
+
+    /// Rewrite a key built by `build_keys` into the key built by the serializer.
+    pub fn decode_key(&self, key: &[u8], decoded: &mut Vec<u8>) -> Result<()> {
+        let mut offset = 0;
+        for column in &self.columns {
+            match column {
+                KeysColumn::Fixed(size) => {
+                    let bytes = key.get(offset..offset + size).ok_or_else(Self::bad_key)?;
+                    decoded.extend_from_slice(bytes);
+                    offset += size;
+                }
+                KeysColumn::Dictionary { values, .. } => {
+                    let bytes = key.get(offset..offset + 2).ok_or_else(Self::bad_key)?;
+                    let id = u16::from_le_bytes([bytes[0], bytes[1]]);
+                    let value = values.get(id as usize).ok_or_else(Self::bad_key)?;
+                    decoded.extend_from_slice(value);
+                    offset += 2;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrite all the keys of the fixed keys state into a serializer's state.
+    ///
+    /// The aggregate function states are copied into the new state, the fixed keys state
+    /// is left with the keys only and must not be finalized.
+    pub fn decode_state<Method, State>(
+        &self,
+        state: &State,
+        params: &AggregatorParams,
+    ) -> Result<SerializedKeysAggregatorState>
+    where
+        Method: HashMethod,
+        State: AggregatorState<Method>,
+    {
+        let mut decoded_state = SerializedKeysAggregatorState {
+            keys_area: Bump::new(),
+            state_area: Bump::new(),
+            data_state_map: HashTable::create(),
+        };
+
+        let has_states = !params.aggregate_functions.is_empty();
+        let mut decoded_key = Vec::new();
+        let mut inserted = true;
+        for entity in state.iter() {
+            let key = entity.get_state_key();
+            let key = unsafe {
+                std::slice::from_raw_parts(key as *const _ as *const u8, std::mem::size_of_val(key))
+            };
+
+            decoded_key.clear();
+            self.decode_key(key, &mut decoded_key)?;
+
+            let decoded_entity = decoded_state.entity(&decoded_key, &mut inserted);
+            match has_states {
+                false => decoded_entity.set_state_value(*entity.get_state_value()),
+                true => {
+                    // The states are moved bytewise, the area of the fixed keys state never
+                    // drops them.
+                    let place = decoded_state.state_area.alloc_layout(params.layout);
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(
+                            *entity.get_state_value() as *const u8,
+                            place.as_ptr(),
+                            params.layout.size(),
+                        );
+                    }
+                    decoded_entity.set_state_value(place.as_ptr() as usize);
+                }
+            }
+        }
+
+        Ok(decoded_state)
+    }
+
+    fn bad_key() -> ErrorCode {
+        ErrorCode::LogicalError("Cannot decode the dictionary encoded group by key")
+    }
+}
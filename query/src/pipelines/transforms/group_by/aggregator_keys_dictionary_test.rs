@@ -0,0 +1,192 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::sum;
+use common_streams::DataBlockStream;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::transforms::group_by::Aggregator;
+use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::DictionaryKeys;
+
+fn test_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![
+        DataField::new("s", DataType::String, false),
+        DataField::new("n", DataType::UInt16, false),
+    ])
+}
+
+fn test_block(strings: Vec<&str>, numbers: Vec<u16>) -> DataBlock {
+    DataBlock::create_by_array(test_schema(), vec![
+        Series::new(strings),
+        Series::new(numbers),
+    ])
+}
+
+fn group_cols() -> Vec<String> {
+    vec!["s".to_string(), "n".to_string()]
+}
+
+#[test]
+fn test_dictionary_keys_decode() -> Result<()> {
+    let block = test_block(vec!["cn", "us", "cn", "", "us"], vec![1, 2, 1, 3, 300]);
+    let columns = vec![
+        block.try_column_by_name("s")?,
+        block.try_column_by_name("n")?,
+    ];
+
+    let mut dictionary = DictionaryKeys::try_create(&test_schema(), &group_cols(), 16)?.unwrap();
+    // 2 bytes id + 2 bytes u16.
+    assert_eq!(dictionary.key_size(), 4);
+    assert!(dictionary.build_keys::<u64>(&columns, 5).is_err());
+
+    let keys = dictionary.build_keys::<u32>(&columns, 5)?.unwrap();
+    assert_eq!(keys[0], keys[2]);
+    assert_ne!(keys[1], keys[4]);
+
+    let expect = HashMethodSerializer::default().build_keys(&columns, block.num_rows())?;
+    for (key, expect) in keys.iter().zip(expect.iter()) {
+        let mut decoded = vec![];
+        dictionary.decode_key(&key.to_le_bytes(), &mut decoded)?;
+        assert_eq!(&decoded, expect);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_dictionary_keys_eligible() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("s", DataType::String, false),
+        DataField::new("i", DataType::Int64, false),
+        DataField::new("f", DataType::Float64, false),
+        DataField::new("j", DataType::Int32, false),
+    ]);
+
+    let only_integer = vec!["i".to_string()];
+    assert!(DictionaryKeys::try_create(&schema, &only_integer, 16)?.is_none());
+
+    let with_float = vec!["s".to_string(), "f".to_string()];
+    assert!(DictionaryKeys::try_create(&schema, &with_float, 16)?.is_none());
+
+    // 2 + 8 bytes do not fit in the u64 keys.
+    let too_wide = vec!["s".to_string(), "i".to_string()];
+    assert!(DictionaryKeys::try_create(&schema, &too_wide, 16)?.is_none());
+
+    let string = vec!["s".to_string()];
+    let dictionary = DictionaryKeys::try_create(&schema, &string, 16)?.unwrap();
+    assert_eq!(dictionary.key_size(), 2);
+
+    let string_and_integer = vec!["s".to_string(), "j".to_string(), "s".to_string()];
+    let dictionary = DictionaryKeys::try_create(&schema, &string_and_integer, 16)?.unwrap();
+    assert_eq!(dictionary.key_size(), 8);
+    Ok(())
+}
+
+#[test]
+fn test_dictionary_keys_overflow() -> Result<()> {
+    let mut dictionary = DictionaryKeys::try_create(&test_schema(), &group_cols(), 2)?.unwrap();
+
+    let block = test_block(vec!["a", "b", "a"], vec![1, 2, 3]);
+    let columns = vec![
+        block.try_column_by_name("s")?,
+        block.try_column_by_name("n")?,
+    ];
+    assert!(dictionary.build_keys::<u32>(&columns, 3)?.is_some());
+
+    let block = test_block(vec!["a", "c"], vec![1, 2]);
+    let columns = vec![
+        block.try_column_by_name("s")?,
+        block.try_column_by_name("n")?,
+    ];
+    assert!(dictionary.build_keys::<u32>(&columns, 2)?.is_none());
+    Ok(())
+}
+
+fn sorted_rows(blocks: &[DataBlock]) -> Result<Vec<String>> {
+    let mut rows = vec![];
+    for block in blocks {
+        for row in 0..block.num_rows() {
+            let values = block
+                .columns()
+                .iter()
+                .map(|column| column.try_get(row))
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(format!("{:?}", values));
+        }
+    }
+    rows.sort();
+    Ok(rows)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_aggregate_with_dictionary() -> Result<()> {
+    let schema = test_schema();
+    let partial_schema = DataSchemaRefExt::create(vec![
+        DataField::new("sum(n)", DataType::String, false),
+        DataField::new("_group_by_key", DataType::String, false),
+    ]);
+
+    // The first block fits in the dictionary, the second one overflows it.
+    let blocks = vec![
+        test_block(vec!["a", "b", "a", "b"], vec![1, 1, 1, 2]),
+        test_block(vec!["c", "d", "e", "a"], vec![1, 1, 1, 1]),
+        test_block(vec!["e", "f", "b", "b"], vec![1, 2, 2, 2]),
+    ];
+
+    // The u32 keys of (s, n) and the u16 keys of s.
+    let cases = [(group_cols(), 7), (vec!["s".to_string()], 6)];
+    for ((cols, groups), max_entries) in cases.iter().flat_map(|c| [(c, 2), (c, 8)]) {
+        let params = AggregatorParams::try_create(schema.clone(), &[sum(col("n"))])?;
+
+        let plain = Aggregator::create(HashMethodSerializer::default(), params.clone());
+        let stream = Box::pin(DataBlockStream::create(
+            schema.clone(),
+            None,
+            blocks.clone(),
+        ));
+        let state = plain.aggregate(cols.clone(), stream).await?;
+        let expect = plain
+            .aggregate_finalized(&state, partial_schema.clone())?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let dictionary = DictionaryKeys::try_create(&schema, cols, max_entries)?.unwrap();
+        let aggregator = Aggregator::create(HashMethodSerializer::default(), params);
+        let stream = Box::pin(DataBlockStream::create(
+            schema.clone(),
+            None,
+            blocks.clone(),
+        ));
+        let state = aggregator
+            .aggregate_with_dictionary(cols.clone(), stream, dictionary)
+            .await?;
+        let actual = aggregator
+            .aggregate_finalized(&state, partial_schema.clone())?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        assert_eq!(sorted_rows(&actual)?, sorted_rows(&expect)?);
+        assert_eq!(sorted_rows(&actual)?.len(), *groups);
+    }
+
+    Ok(())
+}
@@ -14,6 +14,7 @@
 
 mod aggregator;
 mod aggregator_keys_builder;
+mod aggregator_keys_dictionary;
 mod aggregator_params;
 mod aggregator_polymorphic_keys;
 mod aggregator_state;
@@ -22,7 +23,12 @@ mod aggregator_state_iterator;
 mod keys_ref;
 
 pub use aggregator::Aggregator;
+pub use aggregator_keys_dictionary::DictionaryKeys;
+pub use aggregator_keys_dictionary::GROUP_BY_DICTIONARY_MAX_ENTRIES;
 pub use aggregator_params::AggregatorParams;
 pub use aggregator_params::AggregatorParamsRef;
 pub use aggregator_polymorphic_keys::PolymorphicKeysHelper;
 pub use aggregator_state::AggregatorState;
+
+#[cfg(test)]
+mod aggregator_keys_dictionary_test;
@@ -13,29 +13,32 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
 use bumpalo::Bump;
+use common_base::tokio::sync::mpsc;
+use common_base::tokio::task;
 use common_datablocks::DataBlock;
+use common_datablocks::GroupHashTable;
 use common_datablocks::HashMethodKind;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::get_layout_offsets;
 use common_functions::aggregates::StateAddr;
-use common_infallible::RwLock;
 use common_planners::Expression;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
-use futures::stream::StreamExt;
+use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 
 pub struct GroupByFinalTransform {
     max_block_size: usize,
+    max_threads: usize,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
     schema: DataSchemaRef,
@@ -47,12 +50,14 @@ impl GroupByFinalTransform {
     pub fn create(
         schema: DataSchemaRef,
         max_block_size: usize,
+        max_threads: usize,
         schema_before_group_by: DataSchemaRef,
         aggr_exprs: Vec<Expression>,
         group_exprs: Vec<Expression>,
     ) -> Self {
         Self {
             max_block_size,
+            max_threads,
             aggr_exprs,
             group_exprs,
             schema,
@@ -105,79 +110,130 @@ impl Processor for GroupByFinalTransform {
             .collect::<Result<Vec<_>>>()?;
 
         let start = Instant::now();
-        let arena = Bump::new();
 
         let mut stream = self.input.execute().await?;
         let sample_block = DataBlock::empty_with_schema(self.schema_before_group_by.clone());
         let method = DataBlock::choose_hash_method(&sample_block, &group_cols)?;
 
         let (layout, offsets_aggregate_states) = unsafe { get_layout_offsets(&funcs) };
+        let threads = std::cmp::max(1, self.max_threads);
 
         macro_rules! apply {
-            ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $group_func_table: ty) => {{
-                type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
-
-                while let Some(block) = stream.next().await {
-                    let mut groups = groups_locker.write();
-                    let block = block?;
+            ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $key_type: ty) => {{
+                // Each thread merges the blocks it receives into its own table and arena, the
+                // blocks are dispatched to the threads as they arrive, the tables are merged
+                // bucket by bucket at last.
+                let hash_method = Arc::new($hash_method);
+                let mut senders = Vec::with_capacity(threads);
+                let mut workers = Vec::with_capacity(threads);
+                for _ in 0..threads {
+                    let (tx, mut rx) = mpsc::channel::<DataBlock>(1);
+                    let funcs = funcs.clone();
+                    let offsets_aggregate_states = offsets_aggregate_states.clone();
+                    let hash_method = hash_method.clone();
+                    workers.push(task::spawn_blocking(move || -> Result<(Bump, GroupHashTable<$key_type, usize>)> {
+                        let arena = Bump::new();
+                        let mut groups = GroupHashTable::create();
+
+                        while let Some(block) = rx.blocking_recv() {
+                            let key_array = block.column(aggr_funcs_len).to_array()?;
+                            let key_array: $key_array_type = key_array.$downcast_fn()?;
+
+                            let states_series = (0..aggr_funcs_len)
+                                .map(|i| block.column(i).to_array())
+                                .collect::<Result<Vec<_>>>()?;
+                            let mut states_binary_arrays = Vec::with_capacity(states_series.len());
+
+                            for agg in states_series.iter().take(aggr_funcs_len) {
+                                let aggr_array: &DFStringArray = agg.string()?;
+                                let aggr_array = aggr_array.inner();
+                                states_binary_arrays.push(aggr_array);
+                            }
 
-                    let key_array = block.column(aggr_funcs_len).to_array()?;
-                    let key_array: $key_array_type = key_array.$downcast_fn()?;
+                            for row in 0..block.num_rows() {
+                                let group_key = hash_method.get_key(&key_array, row);
+                                match groups.get_mut(&group_key) {
+                                    None => {
+                                        if aggr_funcs_len == 0 {
+                                            groups.insert(group_key, 0usize);
+                                        } else {
+                                            let place: StateAddr = arena.alloc_layout(layout).into();
+                                            for (idx, func) in funcs.iter().enumerate() {
+                                                let arg_place = place.next(offsets_aggregate_states[idx]);
+
+                                                let mut data = states_binary_arrays[idx].value(row);
+                                                func.init_state(arg_place);
+                                                func.deserialize(arg_place, &mut data)?;
+                                            }
+                                            groups.insert(group_key, place.addr());
+                                        }
+                                    }
+                                    Some(place) => {
+                                        let place: StateAddr = (*place).into();
 
-                    let states_series = (0..aggr_funcs_len)
-                        .map(|i| block.column(i).to_array())
-                        .collect::<Result<Vec<_>>>()?;
-                    let mut states_binary_arrays = Vec::with_capacity(states_series.len());
+                                        for (idx, func) in funcs.iter().enumerate() {
+                                            let arg_place = place.next(offsets_aggregate_states[idx]);
 
-                    for agg in states_series.iter().take(aggr_funcs_len) {
-                        let aggr_array: &DFStringArray = agg.string()?;
-                        let aggr_array = aggr_array.inner();
-                        states_binary_arrays.push(aggr_array);
-                    }
+                                            let mut data = states_binary_arrays[idx].value(row);
+                                            let temp = arena.alloc_layout(funcs[idx].state_layout());
+                                            let temp_addr = temp.into();
 
-                    for row in 0..block.num_rows() {
-                        let group_key = $hash_method.get_key(&key_array, row);
-                        match groups.get(&group_key) {
-                            None => {
-                                if aggr_funcs_len == 0 {
-                                    groups.insert(group_key, 0usize);
-                                } else {
-                                    let place: StateAddr = arena.alloc_layout(layout).into();
-                                    for (idx, func) in funcs.iter().enumerate() {
-                                        let arg_place = place.next(offsets_aggregate_states[idx]);
-
-                                        let mut data = states_binary_arrays[idx].value(row);
-                                        func.init_state(arg_place);
-                                        func.deserialize(arg_place, &mut data)?;
+                                            funcs[idx].init_state(temp_addr);
+                                            func.deserialize(temp_addr, &mut data)?;
+                                            func.merge(arg_place, temp_addr)?;
+                                        }
                                     }
-                                    groups.insert(group_key, place.addr());
-                                }
+                                };
                             }
-                            Some(place) => {
-                                let place: StateAddr = (*place).into();
-
-                                for (idx, func) in funcs.iter().enumerate() {
-                                    let arg_place = place.next(offsets_aggregate_states[idx]);
-
-                                    let mut data = states_binary_arrays[idx].value(row);
-                                    let temp = arena.alloc_layout(funcs[idx].state_layout());
-                                    let temp_addr = temp.into();
+                        }
+                        Ok((arena, groups))
+                    }));
+                    senders.push(tx);
+                }
 
-                                    funcs[idx].init_state(temp_addr);
-                                    func.deserialize(temp_addr, &mut data)?;
-                                    func.merge(arg_place, temp_addr)?;
-                                }
-                            }
-                        };
+                let mut next = 0;
+                while let Some(block) = stream.next().await {
+                    // A closed channel means the thread has failed, its error is returned below.
+                    if senders[next].send(block?).await.is_err() {
+                        break;
+                    }
+                    next = (next + 1) % threads;
+                }
+                drop(senders);
+
+                let mut partials = Vec::with_capacity(threads);
+                for worker in workers {
+                    match worker.await {
+                        Ok(partial) => partials.push(partial?),
+                        Err(cause) => {
+                            return Err(ErrorCode::LogicalError(format!(
+                                "Group by final thread panicked: {:?}",
+                                cause
+                            )))
+                        }
                     }
                 }
+
+                // The states of the groups live in the arenas, which are kept until the results are collected.
+                let (_arenas, partials): (Vec<Bump>, Vec<_>) = partials.into_iter().unzip();
+                let groups = {
+                    let funcs = funcs.clone();
+                    let offsets_aggregate_states = offsets_aggregate_states.clone();
+                    GroupHashTable::merge_parallel(partials, threads, move |place, other| {
+                        let place: StateAddr = (*place).into();
+                        let other: StateAddr = other.into();
+                        for (idx, func) in funcs.iter().enumerate() {
+                            let offset = offsets_aggregate_states[idx];
+                            func.merge(place.next(offset), other.next(offset))?;
+                        }
+                        Ok(())
+                    })?
+                };
+
                 let delta = start.elapsed();
                 tracing::debug!("Group by final cost: {:?}", delta);
 
                 // Collect the merge states.
-                let groups = groups_locker.read();
-
                 let mut aggr_values: Vec<Vec<DataValue>> = {
                     let mut values = vec![];
                     for _i in 0..aggr_funcs_len {
@@ -186,10 +242,10 @@ impl Processor for GroupByFinalTransform {
                     values
                 };
                 let mut keys = Vec::with_capacity(groups.len());
-                for (key, place) in groups.iter() {
-                    keys.push(key.clone());
+                for (key, place) in groups {
+                    keys.push(key);
 
-                    let place: StateAddr = (*place).into();
+                    let place: StateAddr = place.into();
                     for (idx, func) in funcs.iter().enumerate() {
                         let arg_place = place.next(offsets_aggregate_states[idx]);
                         let merge = func.merge_result(arg_place)?;
@@ -208,7 +264,7 @@ impl Processor for GroupByFinalTransform {
                 }
 
                 {
-                    let group_columns = $hash_method.de_group_columns(keys, &group_fields)?;
+                    let group_columns = hash_method.de_group_columns(keys, &group_fields)?;
                     columns.extend_from_slice(&group_columns);
                 }
 
@@ -230,19 +286,19 @@ impl Processor for GroupByFinalTransform {
             ($method: ident, $apply: ident) => {{
                 match $method {
                     HashMethodKind::Serializer(hash_method) => {
-                        apply! { hash_method,  &DFStringArray, string, RwLock<HashMap<Vec<u8>, usize, ahash::RandomState>>}
+                        apply! { hash_method,  &DFStringArray, string, Vec<u8>}
                     }
                     HashMethodKind::KeysU8(hash_method) => {
-                        apply! { hash_method , &DFUInt8Array, u8, RwLock<HashMap<u8, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt8Array, u8, u8 }
                     }
                     HashMethodKind::KeysU16(hash_method) => {
-                        apply! { hash_method , &DFUInt16Array, u16, RwLock<HashMap<u16, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt16Array, u16, u16 }
                     }
                     HashMethodKind::KeysU32(hash_method) => {
-                        apply! { hash_method , &DFUInt32Array, u32, RwLock<HashMap<u32, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt32Array, u32, u32 }
                     }
                     HashMethodKind::KeysU64(hash_method) => {
-                        apply! { hash_method , &DFUInt64Array, u64, RwLock<HashMap<u64, usize, ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt64Array, u64, u64 }
                     }
                 }
             }};
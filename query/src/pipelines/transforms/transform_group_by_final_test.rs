@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::sync::Arc;
 
 use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use common_planners::{self};
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
@@ -60,10 +66,12 @@ async fn test_transform_final_group_by() -> Result<()> {
     pipeline.merge_processor()?;
 
     let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+    let max_threads = ctx.get_settings().get_max_threads()? as usize;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByFinalTransform::create(
             aggr_final.schema(),
             max_block_size,
+            max_threads,
             source_schema.clone(),
             aggr_exprs.to_vec(),
             group_exprs.to_vec(),
@@ -92,3 +100,102 @@ async fn test_transform_final_group_by() -> Result<()> {
 
     Ok(())
 }
+
+struct PartialBlocks {
+    schema: DataSchemaRef,
+    blocks: Vec<DataBlock>,
+}
+
+#[async_trait::async_trait]
+impl Processor for PartialBlocks {
+    fn name(&self) -> &str {
+        "PartialBlocks"
+    }
+
+    fn connect_to(&mut self, _: Arc<dyn Processor>) -> Result<()> {
+        Result::Err(ErrorCode::IllegalTransformConnectionState(
+            "Cannot call PartialBlocks connect_to",
+        ))
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            self.blocks.clone(),
+        )))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_final_group_by_parallel_merge() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let source_schema = test_source.number_schema_for_test()?;
+
+    // sum(number), avg(number)
+    let aggr_exprs = &[sum(col("number")), avg(col("number"))];
+
+    let group_exprs = &[col("number")];
+    let aggr_partial = PlanBuilder::create(source_schema.clone())
+        .aggregate_partial(aggr_exprs, group_exprs)?
+        .build()?;
+
+    let aggr_final = PlanBuilder::create(source_schema.clone())
+        .aggregate_final(source_schema.clone(), aggr_exprs, group_exprs)?
+        .build()?;
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(5)?;
+    pipeline.add_source(Arc::new(source))?;
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(GroupByPartialTransform::create(
+            aggr_partial.schema(),
+            source_schema.clone(),
+            aggr_exprs.to_vec(),
+            group_exprs.to_vec(),
+        )))
+    })?;
+    pipeline.merge_processor()?;
+    let partial = pipeline.execute().await?.try_collect::<Vec<_>>().await?;
+
+    // Four copies of the partial states over three threads, the same groups are merged
+    // both in one thread and across the threads.
+    let blocks = (0..4).flat_map(|_| partial.clone()).collect::<Vec<_>>();
+    let mut transform = GroupByFinalTransform::create(
+        aggr_final.schema(),
+        ctx.get_settings().get_max_block_size()? as usize,
+        3,
+        source_schema.clone(),
+        aggr_exprs.to_vec(),
+        group_exprs.to_vec(),
+    );
+    transform.connect_to(Arc::new(PartialBlocks {
+        schema: aggr_partial.schema(),
+        blocks,
+    }))?;
+
+    let result = transform.execute().await?.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-------------+-------------+--------+",
+        "| sum(number) | avg(number) | number |",
+        "+-------------+-------------+--------+",
+        "| 0           | 0           | 0      |",
+        "| 4           | 1           | 1      |",
+        "| 8           | 2           | 2      |",
+        "| 12          | 3           | 3      |",
+        "| 16          | 4           | 4      |",
+        "+-------------+-------------+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -19,6 +19,7 @@ use std::time::Instant;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
 use common_datablocks::HashMethodKind;
+use common_datablocks::HashMethodSerializer;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::Expression;
@@ -29,7 +30,9 @@ use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::group_by::Aggregator;
 use crate::pipelines::transforms::group_by::AggregatorParams;
+use crate::pipelines::transforms::group_by::DictionaryKeys;
 use crate::pipelines::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::transforms::group_by::GROUP_BY_DICTIONARY_MAX_ENTRIES;
 
 pub struct GroupByPartialTransform {
     aggr_exprs: Vec<Expression>,
@@ -85,6 +88,37 @@ impl GroupByPartialTransform {
         let finalized_schema = self.schema.clone();
         aggregator.aggregate_finalized(&state, finalized_schema)
     }
+
+    async fn aggregate_serializer(
+        &self,
+        method: HashMethodSerializer,
+        group_cols: Vec<String>,
+    ) -> Result<SendableDataBlockStream> {
+        let schema = self.schema_before_group_by.clone();
+        let dictionary =
+            DictionaryKeys::try_create(&schema, &group_cols, GROUP_BY_DICTIONARY_MAX_ENTRIES)?;
+
+        let dictionary = match dictionary {
+            None => return self.aggregate(method, group_cols).await,
+            Some(dictionary) => dictionary,
+        };
+
+        let start = Instant::now();
+
+        let stream = self.input.execute().await?;
+        let aggregator_params = AggregatorParams::try_create(schema, &self.aggr_exprs)?;
+
+        let aggregator = Aggregator::create(method, aggregator_params);
+        let state = aggregator
+            .aggregate_with_dictionary(group_cols, stream, dictionary)
+            .await?;
+
+        let delta = start.elapsed();
+        tracing::debug!("Group by partial with dictionary cost: {:?}", delta);
+
+        let finalized_schema = self.schema.clone();
+        aggregator.aggregate_finalized(&state, finalized_schema)
+    }
 }
 
 #[async_trait::async_trait]
@@ -136,7 +170,9 @@ impl Processor for GroupByPartialTransform {
             HashMethodKind::KeysU16(method) => self.aggregate(method, group_cols).await,
             HashMethodKind::KeysU32(method) => self.aggregate(method, group_cols).await,
             HashMethodKind::KeysU64(method) => self.aggregate(method, group_cols).await,
-            HashMethodKind::Serializer(method) => self.aggregate(method, group_cols).await,
+            HashMethodKind::Serializer(method) => {
+                self.aggregate_serializer(method, group_cols).await
+            }
         }
     }
 }
@@ -30,6 +30,7 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planners::InsertIntoPlan;
 use common_planners::PlanNode;
+use common_streams::LimitBytesStream;
 use futures::channel::mpsc;
 use futures::channel::mpsc::Receiver;
 use futures::SinkExt;
@@ -79,6 +80,12 @@ impl InteractiveWorkerBase {
                     start.elapsed(),
                     "interpreter" => name
                 );
+
+                let max_result_size = ctx.get_settings().get_max_result_size()? as usize;
+                if max_result_size > 0 {
+                    data_stream = Box::pin(LimitBytesStream::new(data_stream, max_result_size));
+                }
+
                 let mut interval_stream = IntervalStream::new(interval(Duration::from_millis(30)));
                 let cancel = Arc::new(AtomicBool::new(false));
 
@@ -152,7 +159,7 @@ pub struct FromClickHouseBlockStream {
 }
 
 impl futures::stream::Stream for FromClickHouseBlockStream {
-    type Item = DataBlock;
+    type Item = Result<DataBlock>;
 
     fn poll_next(
         mut self: std::pin::Pin<&mut Self>,
@@ -162,7 +169,7 @@ impl futures::stream::Stream for FromClickHouseBlockStream {
             Some(v) => {
                 let block = from_clickhouse_block(self.schema.clone(), v);
                 match block {
-                    Ok(block) => Some(block),
+                    Ok(block) => Some(Ok(block)),
                     Err(e) => {
                         log::error!(
                             "failed to convert ClickHouseBlock to block , breaking out, {:?}",
@@ -21,6 +21,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::*;
 use common_planners::PlanNode;
+use common_streams::LimitBytesStream;
 use metrics::histogram;
 use msql_srv::ErrorKind;
 use msql_srv::InitWriter;
@@ -273,12 +274,17 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         let instant = Instant::now();
 
         let interpreter = InterpreterFactory::get(context.clone(), plan?)?;
-        let data_stream = interpreter.execute().await?;
+        let mut data_stream = interpreter.execute().await?;
         histogram!(
             super::mysql_metrics::METRIC_INTERPRETER_USEDTIME,
             instant.elapsed()
         );
 
+        let max_result_size = context.get_settings().get_max_result_size()? as usize;
+        if max_result_size > 0 {
+            data_stream = Box::pin(LimitBytesStream::new(data_stream, max_result_size));
+        }
+
         let collector = data_stream.collect::<Result<Vec<DataBlock>>>();
         let query_result = collector.await;
         query_result.map(|data| (data, Self::extra_info(context, instant)))
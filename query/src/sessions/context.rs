@@ -22,6 +22,7 @@ use common_base::tokio::task::JoinHandle;
 use common_base::ProgressCallback;
 use common_base::ProgressValues;
 use common_base::TrySpawn;
+use common_dal::DalContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
@@ -142,8 +143,44 @@ impl DatabendQueryContext {
         self.shared.get_catalog()
     }
 
+    /// The temporary table of the session is preferred over the table of the same name.
     pub fn get_table(&self, database: &str, table: &str) -> Result<Arc<TableMeta>> {
-        self.get_catalog().get_table(database, table)
+        match self.shared.session.get_temporary_table(database, table) {
+            Some(table_meta) => Ok(table_meta),
+            None => self.get_catalog().get_table(database, table),
+        }
+    }
+
+    /// Get the tables of one database, the tables which are not temporary are fetched in bulk.
+    pub fn get_tables(&self, database: &str, tables: &[String]) -> Result<Vec<Arc<TableMeta>>> {
+        let mut temporary_tables = Vec::with_capacity(tables.len());
+        let mut missed = vec![];
+        for table in tables {
+            let table_meta = self.shared.session.get_temporary_table(database, table);
+            if table_meta.is_none() {
+                missed.push(table.clone());
+            }
+            temporary_tables.push(table_meta);
+        }
+
+        let mut fetched = match missed.is_empty() {
+            true => vec![].into_iter(),
+            false => self
+                .get_catalog()
+                .get_database(database)?
+                .mget_tables(&missed)?
+                .into_iter(),
+        };
+
+        temporary_tables
+            .into_iter()
+            .map(|table_meta| match table_meta {
+                Some(table_meta) => Ok(table_meta),
+                None => fetched.next().ok_or_else(|| {
+                    ErrorCode::LogicalError("mget_tables returned fewer tables than requested")
+                }),
+            })
+            .collect()
     }
 
     pub fn get_table_by_id(
@@ -152,8 +189,33 @@ impl DatabendQueryContext {
         table_id: MetaId,
         table_ver: Option<MetaVersion>,
     ) -> Result<Arc<TableMeta>> {
-        self.get_catalog()
-            .get_table_by_id(database, table_id, table_ver)
+        match self.shared.session.get_temporary_table_by_id(table_id) {
+            Some(table_meta) => Ok(table_meta),
+            None => self
+                .get_catalog()
+                .get_table_by_id(database, table_id, table_ver),
+        }
+    }
+
+    /// A table id for a new temporary table of the session.
+    pub fn next_temporary_table_id(&self) -> MetaId {
+        self.shared.session.next_temporary_table_id()
+    }
+
+    /// Returns false if the session already has a temporary table of the name.
+    pub fn add_temporary_table(
+        &self,
+        database: &str,
+        table: &str,
+        table_meta: Arc<TableMeta>,
+    ) -> bool {
+        self.shared
+            .session
+            .add_temporary_table(database, table, table_meta)
+    }
+
+    pub fn drop_temporary_table(&self, database: &str, table: &str) -> Option<Arc<TableMeta>> {
+        self.shared.session.drop_temporary_table(database, table)
     }
 
     pub fn get_table_function(
@@ -175,6 +237,17 @@ impl DatabendQueryContext {
         Ok(abort_stream)
     }
 
+    /// Returns an AbortedQuery error once the query has been killed, for the long running
+    /// work which is not driven by an abortable stream, e.g. writing blocks of an INSERT.
+    pub fn check_aborting(&self) -> Result<()> {
+        match self.shared.aborting.load(Acquire) {
+            true => Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed",
+            )),
+            false => Ok(()),
+        }
+    }
+
     pub fn get_current_database(&self) -> String {
         self.shared.get_current_database()
     }
@@ -205,6 +278,10 @@ impl DatabendQueryContext {
         self.shared.conf.clone()
     }
 
+    pub fn get_dal_context(&self) -> Arc<DalContext> {
+        self.shared.dal_ctx.clone()
+    }
+
     pub fn get_subquery_name(&self, _query: &PlanNode) -> String {
         let index = self.shared.subquery_index.fetch_add(1, Ordering::Relaxed);
         format!("_subquery_{}", index)
@@ -252,6 +329,8 @@ impl DatabendQueryContextShared {
         if self.ref_count.fetch_sub(1, Ordering::Release) == 1 {
             std::sync::atomic::fence(Acquire);
             log::info!("Destroy DatabendQueryContext");
+            self.dump_read_stats();
+            self.dump_io_trace();
             self.session.destroy_context_shared();
         }
     }
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 
 use common_base::Progress;
 use common_base::Runtime;
+use common_dal::DalContext;
 use common_exception::Result;
 use common_infallible::RwLock;
 use common_planners::PlanNode;
@@ -29,6 +33,9 @@ use crate::configs::Config;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 
+/// Max number of the storage requests kept in the io trace of a query.
+const IO_TRACE_CAPACITY: usize = 10000;
+
 /// Data that needs to be shared in a query context.
 /// This is very useful, for example, for queries:
 ///     USE database_1;
@@ -41,11 +48,13 @@ use crate::sessions::Settings;
 pub struct DatabendQueryContextShared {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) progress: Arc<Progress>,
+    pub(in crate::sessions) dal_ctx: Arc<DalContext>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
     pub(in crate::sessions) cluster_cache: ClusterRef,
     pub(in crate::sessions) sources_abort_handle: Arc<RwLock<Vec<AbortHandle>>>,
+    pub(in crate::sessions) aborting: Arc<AtomicBool>,
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
@@ -62,10 +71,12 @@ impl DatabendQueryContextShared {
             conf,
             init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
             progress: Arc::new(Progress::create()),
+            dal_ctx: Arc::new(Self::create_dal_context(&session)),
             session,
             cluster_cache,
             runtime: Arc::new(RwLock::new(None)),
             sources_abort_handle: Arc::new(RwLock::new(Vec::new())),
+            aborting: Arc::new(AtomicBool::new(false)),
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
@@ -74,6 +85,7 @@ impl DatabendQueryContextShared {
     }
 
     pub fn kill(&self) {
+        self.aborting.store(true, Ordering::Release);
         let mut sources_abort_handle = self.sources_abort_handle.write();
 
         while let Some(source_abort_handle) = sources_abort_handle.pop() {
@@ -95,6 +107,70 @@ impl DatabendQueryContextShared {
         self.session.set_current_database(new_database_name);
     }
 
+    fn create_dal_context(session: &Arc<Session>) -> DalContext {
+        let settings = session.get_settings();
+        let ctx = match settings.get_max_open_files_per_query() {
+            Ok(limit) => DalContext::with_open_files_limit(limit as usize),
+            Err(_) => DalContext::create(),
+        };
+
+        let read_mb = settings.get_max_storage_read_bandwidth_mb().unwrap_or(0);
+        let write_mb = settings.get_max_storage_write_bandwidth_mb().unwrap_or(0);
+        let ctx = ctx.with_bandwidth_limit(read_mb * 1024 * 1024, write_mb * 1024 * 1024);
+
+        match settings.get_enable_storage_io_trace() {
+            Ok(1) => ctx.with_io_trace(IO_TRACE_CAPACITY),
+            _ => ctx,
+        }
+    }
+
+    /// Write the storage stats of the query to the log, if it has read or been throttled.
+    pub(in crate::sessions) fn dump_read_stats(&self) {
+        if let Some(stats) = self.dal_ctx.get_stats_summary() {
+            log::info!(
+                "dal_read_stats query_id: {}, {}",
+                self.init_query_id.read(),
+                stats
+            );
+        }
+    }
+
+    /// Write the io trace of the query to the log, if it is on.
+    pub(in crate::sessions) fn dump_io_trace(&self) {
+        let io_trace = match self.dal_ctx.get_io_trace() {
+            None => return,
+            Some(io_trace) => io_trace,
+        };
+
+        let query_id = self.init_query_id.read().clone();
+        for entry in io_trace.entries() {
+            let start = entry
+                .start
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros();
+            log::info!(
+                "io_trace query_id: {}, tag: {}, operation: {}, path: {}, offset: {:?}, bytes: {}, start_us: {}, latency: {:?}",
+                query_id,
+                entry.tag,
+                entry.operation,
+                entry.path,
+                entry.offset,
+                entry.bytes,
+                start,
+                entry.latency
+            );
+        }
+
+        if io_trace.dropped() > 0 {
+            log::info!(
+                "io_trace query_id: {}, {} earlier entries are dropped",
+                query_id,
+                io_trace.dropped()
+            );
+        }
+    }
+
     pub fn get_settings(&self) -> Arc<Settings> {
         self.session.get_settings()
     }
@@ -0,0 +1,78 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_context_check_aborting() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+
+    let ctx = session.create_context().await?;
+    let subquery_ctx = crate::sessions::DatabendQueryContext::new(ctx.clone());
+    assert!(ctx.check_aborting().is_ok());
+
+    session.force_kill_query();
+    for ctx in [ctx, subquery_ctx] {
+        match ctx.check_aborting() {
+            Err(cause) => assert_eq!(cause.code(), ErrorCode::AbortedQuery("").code()),
+            Ok(_) => panic!("check_aborting must fail after the query is killed"),
+        }
+    }
+
+    // The next query of the session is not affected.
+    let ctx = session.create_context().await?;
+    assert!(ctx.check_aborting().is_ok());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_context_get_tables() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+
+    for sql in [
+        "create table default.a(a bigint) Engine = Memory",
+        "create table default.b(a bigint) Engine = Memory",
+        "create temporary table default.c(a bigint)",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        executor.execute().await?.try_collect::<Vec<_>>().await?;
+    }
+
+    // Same as getting the tables one by one, the temporary table included.
+    let names = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+    let tables = ctx.get_tables("default", &names)?;
+    assert_eq!(tables.len(), names.len());
+    for (table, name) in tables.iter().zip(names.iter()) {
+        assert_eq!(table.raw().name(), name);
+        assert_eq!(table.meta_id(), ctx.get_table("default", name)?.meta_id());
+    }
+
+    let names = vec!["a".to_string(), "x".to_string()];
+    match ctx.get_tables("default", &names) {
+        Ok(_) => panic!("table x must not be found"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::UnknownTable("").code()),
+    }
+    Ok(())
+}
@@ -17,6 +17,8 @@ mod macros;
 
 mod context;
 mod context_shared;
+#[cfg(test)]
+mod context_test;
 mod metrics;
 mod session;
 mod session_info;
@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
 use common_exception::Result;
 use common_infallible::Mutex;
+use common_metatypes::MetaId;
 use futures::channel::oneshot::Sender;
 use futures::channel::*;
 
 use crate::catalogs::impls::DatabaseCatalog;
+use crate::catalogs::TableMeta;
 use crate::configs::Config;
 use crate::sessions::context_shared::DatabendQueryContextShared;
 use crate::sessions::DatabendQueryContext;
@@ -30,6 +33,10 @@ use crate::sessions::SessionManagerRef;
 use crate::sessions::Settings;
 use crate::users::UserManagerRef;
 
+/// The ids of the temporary tables of a session start from here, so they never clash with
+/// the meta service's. They are only looked up within the session.
+const TEMPORARY_TABLE_ID_BEGIN: MetaId = 1 << 63;
+
 pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) abort: bool,
     pub(in crate::sessions) current_database: String,
@@ -37,6 +44,9 @@ pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) client_host: Option<SocketAddr>,
     pub(in crate::sessions) io_shutdown_tx: Option<Sender<Sender<()>>>,
     pub(in crate::sessions) context_shared: Option<Arc<DatabendQueryContextShared>>,
+    // (database, table) -> the temporary table, dropped with the session.
+    pub(in crate::sessions) temporary_tables: HashMap<(String, String), Arc<TableMeta>>,
+    pub(in crate::sessions) next_temporary_table_id: MetaId,
 }
 
 #[derive(Clone)]
@@ -69,6 +79,8 @@ impl Session {
                 client_host: None,
                 io_shutdown_tx: None,
                 context_shared: None,
+                temporary_tables: HashMap::new(),
+                next_temporary_table_id: TEMPORARY_TABLE_ID_BEGIN,
             })),
         }))
     }
@@ -185,4 +197,60 @@ impl Session {
     pub fn get_user_manager(self: &Arc<Self>) -> UserManagerRef {
         self.sessions.get_user_manager()
     }
+
+    pub fn get_temporary_table(
+        self: &Arc<Self>,
+        database: &str,
+        table: &str,
+    ) -> Option<Arc<TableMeta>> {
+        let key = (database.to_string(), table.to_string());
+        self.mutable_state
+            .lock()
+            .temporary_tables
+            .get(&key)
+            .cloned()
+    }
+
+    pub fn get_temporary_table_by_id(self: &Arc<Self>, table_id: MetaId) -> Option<Arc<TableMeta>> {
+        let inner = self.mutable_state.lock();
+        inner
+            .temporary_tables
+            .values()
+            .find(|table| table.meta_id() == table_id)
+            .cloned()
+    }
+
+    pub fn next_temporary_table_id(self: &Arc<Self>) -> MetaId {
+        let mut inner = self.mutable_state.lock();
+        let table_id = inner.next_temporary_table_id;
+        inner.next_temporary_table_id += 1;
+        table_id
+    }
+
+    /// Returns false if the session already has a temporary table of the name.
+    pub fn add_temporary_table(
+        self: &Arc<Self>,
+        database: &str,
+        table: &str,
+        table_meta: Arc<TableMeta>,
+    ) -> bool {
+        let key = (database.to_string(), table.to_string());
+        let mut inner = self.mutable_state.lock();
+        match inner.temporary_tables.contains_key(&key) {
+            true => false,
+            false => {
+                inner.temporary_tables.insert(key, table_meta);
+                true
+            }
+        }
+    }
+
+    pub fn drop_temporary_table(
+        self: &Arc<Self>,
+        database: &str,
+        table: &str,
+    ) -> Option<Arc<TableMeta>> {
+        let key = (database.to_string(), table.to_string());
+        self.mutable_state.lock().temporary_tables.remove(&key)
+    }
 }
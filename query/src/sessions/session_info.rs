@@ -15,6 +15,8 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use common_base::ProgressValues;
+
 use crate::sessions::session::MutableStatus;
 use crate::sessions::Session;
 use crate::sessions::Settings;
@@ -28,6 +30,7 @@ pub struct ProcessInfo {
     pub settings: Arc<Settings>,
     pub client_address: Option<SocketAddr>,
     pub session_extra_info: Option<String>,
+    pub scan_progress_value: Option<ProgressValues>,
 }
 
 impl Session {
@@ -45,6 +48,7 @@ impl Session {
             settings: status.session_settings.clone(),
             client_address: status.client_host,
             session_extra_info: self.process_extra_info(status),
+            scan_progress_value: Session::query_scan_progress_value(status),
         }
     }
 
@@ -68,6 +72,13 @@ impl Session {
         context_shared.map(|_| String::from("Partial cluster query stage"))
     }
 
+    fn query_scan_progress_value(status: &MutableStatus) -> Option<ProgressValues> {
+        status
+            .context_shared
+            .as_ref()
+            .map(|context_shared| context_shared.progress.get_values())
+    }
+
     fn query_extra_info(status: &MutableStatus) -> Option<String> {
         status.context_shared.as_ref().and_then(|context_shared| {
             context_shared
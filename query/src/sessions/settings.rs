@@ -29,6 +29,13 @@ impl Settings {
     apply_macros! { apply_getter_setter_settings, apply_initial_settings, apply_update_settings,
         ("max_block_size", u64, 10000, "Maximum block size for reading"),
         ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically."),
+        ("max_open_files_per_query", u64, 1024, "The maximum number of files opened at the same time by a query on the local storage, 0 means no limit."),
+        ("max_storage_read_bandwidth_mb", u64, 0, "The maximum bandwidth of the storage reads of a query in MB per second, 0 means no limit."),
+        ("max_storage_write_bandwidth_mb", u64, 0, "The maximum bandwidth of the storage writes of a query in MB per second, 0 means no limit."),
+        ("max_result_size", u64, 0, "The maximum bytes of the result returned to the client, the query fails once it is exceeded. 0 means no limit."),
+        ("enable_storage_io_trace", u64, 0, "Record every storage request of a query and write them to the log when the query is finished, 1 means enabled."),
+        ("enable_fuse_statistics_check", u64, 0, "Recompute the summary statistics of a fuse table from all its segments on each commit and fail if they differ from the incremental ones, 1 means enabled."),
+        ("exchange_compression", String, "none", "The codec of the data blocks sent between the cluster nodes, 'none' or 'lz4'. Set 'lz4' only when all the nodes support it."),
         ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds"),
         ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query."),
         ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.")
@@ -228,7 +235,7 @@ impl SettingsBase {
     }
 
     #[allow(unused)]
-    pub fn try_update_string(&self, key: &'static str, val: &str) -> Result<()> {
+    pub fn try_update_string(&self, key: &'static str, val: String) -> Result<()> {
         let mut settings = self.settings.write();
         let setting_val = settings
             .get(key)
@@ -236,7 +243,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             let v = DataValue::Struct(vec![
-                DataValue::String(Some(val.as_bytes().to_vec())),
+                DataValue::String(Some(val.into_bytes())),
                 values[1].clone(),
                 values[2].clone(),
             ]);
@@ -246,7 +253,7 @@ impl SettingsBase {
     }
 
     #[allow(unused)]
-    pub fn try_get_string(&self, key: &str) -> Result<Vec<u8>> {
+    pub fn try_get_string(&self, key: &str) -> Result<String> {
         let settings = self.settings.read();
         let setting_val = settings
             .get(key)
@@ -254,7 +261,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             if let DataValue::String(Some(result)) = values[0].clone() {
-                return Ok(result);
+                return Ok(String::from_utf8_lossy(&result).to_string());
             }
         }
 
@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::io::Cursor;
 use std::sync::Arc;
 
-use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -64,6 +64,7 @@ use sqlparser::ast::TableFactor;
 use sqlparser::ast::UnaryOperator;
 
 use crate::catalogs::Catalog;
+use crate::catalogs::TableMeta;
 use crate::functions::ContextFunction;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sql::sql_statement::DfCreateTable;
@@ -74,6 +75,7 @@ use crate::sql::DfDescribeTable;
 use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
+use crate::sql::DfInsertValues;
 use crate::sql::DfKillStatement;
 use crate::sql::DfParser;
 use crate::sql::DfShowCreateTable;
@@ -85,11 +87,16 @@ use crate::sql::SQLCommon;
 
 pub struct PlanParser {
     ctx: DatabendQueryContextRef,
+    // The tables resolved in bulk before planning a query, keyed by (database, table).
+    tables: Mutex<HashMap<(String, String), Arc<TableMeta>>>,
 }
 
 impl PlanParser {
     pub fn create(ctx: DatabendQueryContextRef) -> Self {
-        Self { ctx }
+        Self {
+            ctx,
+            tables: Mutex::new(HashMap::new()),
+        }
     }
 
     pub fn build_from_sql(&self, query: &str) -> Result<PlanNode> {
@@ -130,6 +137,7 @@ impl PlanParser {
             DfStatement::DescribeTable(v) => self.sql_describe_table_to_plan(v),
             DfStatement::DropTable(v) => self.sql_drop_table_to_plan(v),
             DfStatement::TruncateTable(v) => self.sql_truncate_table_to_plan(v),
+            DfStatement::InsertValues(v) => self.sql_insert_values_to_plan(v),
             DfStatement::UseDatabase(v) => self.sql_use_database_to_plan(v),
             DfStatement::ShowCreateTable(v) => self.sql_show_create_table_to_plan(v),
             DfStatement::ShowTables(df) => {
@@ -174,7 +182,10 @@ impl PlanParser {
     #[tracing::instrument(level = "info", skip(self, statement))]
     pub fn sql_statement_to_plan(&self, statement: &sqlparser::ast::Statement) -> Result<PlanNode> {
         match statement {
-            Statement::Query(query) => self.query_to_plan(query),
+            Statement::Query(query) => {
+                self.prefetch_tables(query);
+                self.query_to_plan(query)
+            }
             Statement::SetVariable {
                 variable, value, ..
             } => self.set_variable_to_plan(variable, value),
@@ -317,6 +328,7 @@ impl PlanParser {
         let schema = DataSchemaRefExt::create(fields);
         Ok(PlanNode::CreateTable(CreateTablePlan {
             if_not_exists: create.if_not_exists,
+            temporary: create.temporary,
             db,
             table,
             schema,
@@ -425,6 +437,29 @@ impl PlanParser {
         columns: &[Ident],
         source: &Option<Box<Query>>,
         format_sql: &str,
+    ) -> Result<PlanNode> {
+        let mut values = None;
+        if let Some(source) = source {
+            if let sqlparser::ast::SetExpr::Values(_vs) = &source.body {
+                tracing::debug!("{:?}", format_sql);
+                let index = format_sql.find_substring(" VALUES ").unwrap();
+                values = Some(&format_sql[index + " VALUES ".len()..]);
+            }
+        }
+        self.insert_values_to_plan(table_name, columns, values)
+    }
+
+    // DfInsertValues to plan.
+    #[tracing::instrument(level = "info", skip(self, insert), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_insert_values_to_plan(&self, insert: &DfInsertValues) -> Result<PlanNode> {
+        self.insert_values_to_plan(&insert.name, &insert.columns, Some(&insert.values))
+    }
+
+    fn insert_values_to_plan(
+        &self,
+        table_name: &ObjectName,
+        columns: &[Ident],
+        values: Option<&str>,
     ) -> Result<PlanNode> {
         let mut db_name = self.ctx.get_current_database();
         let mut tbl_name = table_name.0[0].value.clone();
@@ -433,7 +468,7 @@ impl PlanParser {
             db_name = tbl_name;
             tbl_name = table_name.0[1].value.clone();
         }
-        let table = self.ctx.get_catalog().get_table(&db_name, &tbl_name)?;
+        let table = self.ctx.get_table(&db_name, &tbl_name)?;
 
         let mut schema = table.raw().schema()?;
         let tbl_id = table.meta_id();
@@ -447,27 +482,22 @@ impl PlanParser {
             schema = DataSchemaRefExt::create(fields);
         }
 
-        let mut input_stream = futures::stream::iter::<Vec<DataBlock>>(vec![]);
+        let mut source = None;
+        if let Some(values) = values {
+            let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+            let reader = Cursor::new(values.as_bytes().to_vec());
+            source = Some(ValueSource::new(reader, schema.clone(), block_size));
+        }
 
-        if let Some(source) = source {
-            if let sqlparser::ast::SetExpr::Values(_vs) = &source.body {
-                tracing::debug!("{:?}", format_sql);
-                let index = format_sql.find_substring(" VALUES ").unwrap();
-                let values = &format_sql[index + " VALUES ".len()..];
-
-                let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
-                let mut source = ValueSource::new(values.as_bytes(), schema.clone(), block_size);
-                let mut blocks = vec![];
-                loop {
-                    let block = source.read()?;
-                    match block {
-                        Some(b) => blocks.push(b),
-                        None => break,
-                    }
-                }
-                input_stream = futures::stream::iter(blocks);
+        // The tuples are parsed as the stream is polled, so only one block is live at a time.
+        // The stream ends after the first error.
+        let input_stream = futures::stream::iter(std::iter::from_fn(move || {
+            let block = source.as_mut()?.read().transpose();
+            if matches!(block, Some(Err(_))) {
+                source = None;
             }
-        }
+            block
+        }));
 
         let plan_node = InsertIntoPlan {
             db_name,
@@ -479,6 +509,106 @@ impl PlanParser {
         Ok(PlanNode::InsertInto(plan_node))
     }
 
+    /// Resolve the tables read by the query and its subqueries with one lookup per database,
+    /// instead of one lookup per table while planning.
+    fn prefetch_tables(&self, query: &Query) {
+        let mut names = HashMap::new();
+        Self::collect_table_names(query, &self.ctx.get_current_database(), &mut names);
+
+        for (db_name, table_names) in names {
+            if table_names.len() < 2 {
+                continue;
+            }
+
+            // The unknown tables are reported by the lookups of the planning.
+            if let Ok(tables) = self.ctx.get_tables(&db_name, &table_names) {
+                let mut cache = self.tables.lock();
+                for (table_name, table) in table_names.into_iter().zip(tables) {
+                    cache.insert((db_name.clone(), table_name), table);
+                }
+            }
+        }
+    }
+
+    fn collect_table_names(
+        query: &Query,
+        current_db: &str,
+        names: &mut HashMap<String, Vec<String>>,
+    ) {
+        let select = match &query.body {
+            sqlparser::ast::SetExpr::Select(select) => select,
+            _ => return,
+        };
+
+        for table_with_joins in &select.from {
+            if let TableFactor::Table { name, args, .. } = &table_with_joins.relation {
+                if !args.is_empty() {
+                    continue;
+                }
+
+                let (db_name, table_name) = match name.0.len() {
+                    2 => (name.0[0].to_string(), name.0[1].to_string()),
+                    _ => (current_db.to_string(), name.to_string()),
+                };
+                let table_names = names.entry(db_name).or_insert_with(Vec::new);
+                if !table_names.contains(&table_name) {
+                    table_names.push(table_name);
+                }
+            }
+        }
+
+        for item in &select.projection {
+            match item {
+                sqlparser::ast::SelectItem::UnnamedExpr(expr)
+                | sqlparser::ast::SelectItem::ExprWithAlias { expr, .. } => {
+                    Self::collect_subquery_table_names(expr, current_db, names)
+                }
+                _ => {}
+            }
+        }
+        if let Some(selection) = &select.selection {
+            Self::collect_subquery_table_names(selection, current_db, names);
+        }
+    }
+
+    fn collect_subquery_table_names(
+        expr: &sqlparser::ast::Expr,
+        current_db: &str,
+        names: &mut HashMap<String, Vec<String>>,
+    ) {
+        match expr {
+            sqlparser::ast::Expr::Subquery(query) | sqlparser::ast::Expr::Exists(query) => {
+                Self::collect_table_names(query, current_db, names)
+            }
+            sqlparser::ast::Expr::Nested(expr) | sqlparser::ast::Expr::UnaryOp { expr, .. } => {
+                Self::collect_subquery_table_names(expr, current_db, names)
+            }
+            sqlparser::ast::Expr::BinaryOp { left, right, .. } => {
+                Self::collect_subquery_table_names(left, current_db, names);
+                Self::collect_subquery_table_names(right, current_db, names);
+            }
+            sqlparser::ast::Expr::Function(function) => {
+                for arg in &function.args {
+                    match arg {
+                        FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => {
+                            Self::collect_subquery_table_names(arg, current_db, names)
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableMeta>> {
+        let key = (db_name.to_string(), table_name.to_string());
+        let prefetched = self.tables.lock().get(&key).cloned();
+        match prefetched {
+            Some(table) => Ok(table),
+            None => self.ctx.get_table(db_name, table_name),
+        }
+    }
+
     /// Generate a logic plan from an SQL query
     pub fn query_to_plan(&self, query: &sqlparser::ast::Query) -> Result<PlanNode> {
         if query.with.is_some() {
@@ -507,10 +637,12 @@ impl PlanParser {
         offset: &Option<sqlparser::ast::Offset>,
         order_by: &[OrderByExpr],
     ) -> Result<PlanNode> {
+        let limit_push_down = Self::limit_push_down(select, limit, offset, order_by);
+
         // Filter expression
         // In example: Filter=(number > 1)
         let plan = self
-            .plan_tables_with_joins(&select.from)
+            .plan_tables_with_joins(&select.from, limit_push_down)
             .and_then(|input| self.filter(&input, &select.selection, Some(select)))?;
 
         // Projection expression
@@ -688,10 +820,82 @@ impl PlanParser {
         }
     }
 
-    fn plan_tables_with_joins(&self, from: &[sqlparser::ast::TableWithJoins]) -> Result<PlanNode> {
+    /// The limit pushed down to the table, so that the table can plan fewer partitions.
+    ///
+    /// It is only pushed down if every row read from the table is returned until the limit is
+    /// reached, that is, without filter, aggregation, sorting or DISTINCT. e.g.
+    /// "SELECT a, b + 1 FROM t LIMIT 10 OFFSET 5" pushes down 15.
+    fn limit_push_down(
+        select: &sqlparser::ast::Select,
+        limit: &Option<sqlparser::ast::Expr>,
+        offset: &Option<sqlparser::ast::Offset>,
+        order_by: &[OrderByExpr],
+    ) -> Option<usize> {
+        if select.selection.is_some()
+            || !select.group_by.is_empty()
+            || select.having.is_some()
+            || select.distinct
+            || !order_by.is_empty()
+            || select.from.iter().any(|from| !from.joins.is_empty())
+        {
+            return None;
+        }
+
+        let row_wise = select.projection.iter().all(|item| match item {
+            sqlparser::ast::SelectItem::UnnamedExpr(expr) => Self::is_row_wise_expr(expr),
+            sqlparser::ast::SelectItem::ExprWithAlias { expr, .. } => Self::is_row_wise_expr(expr),
+            sqlparser::ast::SelectItem::Wildcard
+            | sqlparser::ast::SelectItem::QualifiedWildcard(_) => true,
+        });
+        if !row_wise {
+            return None;
+        }
+
+        let literal = |expr: &sqlparser::ast::Expr| match expr {
+            sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number(n, _)) => {
+                n.parse::<usize>().ok()
+            }
+            _ => None,
+        };
+        let n = literal(limit.as_ref()?)?;
+        let offset = match offset {
+            None => 0,
+            Some(offset) => literal(&offset.value)?,
+        };
+        n.checked_add(offset)
+    }
+
+    /// Whether the expression produces exactly one value for each row, conservatively.
+    fn is_row_wise_expr(expr: &sqlparser::ast::Expr) -> bool {
+        match expr {
+            sqlparser::ast::Expr::Identifier(_)
+            | sqlparser::ast::Expr::CompoundIdentifier(_)
+            | sqlparser::ast::Expr::Value(_) => true,
+            sqlparser::ast::Expr::Nested(expr)
+            | sqlparser::ast::Expr::UnaryOp { expr, .. }
+            | sqlparser::ast::Expr::Cast { expr, .. } => Self::is_row_wise_expr(expr),
+            sqlparser::ast::Expr::BinaryOp { left, right, .. } => {
+                Self::is_row_wise_expr(left) && Self::is_row_wise_expr(right)
+            }
+            sqlparser::ast::Expr::Function(function) => {
+                !AggregateFunctionFactory::instance().check(&function.name.to_string())
+                    && function.args.iter().all(|arg| match arg {
+                        FunctionArg::Named { arg, .. } => Self::is_row_wise_expr(arg),
+                        FunctionArg::Unnamed(arg) => Self::is_row_wise_expr(arg),
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn plan_tables_with_joins(
+        &self,
+        from: &[sqlparser::ast::TableWithJoins],
+        limit: Option<usize>,
+    ) -> Result<PlanNode> {
         match from.len() {
             0 => self.plan_with_dummy_source(),
-            1 => self.plan_table_with_joins(&from[0]),
+            1 => self.plan_table_with_joins(&from[0], limit),
             // Such as SELECT * FROM t1, t2;
             // It's not `JOIN` clause.
             _ => Result::Err(ErrorCode::SyntaxException("Cannot SELECT multiple tables")),
@@ -734,11 +938,19 @@ impl PlanParser {
             })
     }
 
-    fn plan_table_with_joins(&self, t: &sqlparser::ast::TableWithJoins) -> Result<PlanNode> {
-        self.create_relation(&t.relation)
+    fn plan_table_with_joins(
+        &self,
+        t: &sqlparser::ast::TableWithJoins,
+        limit: Option<usize>,
+    ) -> Result<PlanNode> {
+        self.create_relation(&t.relation, limit)
     }
 
-    fn create_relation(&self, relation: &sqlparser::ast::TableFactor) -> Result<PlanNode> {
+    fn create_relation(
+        &self,
+        relation: &sqlparser::ast::TableFactor,
+        limit: Option<usize>,
+    ) -> Result<PlanNode> {
         match relation {
             TableFactor::Table { name, args, .. } => {
                 let mut db_name = self.ctx.get_current_database();
@@ -784,7 +996,7 @@ impl PlanParser {
                     table_name = table_function.name().to_string();
                     table = table_function.as_table();
                 } else {
-                    let table_meta = self.ctx.get_table(&db_name, &table_name)?;
+                    let table_meta = self.get_table(&db_name, &table_name)?;
                     meta_id = table_meta.meta_id();
                     meta_version = table_meta.meta_ver();
                     table = table_meta.raw().clone();
@@ -799,7 +1011,7 @@ impl PlanParser {
                             table_schema: schema.as_ref(),
                             table_args,
                         };
-                        PlanBuilder::scan(&db_name, tbl_scan_info, None, None)
+                        PlanBuilder::scan(&db_name, tbl_scan_info, None, limit)
                             .and_then(|builder| builder.build())
                     })
                 };
@@ -819,7 +1031,7 @@ impl PlanParser {
             }
             TableFactor::Derived { subquery, .. } => self.query_to_plan(subquery),
             TableFactor::NestedJoin(table_with_joins) => {
-                self.plan_table_with_joins(table_with_joins)
+                self.plan_table_with_joins(table_with_joins, None)
             }
             TableFactor::TableFunction { .. } => {
                 Result::Err(ErrorCode::UnImplement("Unsupported table function"))
@@ -1184,7 +1396,12 @@ impl PlanParser {
             let variable = variable.value.clone();
             let value = match value {
                 sqlparser::ast::SetVariableValue::Ident(v) => v.value.clone(),
-                sqlparser::ast::SetVariableValue::Literal(v) => v.to_string(),
+                sqlparser::ast::SetVariableValue::Literal(v) => match v {
+                    // The quotes of a string literal are not part of the value.
+                    sqlparser::ast::Value::SingleQuotedString(s)
+                    | sqlparser::ast::Value::DoubleQuotedString(s) => s.clone(),
+                    _ => v.to_string(),
+                },
             };
             vars.push(VarValue { variable, value });
         }
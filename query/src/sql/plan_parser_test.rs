@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_base::tokio;
 use common_exception::Result;
+use common_functions::scalars::CountMeFunction;
+use common_planners::Expression;
+use common_planners::PlanNode;
+use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::interpreters::InterpreterFactory;
 use crate::sql::PlanParser;
 
 #[test]
@@ -217,3 +223,157 @@ fn test_plan_parser() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_plan_parser_group_by_shared_expr() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let sql = "select number % 3 as g, count(), max(number % 3) from numbers(10) group by g";
+    let mut plan = PlanParser::create(ctx).build_from_sql(sql)?;
+
+    // Walk down to the partial aggregator.
+    let partial = loop {
+        match plan {
+            PlanNode::AggregatorPartial(partial) => break partial,
+            PlanNode::ReadSource(_) => panic!("AggregatorPartial not found: {}", sql),
+            other => plan = other.input(0).as_ref().clone(),
+        }
+    };
+
+    // The group key and the argument of max share the same column.
+    let group_names = partial
+        .group_expr
+        .iter()
+        .map(|expr| expr.column_name())
+        .collect::<Vec<_>>();
+    assert_eq!(group_names, vec!["(number % 3)".to_string()]);
+    match &partial.aggr_expr[1] {
+        Expression::AggregateFunction { args, .. } => {
+            assert_eq!(args[0].column_name(), group_names[0]);
+        }
+        other => panic!("Unexpected aggregate expression: {:?}", other),
+    }
+
+    // It is evaluated only once before group by.
+    match partial.input.as_ref() {
+        PlanNode::Expression(before_group_by) => {
+            assert_eq!(before_group_by.desc, "Before GroupBy");
+            assert_eq!(format!("{:?}", before_group_by.exprs), "[(number % 3)]");
+        }
+        other => panic!("Unexpected input of AggregatorPartial: {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_plan_parser_group_by_shared_expr_evaluated_once() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: Vec<&'static str>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "group key shared with an aggregate argument",
+            query: "select countme(number % 3) as g, count() as c, max(countme(number % 3)) as m from numbers(10) group by g",
+            expect: vec![
+                "+---+---+---+",
+                "| g | c | m |",
+                "+---+---+---+",
+                "| 0 | 4 | 0 |",
+                "| 1 | 3 | 1 |",
+                "| 2 | 3 | 2 |",
+                "+---+---+---+",
+            ],
+        },
+        Test {
+            name: "nested in the group key and the aggregate arguments",
+            query: "select countme(number) % 3 as g, max(countme(number) % 3) as m, sum(countme(number)) as s from numbers(10) group by g",
+            expect: vec![
+                "+---+---+----+",
+                "| g | m | s  |",
+                "+---+---+----+",
+                "| 0 | 0 | 18 |",
+                "| 1 | 1 | 12 |",
+                "| 2 | 2 | 15 |",
+                "+---+---+----+",
+            ],
+        },
+    ];
+
+    for t in tests {
+        let ctx = crate::tests::try_create_context()?;
+        // numbers(10) is read as one block.
+        ctx.get_settings().set_max_threads(1)?;
+
+        let evals = CountMeFunction::evals();
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(t.query)?;
+        let executor = InterpreterFactory::get(ctx, plan)?;
+        let result = executor.execute().await?.try_collect::<Vec<_>>().await?;
+
+        common_datablocks::assert_blocks_sorted_eq_with_name(t.name, t.expect, result.as_slice());
+        assert_eq!(CountMeFunction::evals() - evals, 1, "{}", t.name);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_plan_parser_limit_push_down() -> Result<()> {
+    struct Test {
+        sql: &'static str,
+        limit: Option<usize>,
+    }
+
+    let tests = vec![
+        Test {
+            sql: "select number from numbers_mt(10) limit 3",
+            limit: Some(3),
+        },
+        Test {
+            sql: "select number + 1 as n, 'a' from numbers_mt(10) limit 3 offset 2",
+            limit: Some(5),
+        },
+        Test {
+            sql: "select * from numbers_mt(10) where number > 1 limit 3",
+            limit: None,
+        },
+        Test {
+            sql: "select number from numbers_mt(10) order by number limit 3",
+            limit: None,
+        },
+        Test {
+            sql: "select number % 3 as g from numbers_mt(10) group by g limit 3",
+            limit: None,
+        },
+        Test {
+            sql: "select sum(number) from numbers_mt(10) limit 3",
+            limit: None,
+        },
+        Test {
+            sql: "select distinct number from numbers_mt(10) limit 3",
+            limit: None,
+        },
+        Test {
+            sql: "select number from numbers_mt(10)",
+            limit: None,
+        },
+    ];
+
+    for test in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let mut plan = PlanParser::create(ctx).build_from_sql(test.sql)?;
+        let read_source = loop {
+            match plan {
+                PlanNode::ReadSource(read_source) => break read_source,
+                other => plan = other.input(0).as_ref().clone(),
+            }
+        };
+
+        let limit = read_source.push_downs.and_then(|extras| extras.limit);
+        assert_eq!(limit, test.limit, "{}", test.sql);
+    }
+
+    Ok(())
+}
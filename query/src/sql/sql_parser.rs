@@ -44,6 +44,7 @@ use crate::sql::DfDropDatabase;
 use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
+use crate::sql::DfInsertValues;
 use crate::sql::DfKillStatement;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
@@ -97,6 +98,11 @@ impl<'a> DfParser<'a> {
         sql: &str,
         dialect: &dyn Dialect,
     ) -> Result<(Vec<DfStatement>, Vec<DfHint>), ParserError> {
+        if let Some((statement, head)) = DfParser::parse_insert_values(sql, dialect) {
+            let hints = DfParser::parse_hints(head, dialect)?;
+            return Ok((vec![statement], hints));
+        }
+
         let mut parser = DfParser::new_with_dialect(sql, dialect)?;
         let mut stmts = Vec::new();
 
@@ -119,6 +125,11 @@ impl<'a> DfParser<'a> {
             expecting_statement_delimiter = true;
         }
 
+        let hints = DfParser::parse_hints(sql, dialect)?;
+        Ok((stmts, hints))
+    }
+
+    fn parse_hints(sql: &str, dialect: &dyn Dialect) -> Result<Vec<DfHint>, ParserError> {
         let mut hints = Vec::new();
 
         let mut parser = DfParser::new_with_dialect(sql, dialect)?;
@@ -132,7 +143,58 @@ impl<'a> DfParser<'a> {
                 _ => continue,
             }
         }
-        Ok((stmts, hints))
+        Ok(hints)
+    }
+
+    /// Fast path for `INSERT INTO t [(columns)] VALUES (...), (...)` with only literal values.
+    ///
+    /// Only the part before VALUES is tokenized, the values are kept as raw text for the
+    /// ValueSource, so a large VALUES list doesn't build a token list and an AST.
+    /// Returns the statement and the SQL before VALUES, or None to use the native parser.
+    fn parse_insert_values<'b>(
+        sql: &'b str,
+        dialect: &dyn Dialect,
+    ) -> Option<(DfStatement, &'b str)> {
+        let index = find_values_keyword(sql)?;
+        let (head, values) = (&sql[..index], &sql[index + "VALUES".len()..]);
+
+        let values = values.trim_end().trim_end_matches(';').trim_end();
+        if !is_literal_values(values.as_bytes()) {
+            return None;
+        }
+
+        let mut parser = DfParser::new_with_dialect(head, dialect).ok()?;
+        if !parser
+            .parser
+            .parse_keywords(&[Keyword::INSERT, Keyword::INTO])
+        {
+            return None;
+        }
+
+        let name = parser.parser.parse_object_name().ok()?;
+        let mut columns = vec![];
+        if parser.parser.consume_token(&Token::LParen) {
+            loop {
+                columns.push(parser.parser.parse_identifier().ok()?);
+                if parser.parser.consume_token(&Token::RParen) {
+                    break;
+                }
+                if !parser.parser.consume_token(&Token::Comma) {
+                    return None;
+                }
+            }
+        }
+
+        if parser.parser.peek_token() != Token::EOF {
+            return None;
+        }
+
+        let insert = DfInsertValues {
+            name,
+            columns,
+            values: values.to_string(),
+        };
+        Some((DfStatement::InsertValues(insert), head))
     }
 
     /// Report unexpected token
@@ -236,6 +298,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "FRAGMENTS" => {
+                    self.parser.next_token();
+                    ExplainType::Fragments
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -379,7 +445,11 @@ impl<'a> DfParser<'a> {
     fn parse_create(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => match w.keyword {
-                Keyword::TABLE => self.parse_create_table(),
+                Keyword::TABLE => self.parse_create_table(false),
+                Keyword::TEMPORARY => {
+                    self.parser.expect_keyword(Keyword::TABLE)?;
+                    self.parse_create_table(true)
+                }
                 Keyword::DATABASE => self.parse_create_database(),
                 _ => self.expected("create statement", Token::Word(w)),
             },
@@ -486,13 +556,18 @@ impl<'a> DfParser<'a> {
         Ok(self.parser.next_token().to_string())
     }
 
-    fn parse_create_table(&mut self) -> Result<DfStatement, ParserError> {
+    fn parse_create_table(&mut self, temporary: bool) -> Result<DfStatement, ParserError> {
         let if_not_exists =
             self.parser
                 .parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parser.parse_object_name()?;
         let (columns, _) = self.parse_columns()?;
-        let engine = self.parse_table_engine()?;
+        // Temporary tables are kept in memory unless another engine is given.
+        let default_engine = match temporary {
+            true => "Memory",
+            false => "NULL",
+        };
+        let engine = self.parse_table_engine(default_engine)?;
 
         let mut table_properties = vec![];
 
@@ -508,6 +583,7 @@ impl<'a> DfParser<'a> {
 
         let create = DfCreateTable {
             if_not_exists,
+            temporary,
             name: table_name,
             columns,
             engine,
@@ -518,10 +594,10 @@ impl<'a> DfParser<'a> {
     }
 
     /// Parses the set of valid formats
-    fn parse_table_engine(&mut self) -> Result<String, ParserError> {
+    fn parse_table_engine(&mut self, default_engine: &str) -> Result<String, ParserError> {
         // TODO make ENGINE as a keyword
         if !self.consume_token("ENGINE") {
-            return Ok("NULL".to_string());
+            return Ok(default_engine.to_string());
         }
 
         self.parser.expect_token(&Token::Eq)?;
@@ -593,3 +669,143 @@ impl<'a> DfParser<'a> {
         }
     }
 }
+
+/// Byte offset of the first VALUES keyword outside quotes, only for statements starting with INSERT.
+fn find_values_keyword(sql: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let start = bytes.iter().position(|c| !c.is_ascii_whitespace())?;
+    if bytes.len() < start + 6 || !bytes[start..start + 6].eq_ignore_ascii_case(b"INSERT") {
+        return None;
+    }
+
+    let is_word = |c: u8| c.is_ascii_alphanumeric() || c == b'_';
+    let mut quote = None;
+    let mut index = start;
+    while index < bytes.len() {
+        let c = bytes[index];
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == b'\'' || c == b'"' || c == b'`' => quote = Some(c),
+            None if (index == 0 || !is_word(bytes[index - 1]))
+                && bytes.len() >= index + 6
+                && bytes[index..index + 6].eq_ignore_ascii_case(b"VALUES")
+                && (bytes.len() == index + 6 || !is_word(bytes[index + 6])) =>
+            {
+                return Some(index);
+            }
+            None => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Whether the text is a list of tuples of literals: quoted strings, numbers, NULL, TRUE or FALSE.
+fn is_literal_values(values: &[u8]) -> bool {
+    let skip_spaces = |pos: &mut usize| {
+        while *pos < values.len() && values[*pos].is_ascii_whitespace() {
+            *pos += 1;
+        }
+    };
+
+    let mut pos = 0;
+    let mut tuples = 0;
+    loop {
+        skip_spaces(&mut pos);
+        if tuples > 0 {
+            if pos == values.len() {
+                return true;
+            }
+            if values[pos] != b',' {
+                return false;
+            }
+            pos += 1;
+            skip_spaces(&mut pos);
+        }
+
+        if pos == values.len() || values[pos] != b'(' {
+            return false;
+        }
+        pos += 1;
+
+        loop {
+            skip_spaces(&mut pos);
+            if !skip_literal(values, &mut pos) {
+                return false;
+            }
+            skip_spaces(&mut pos);
+            match values.get(pos) {
+                Some(b',') => pos += 1,
+                Some(b')') => {
+                    pos += 1;
+                    break;
+                }
+                _ => return false,
+            }
+        }
+        tuples += 1;
+    }
+}
+
+fn skip_literal(values: &[u8], pos: &mut usize) -> bool {
+    match values.get(*pos) {
+        Some(&quote) if quote == b'\'' || quote == b'"' => {
+            // Escapes are not supported by the ValueSource, leave them to the native parser.
+            match values[*pos + 1..]
+                .iter()
+                .position(|c| *c == quote || *c == b'\\')
+            {
+                Some(len) if values[*pos + 1 + len] == quote => {
+                    *pos += len + 2;
+                    true
+                }
+                _ => false,
+            }
+        }
+        Some(_) => {
+            let start = *pos;
+            while *pos < values.len()
+                && (values[*pos].is_ascii_alphanumeric() || b"+-.".contains(&values[*pos]))
+            {
+                *pos += 1;
+            }
+
+            let literal = &values[start..*pos];
+            ["NULL", "TRUE", "FALSE"]
+                .iter()
+                .any(|keyword| literal.eq_ignore_ascii_case(keyword.as_bytes()))
+                || is_number(literal)
+        }
+        None => false,
+    }
+}
+
+fn is_number(literal: &[u8]) -> bool {
+    let digits = match literal.first() {
+        Some(b'+') | Some(b'-') => &literal[1..],
+        _ => literal,
+    };
+
+    let (mantissa, exponent) = match digits.iter().position(|c| *c == b'e' || *c == b'E') {
+        Some(index) => (&digits[..index], Some(&digits[index + 1..])),
+        None => (digits, None),
+    };
+
+    let valid_mantissa = mantissa.iter().any(|c| c.is_ascii_digit())
+        && mantissa.iter().filter(|c| **c == b'.').count() <= 1
+        && mantissa.iter().all(|c| c.is_ascii_digit() || *c == b'.');
+
+    let valid_exponent = match exponent {
+        None => true,
+        Some(exponent) => {
+            let exponent = match exponent.first() {
+                Some(b'+') | Some(b'-') => &exponent[1..],
+                _ => exponent,
+            };
+            !exponent.is_empty() && exponent.iter().all(|c| c.is_ascii_digit())
+        }
+    };
+
+    valid_mantissa && valid_exponent
+}
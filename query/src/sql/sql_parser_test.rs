@@ -122,6 +122,7 @@ fn create_table() -> Result<()> {
     let sql = "CREATE TABLE t(c1 int) ENGINE = CSV location = '/data/33.csv' ";
     let expected = DfStatement::CreateTable(DfCreateTable {
         if_not_exists: false,
+        temporary: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![make_column_def("c1", DataType::Int(None))],
         engine: "CSV".to_string(),
@@ -136,6 +137,7 @@ fn create_table() -> Result<()> {
     let sql = "CREATE TABLE t(c1 int, c2 bigint, c3 varchar(255) ) ENGINE = Parquet location = 'foo.parquet' ";
     let expected = DfStatement::CreateTable(DfCreateTable {
         if_not_exists: false,
+        temporary: false,
         name: ObjectName(vec![Ident::new("t")]),
         columns: vec![
             make_column_def("c1", DataType::Int(None)),
@@ -150,6 +152,28 @@ fn create_table() -> Result<()> {
     });
     expect_parse_ok(sql, expected)?;
 
+    let sql = "CREATE TEMPORARY TABLE IF NOT EXISTS t(c1 int) ENGINE = MEMORY";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: true,
+        temporary: true,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![make_column_def("c1", DataType::Int(None))],
+        engine: "MEMORY".to_string(),
+        options: vec![],
+    });
+    expect_parse_ok(sql, expected)?;
+
+    let sql = "CREATE TEMPORARY TABLE t(c1 int)";
+    let expected = DfStatement::CreateTable(DfCreateTable {
+        if_not_exists: false,
+        temporary: true,
+        name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![make_column_def("c1", DataType::Int(None))],
+        engine: "Memory".to_string(),
+        options: vec![],
+    });
+    expect_parse_ok(sql, expected)?;
+
     Ok(())
 }
 
@@ -401,3 +425,51 @@ fn show_databases_test() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn insert_values() -> Result<()> {
+    expect_parse_ok(
+        "INSERT INTO db1.t1 (a, b) VALUES (1, 'a,b'), (-2.5e3, NULL);",
+        DfStatement::InsertValues(DfInsertValues {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            columns: vec![Ident::new("a"), Ident::new("b")],
+            values: "(1, 'a,b'), (-2.5e3, NULL)".to_string(),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "insert into t1 values('values', \"x\")",
+        DfStatement::InsertValues(DfInsertValues {
+            name: ObjectName(vec![Ident::new("t1")]),
+            columns: vec![],
+            values: "('values', \"x\")".to_string(),
+        }),
+    )?;
+
+    expect_parse_ok(
+        "INSERT INTO t1 VALUES (1 , 2 ),\n(\n  3,\n  4\n)\n",
+        DfStatement::InsertValues(DfInsertValues {
+            name: ObjectName(vec![Ident::new("t1")]),
+            columns: vec![],
+            values: "(1 , 2 ),\n(\n  3,\n  4\n)".to_string(),
+        }),
+    )?;
+
+    // Expressions, escapes and trailing statements go to the native parser.
+    for sql in [
+        "INSERT INTO t1 VALUES (1 + 1)",
+        "INSERT INTO t1 VALUES (now())",
+        "INSERT INTO t1 VALUES ('a''b')",
+        "INSERT INTO t1 VALUES (1); SELECT 1",
+        "INSERT INTO t1 SELECT * FROM t2",
+    ] {
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        assert!(
+            matches!(statements[0], DfStatement::Statement(_)),
+            "{} should use the native parser",
+            sql
+        );
+    }
+
+    Ok(())
+}
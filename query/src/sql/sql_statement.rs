@@ -59,6 +59,8 @@ pub struct DfShowCreateTable {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateTable {
     pub if_not_exists: bool,
+    /// The table is only visible to the session and dropped with it
+    pub temporary: bool,
     /// Table name
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
@@ -82,6 +84,14 @@ pub struct DfTruncateTable {
     pub name: ObjectName,
 }
 
+/// `INSERT INTO ... VALUES` with only literal values, the values are kept as raw text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfInsertValues {
+    pub name: ObjectName,
+    pub columns: Vec<Ident>,
+    pub values: String,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateDatabase {
     pub if_not_exists: bool,
@@ -126,6 +136,7 @@ pub enum DfStatement {
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
     TruncateTable(DfTruncateTable),
+    InsertValues(DfInsertValues),
 
     // Settings.
     ShowSettings(DfShowSettings),
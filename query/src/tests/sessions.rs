@@ -86,6 +86,12 @@ impl SessionManagerBuilder {
         SessionManagerBuilder::inner_create(new_config)
     }
 
+    pub fn disk_data_path(self, value: impl Into<String>) -> SessionManagerBuilder {
+        let mut new_config = self.config.clone();
+        new_config.storage.disk.data_path = value.into();
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
     pub fn log_dir_with_relative(self, path: impl Into<String>) -> SessionManagerBuilder {
         let mut new_config = self.config.clone();
         new_config.log.log_dir = env::current_dir()
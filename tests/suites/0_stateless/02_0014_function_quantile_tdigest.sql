@@ -0,0 +1,6 @@
+select median(number) = 2 from numbers(5);
+select quantile_tdigest(0.5)(number) = 2 from numbers(5);
+select quantile_tdigest(0)(number) = 0 from numbers(10000);
+select quantile_tdigest(1)(number) = 9999 from numbers(10000);
+select quantile_tdigest(0.5)(number) > 49000 and quantile_tdigest(0.5)(number) < 51000 from numbers(100000);
+select quantile_tdigest(0.99)(number) > 98900 and quantile_tdigest(0.99)(number) < 99100 from numbers(100000);
@@ -0,0 +1,2 @@
+set max_threads = 8;
+explain fragments select number from numbers_local(10) where (number+1)=4;
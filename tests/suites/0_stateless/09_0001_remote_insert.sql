@@ -4,6 +4,12 @@ USE db1;
 
 CREATE TABLE IF NOT EXISTS t1(a UInt32, b UInt64, c String) Engine = remote ;
 INSERT INTO t1 (a,b,c) values ( 1, 1, '1' ), (2, 2, '"2"-"2"');
+INSERT INTO t1 (a,b,c) values (3 , 3 , '3' ),
+(
+  4,
+  4,
+  '4'
+);
 SELECT * FROM t1;
 
 SELECT sum(a) from t1;